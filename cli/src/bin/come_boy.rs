@@ -8,7 +8,8 @@ use clap::Parser as _;
 use come_boy::game_boy_emulator::{
     self,
     perf::{PerfObserver, PerfStats},
-    GamePak, NullPerfObserver,
+    BmpImageSource, Boot, BootRom, CpuMode, GameBoyModel, GameBoyPrinter, GamePak,
+    NetworkLinkCable, NullPerfObserver, PpuMode, SerialDevice, DEFAULT_LINK_SYNC_CYCLES,
 };
 use come_boy::rendering::{Renderer, RenderingOptions};
 use come_boy::sound::{NullSoundStream, SoundStream};
@@ -25,6 +26,10 @@ struct Frontend {
     unlock_cpu: bool,
     perf_stats: bool,
     game_pak: GamePak<Fs>,
+    boot: Boot,
+    serial_device: Option<Box<dyn SerialDevice + Send>>,
+    ppu_mode: PpuMode,
+    cpu_mode: CpuMode,
     save_state: Option<Vec<u8>>,
     run_until: Option<u64>,
 }
@@ -38,6 +43,10 @@ impl Frontend {
         unlock_cpu: bool,
         perf_stats: bool,
         game_pak: GamePak<Fs>,
+        boot: Boot,
+        serial_device: Option<Box<dyn SerialDevice + Send>>,
+        ppu_mode: PpuMode,
+        cpu_mode: CpuMode,
         save_state: Option<Vec<u8>>,
         run_until: Option<u64>,
     ) -> Self {
@@ -48,6 +57,10 @@ impl Frontend {
             unlock_cpu,
             perf_stats,
             game_pak,
+            boot,
+            serial_device,
+            ppu_mode,
+            cpu_mode,
             save_state,
             run_until,
         }
@@ -64,6 +77,10 @@ impl Frontend {
             sound_stream,
             self.fs,
             self.game_pak,
+            self.boot,
+            self.serial_device.map(|d| d as Box<dyn SerialDevice>),
+            self.ppu_mode,
+            self.cpu_mode,
             self.save_state,
             self.unlock_cpu,
             observer,
@@ -123,6 +140,33 @@ struct Options {
     #[arg(long = "run-until")]
     run_until: Option<u64>,
 
+    #[arg(long = "boot-rom")]
+    boot_rom: Option<PathBuf>,
+
+    #[arg(long = "model", default_value = "dmg", conflicts_with = "boot_rom")]
+    model: GameBoyModel,
+
+    #[arg(long = "link-listen")]
+    link_listen: Option<String>,
+
+    #[arg(long = "link-connect", conflicts_with = "link_listen")]
+    link_connect: Option<String>,
+
+    #[arg(long = "link-sync-cycles", default_value_t = DEFAULT_LINK_SYNC_CYCLES)]
+    link_sync_cycles: u64,
+
+    #[arg(long = "printer", conflicts_with_all = ["link_listen", "link_connect"])]
+    printer: Option<PathBuf>,
+
+    #[arg(long = "pixel-fifo")]
+    pixel_fifo: bool,
+
+    #[arg(long = "m-cycle")]
+    m_cycle: bool,
+
+    #[arg(long = "camera-image")]
+    camera_image: Option<PathBuf>,
+
     #[arg(long = "log-level", default_value = "info")]
     log_level: log::LevelFilter,
 }
@@ -136,7 +180,48 @@ fn main() -> Result<()> {
 
     let mut fs = Fs::new(options.rom.parent());
     let rom_key = Fs::path_to_key(&options.rom)?;
-    let game_pak = GamePak::from_storage(&mut fs, &rom_key)?;
+    let mut game_pak = GamePak::from_storage(&mut fs, &rom_key)?;
+    if let Some(camera_image) = &options.camera_image {
+        let mut file = std::fs::File::open(camera_image)?;
+        game_pak.set_image_source(BmpImageSource::from_reader(&mut file)?);
+    }
+    let boot = match &options.boot_rom {
+        Some(boot_rom) => {
+            Boot::BootRom(BootRom::from_storage(&mut fs, &Fs::path_to_key(boot_rom)?)?)
+        }
+        None => Boot::PostBoot(options.model),
+    };
+    let serial_device: Option<Box<dyn SerialDevice + Send>> =
+        if let Some(address) = &options.link_listen {
+            Some(Box::new(NetworkLinkCable::listen(
+                address,
+                game_pak.hash(),
+                options.link_sync_cycles,
+            )?))
+        } else if let Some(address) = &options.link_connect {
+            Some(Box::new(NetworkLinkCable::connect(
+                address,
+                game_pak.hash(),
+                options.link_sync_cycles,
+            )?))
+        } else if let Some(printer) = &options.printer {
+            Some(Box::new(GameBoyPrinter::new(
+                Fs::new(None),
+                printer.to_string_lossy(),
+            )))
+        } else {
+            None
+        };
+    let ppu_mode = if options.pixel_fifo {
+        PpuMode::PixelFifo
+    } else {
+        PpuMode::Scanline
+    };
+    let cpu_mode = if options.m_cycle {
+        CpuMode::MCycle
+    } else {
+        CpuMode::Instruction
+    };
     let save_state = options.save_state.map(read_save_state).transpose()?;
 
     let rendering_options = RenderingOptions {
@@ -151,6 +236,10 @@ fn main() -> Result<()> {
         options.unlock_cpu,
         options.perf_stats,
         game_pak,
+        boot,
+        serial_device,
+        ppu_mode,
+        cpu_mode,
         save_state,
         options.run_until,
     );
@@ -1,20 +1,147 @@
 // Copyright 2019 Remi Bernotavicius
 
-use come_boy::game_boy_emulator::{GamePak, Result};
+use come_boy::game_boy_emulator::{
+    CartridgeHeader, CgbSupport, Checksum, Destination, GamePak, Result,
+};
 use come_boy::storage::fs::Fs;
+use std::fmt;
 use std::path::PathBuf;
 
 #[derive(clap::Args)]
 #[command(about = "Prints information about GamePaks")]
 pub struct Options {
     rom: PathBuf,
+    #[arg(long = "json")]
+    json: bool,
+}
+
+fn or_unknown(name: Option<&str>) -> &str {
+    name.unwrap_or("unknown")
+}
+
+fn size(bytes: Option<usize>) -> String {
+    match bytes {
+        Some(b) if b >= 0x100000 => format!("{} MiB", b / 0x100000),
+        Some(b) => format!("{} KiB", b / 0x400),
+        None => "unknown".into(),
+    }
+}
+
+fn checksum<T: fmt::UpperHex + PartialEq>(checksum: &Checksum<T>) -> String {
+    let width = 2 + 2 * std::mem::size_of::<T>();
+    let expected = format!("{:#0width$X}", checksum.expected);
+    if checksum.verified() {
+        format!("{expected} (ok)")
+    } else {
+        format!(
+            "{expected} (failed, computed {:#0width$X})",
+            checksum.actual
+        )
+    }
+}
+
+fn cgb_support(header: &CartridgeHeader) -> &'static str {
+    match header.cgb_support {
+        CgbSupport::None => "none",
+        CgbSupport::Enhanced => "enhanced",
+        CgbSupport::Only => "only",
+    }
+}
+
+fn destination(header: &CartridgeHeader) -> String {
+    match header.destination {
+        Destination::Japan => "japan".into(),
+        Destination::Overseas => "overseas".into(),
+        Destination::Unknown(v) => format!("unknown ({v:#04X})"),
+    }
+}
+
+fn licensee_code(header: &CartridgeHeader) -> String {
+    match &header.new_licensee_code {
+        Some(code) => code.clone(),
+        None => format!("{:02X}", header.old_licensee_code),
+    }
+}
+
+fn print_human_readable(header: &CartridgeHeader, mapper: &str) {
+    let entry_point: Vec<_> = header
+        .entry_point
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect();
+    println!("title: {}", header.title);
+    if let Some(code) = &header.manufacturer_code {
+        println!("manufacturer code: {code}");
+    }
+    println!("entry point: {}", entry_point.join(" "));
+    println!(
+        "nintendo logo: {}",
+        if header.logo_valid { "ok" } else { "bad" }
+    );
+    println!("cgb support: {}", cgb_support(header));
+    println!(
+        "sgb support: {}",
+        if header.sgb_support { "yes" } else { "no" }
+    );
+    println!(
+        "licensee: {} ({})",
+        licensee_code(header),
+        or_unknown(header.licensee_name())
+    );
+    println!(
+        "cartridge type: {:#04X} ({})",
+        header.cartridge_type,
+        or_unknown(header.cartridge_type_name())
+    );
+    println!(
+        "rom size: {:#04X} ({})",
+        header.rom_size,
+        size(header.rom_size_bytes())
+    );
+    println!(
+        "ram size: {:#04X} ({})",
+        header.ram_size,
+        size(header.ram_size_bytes())
+    );
+    println!("destination: {}", destination(header));
+    println!("mask rom version: {}", header.mask_rom_version);
+    println!("header checksum: {}", checksum(&header.header_checksum));
+    println!("global checksum: {}", checksum(&header.global_checksum));
+    println!("mapper: {mapper}");
+}
+
+fn print_json(header: &CartridgeHeader, mapper: &str) {
+    let mut value = serde_json::to_value(header).unwrap();
+    let fields = value.as_object_mut().unwrap();
+    fields.insert("licensee_name".into(), header.licensee_name().into());
+    fields.insert(
+        "cartridge_type_name".into(),
+        header.cartridge_type_name().into(),
+    );
+    fields.insert("rom_size_bytes".into(), header.rom_size_bytes().into());
+    fields.insert("ram_size_bytes".into(), header.ram_size_bytes().into());
+    fields.insert("mapper".into(), mapper.into());
+    println!("{}", serde_json::to_string_pretty(&value).unwrap());
 }
 
 pub fn main(options: Options) -> Result<()> {
+    let rom = std::fs::read(&options.rom)?;
     let mut fs = Fs::new(options.rom.parent());
-    let rom_key = Fs::path_to_key(&options.rom)?;
-    let game_pak = GamePak::from_storage_without_sav(&mut fs, &rom_key)?;
-    println!("{game_pak:?}");
+
+    // The header is still shown for cartridges that fail to load
+    let (header, mapper) = match GamePak::new(&rom, &mut fs, None) {
+        Ok(game_pak) => (game_pak.header().clone(), format!("{game_pak:?}")),
+        Err(error) => (
+            CartridgeHeader::from_rom(&rom)?,
+            format!("failed to load: {error}"),
+        ),
+    };
+
+    if options.json {
+        print_json(&header, &mapper);
+    } else {
+        print_human_readable(&header, &mapper);
+    }
 
     Ok(())
 }
@@ -0,0 +1,78 @@
+// Copyright 2026 Remi Bernotavicius
+
+use come_boy::game_boy_emulator::{self, GamePak, LinkedGameBoy, Result};
+use come_boy::rendering::bitmap::BitmapRenderer;
+use come_boy::storage::fs::Fs;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+#[command(
+    about = "Runs two emulators connected by a link cable and takes a screenshot of each",
+    rename_all = "kebab-case"
+)]
+pub struct Options {
+    rom_a: PathBuf,
+    rom_b: PathBuf,
+    #[arg(long = "ticks")]
+    ticks: u64,
+    #[arg(long = "replay-a")]
+    replay_a: Option<PathBuf>,
+    #[arg(long = "replay-b")]
+    replay_b: Option<PathBuf>,
+    #[arg(long = "output-a")]
+    output_a: PathBuf,
+    #[arg(long = "output-b")]
+    output_b: PathBuf,
+}
+
+/// The storage keys for one side of the link cable
+struct Keys {
+    rom_dir: Option<PathBuf>,
+    rom: String,
+    replay: Option<String>,
+    output: String,
+}
+
+impl Keys {
+    fn new(rom: &Path, replay: Option<&Path>, output: &Path) -> Result<Self> {
+        Ok(Self {
+            rom_dir: rom.parent().map(Path::to_owned),
+            rom: Fs::path_to_key(rom)?,
+            replay: replay.map(Fs::path_to_key).transpose()?,
+            output: Fs::path_to_key(output)?,
+        })
+    }
+
+    fn game_boy(&self) -> Result<LinkedGameBoy<'_, BitmapRenderer, Fs>> {
+        let mut fs = Fs::new(self.rom_dir.as_deref());
+        let game_pak = GamePak::from_storage_without_sav(&mut fs, &self.rom)?;
+        Ok(LinkedGameBoy {
+            renderer: BitmapRenderer::new(Default::default()),
+            storage: fs,
+            game_pak,
+            replay_key: self.replay.as_deref(),
+            output_key: &self.output,
+        })
+    }
+}
+
+pub fn main(options: Options) -> Result<()> {
+    let keys_a = Keys::new(
+        &options.rom_a,
+        options.replay_a.as_deref(),
+        &options.output_a,
+    )?;
+    let keys_b = Keys::new(
+        &options.rom_b,
+        options.replay_b.as_deref(),
+        &options.output_b,
+    )?;
+
+    game_boy_emulator::run_linked_until_and_take_screenshots(
+        keys_a.game_boy()?,
+        keys_b.game_boy()?,
+        options.ticks,
+    )?;
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ mod coverage;
 mod debugger;
 mod disassembler;
 mod game_pak;
+mod link;
 mod replay;
 mod screenshot;
 mod tandem;
@@ -21,6 +22,7 @@ enum Options {
     Debugger(debugger::Options),
     Disassembler(disassembler::Options),
     GamePak(game_pak::Options),
+    Link(link::Options),
     Replay(replay::Options),
     Screenshot(screenshot::Options),
     Tandem(tandem::Options),
@@ -33,6 +35,7 @@ impl Options {
             Self::Debugger(opts) => debugger::main(opts)?,
             Self::Disassembler(opts) => disassembler::main(opts)?,
             Self::GamePak(opts) => game_pak::main(opts)?,
+            Self::Link(opts) => link::main(opts)?,
             Self::Replay(opts) => replay::main(opts)?,
             Self::Screenshot(opts) => screenshot::main(opts)?,
             Self::Tandem(opts) => tandem::main(opts)?,
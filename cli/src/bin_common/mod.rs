@@ -12,6 +12,7 @@ pub mod frontend;
 #[derive(Debug)]
 pub enum Error {
     Emulator(game_boy_emulator::Error),
+    GamePak(game_boy_emulator::GamePakError),
     Backend(backend::Error),
     Io(std::io::Error),
 }
@@ -22,6 +23,12 @@ impl From<game_boy_emulator::Error> for Error {
     }
 }
 
+impl From<game_boy_emulator::GamePakError> for Error {
+    fn from(error: game_boy_emulator::GamePakError) -> Self {
+        Self::GamePak(error)
+    }
+}
+
 impl From<backend::Error> for Error {
     fn from(error: backend::Error) -> Self {
         Self::Backend(error)
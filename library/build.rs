@@ -27,6 +27,7 @@ struct OpcodeOnDisk {
     args: Vec<String>,
     size: u8,
     duration: Option<u8>,
+    accesses: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone)]
@@ -336,6 +337,7 @@ struct Opcode {
     enum_args: Vec<OpcodeArgument>,
     size: u8,
     duration: u8,
+    accesses: Option<Vec<u8>>,
 }
 
 impl Opcode {
@@ -360,6 +362,7 @@ impl Opcode {
             function_call: OpcodeFunctionCall::new(function, args),
             enum_args,
             duration: on_disk.duration.unwrap_or(0),
+            accesses: on_disk.accesses,
             size: on_disk.size,
         }
     }
@@ -893,6 +896,51 @@ impl OpcodeGenerator {
         ));
     }
 
+    fn generate_access_cycles_fn(&self, tokens: &mut TokenStream) {
+        let enum_name = &self.enum_name;
+
+        fn leaf_tokenize(tree: &OpcodeDispatchTreeNode, tokens: &mut TokenStream) {
+            let opcode = tree.opcode.as_ref().unwrap();
+            let variant_name = Ident::new(&opcode.camel_name, Span::call_site());
+            let accesses = opcode.accesses.as_deref().unwrap_or_default();
+
+            let field_names = opcode
+                .function_call
+                .function
+                .parameters
+                .iter()
+                .map(|n| &n.name);
+            let values = &opcode.enum_args;
+
+            let matches: Vec<_> = field_names
+                .zip(values.iter())
+                .filter(|(_, v)| !v.dynamic())
+                .collect();
+
+            let field_names = matches.iter().map(|(k, _)| k);
+            let values = matches.iter().map(|(_, v)| v);
+
+            tokens.extend(quote!(
+                Self::#variant_name { #(#field_names : #values,)* .. } => { &[#(#accesses),*] }
+            ));
+        }
+
+        let dispatches = self.build_dispatches(leaf_tokenize);
+
+        tokens.extend(quote!(
+            impl #enum_name {
+                /// The M-cycles (counting from the opcode fetch as zero) in which the instruction
+                /// reads or writes memory, in the order it does so.
+                pub fn access_cycles(&self) -> &'static [u8] {
+                    match self {
+                        #( #dispatches, )*
+                        instr => panic!("invalid instruction {:?}", instr)
+                    }
+                }
+            }
+        ));
+    }
+
     fn generate_opcode_printer(&self, tokens: &mut TokenStream) {
         let trait_name = &self.trait_name;
         let printer_name = &self.printer_name;
@@ -977,6 +1025,9 @@ impl OpcodeGenerator {
         self.generate_instruction_enum(tokens);
         self.generate_size_fn(tokens);
         self.generate_duration_fn(tokens);
+        if self.opcodes.iter().any(|o| o.accesses.is_some()) {
+            self.generate_access_cycles_fn(tokens);
+        }
         self.generate_instructions_trait(tokens);
         self.generate_instruction_dispatch(tokens);
         self.generate_opcode_printer(tokens);
@@ -1144,12 +1195,15 @@ fn filter_write<T>((v, mapping_type): &(T, MappingType)) -> Option<&T> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_memory_map_from_mapping(
     type_name: &str,
     generics: syn::Generics,
     where_clause: Option<syn::WhereClause>,
     mapping: &BTreeMap<AddressRange, MemoryMapping>,
     interrupts_enabled: &Option<MemoryMapping>,
+    increment_decrement: &Option<MemoryMapping>,
+    bus_conflicts: &Option<MemoryMapping>,
     mutable: bool,
 ) -> TokenStream {
     let name: Ident = syn::Ident::new(type_name, Span::call_site());
@@ -1212,6 +1266,44 @@ fn generate_memory_map_from_mapping(
         syn::parse_quote!(panic!("unexpected set_interrupts_enabled call"))
     };
 
+    let increment_decrement_body: Option<syn::Expr> = increment_decrement.as_ref().map(|b| {
+        let (f, m) = b.to_field_and_type(true);
+        assert!(matches!(m, MappingType::ReadWrite));
+        syn::parse_quote!(MemoryMappedHardware::increment_decrement(#f, address, access))
+    });
+    let increment_decrement_fn = increment_decrement_body.map(|body| {
+        quote!(
+            #[cfg_attr(feature = "aggressive-inline", inline(always))]
+            fn increment_decrement(
+                &mut self,
+                address: u16,
+                access: #memory_controller::BusAccess,
+            ) {
+                #body
+            }
+        )
+    });
+
+    let bus_conflict: Option<syn::Expr> = bus_conflicts.as_ref().map(|b| {
+        let (f, m) = b.to_field_and_type(false);
+        assert!(matches!(m, MappingType::ReadWrite));
+        syn::parse_quote!(MemoryMappedHardware::bus_conflict(#f, address))
+    });
+    let read_bus_conflict = bus_conflict.as_ref().map(|c| {
+        quote!(
+            if let Some(value) = #c {
+                return value;
+            }
+        )
+    });
+    let set_bus_conflict = bus_conflict.as_ref().filter(|_| mutable).map(|c| {
+        quote!(
+            if #c.is_some() {
+                return;
+            }
+        )
+    });
+
     quote!(
         use super::#name;
 
@@ -1219,6 +1311,7 @@ fn generate_memory_map_from_mapping(
             #[allow(clippy::identity_op, clippy::if_same_then_else)]
             #[cfg_attr(feature = "aggressive-inline", inline(always))]
             fn read_memory(&self, address: u16) -> u8 {
+                #read_bus_conflict
                 #(if #read_condition {
                     MemoryMappedHardware::read_value(#read_expr, address - #read_offset)
                 }) else *
@@ -1230,6 +1323,7 @@ fn generate_memory_map_from_mapping(
             #[allow(unused_variables, clippy::identity_op, clippy::if_same_then_else)]
             #[cfg_attr(feature = "aggressive-inline", inline(always))]
             fn set_memory(&mut self, address: u16, value: u8) {
+                #set_bus_conflict
                 #set_memory_body
             }
 
@@ -1242,6 +1336,8 @@ fn generate_memory_map_from_mapping(
             fn describe_address(&self, _address: u16) -> #memory_controller::MemoryDescription {
                 #memory_controller::MemoryDescription::Instruction
             }
+
+            #increment_decrement_fn
         }
     )
 }
@@ -1260,6 +1356,8 @@ fn generate_memory_map(
         serde_json::from_reader(File::open(&memory_map_json).unwrap()).unwrap();
 
     let mut interrupts_enabled = mapping.remove("interrupts_enabled");
+    let mut increment_decrement = mapping.remove("increment_decrement");
+    let bus_conflicts = mapping.remove("bus_conflicts");
     let mapping: BTreeMap<AddressRange, MemoryMapping> = mapping
         .into_iter()
         .map(|(k, v)| (k.parse().unwrap(), v))
@@ -1267,6 +1365,7 @@ fn generate_memory_map(
 
     if !mutable {
         interrupts_enabled = None;
+        increment_decrement = None;
     }
 
     let mut tokens = TokenStream::new();
@@ -1283,6 +1382,8 @@ fn generate_memory_map(
         where_clause,
         &mapping,
         &interrupts_enabled,
+        &increment_decrement,
+        &bus_conflicts,
         mutable,
     ));
 
@@ -12,6 +12,15 @@ pub enum MemoryDescription {
     Ascii(u16),
 }
 
+/// What else the CPU is doing with the address bus while its 16-bit increment / decrement unit
+/// has put an address on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusAccess {
+    Idle,
+    Read,
+    Write,
+}
+
 pub trait MemoryAccessor {
     fn read_memory(&self, address: u16) -> u8;
     fn set_memory(&mut self, address: u16, value: u8);
@@ -22,7 +31,10 @@ pub trait MemoryAccessor {
             return self.read_memory(address) as u16;
         }
 
-        (self.read_memory(address + 1) as u16) << 8 | (self.read_memory(address) as u16)
+        // The CPU reads the low byte first, and the timing of each read can matter.
+        let low = self.read_memory(address);
+        let high = self.read_memory(address + 1);
+        u16::from_le_bytes([low, high])
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -39,6 +51,11 @@ pub trait MemoryAccessor {
     fn set_interrupts_enabled(&mut self, enabled: bool);
 
     fn describe_address(&self, address: u16) -> MemoryDescription;
+
+    /// Called when the 16-bit increment / decrement unit puts the given address on the bus. Most
+    /// memory doesn't notice this, so by default it does nothing.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn increment_decrement(&mut self, _address: u16, _access: BusAccess) {}
 }
 
 impl MemoryAccessor for &dyn MemoryAccessor {
@@ -0,0 +1,314 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::memory_controller::MemoryMappedHardware;
+use super::{Error, Result};
+use crate::io::Read as _;
+use crate::storage::{OpenMode, PersistentStorage};
+use alloc::{vec, vec::Vec};
+use core::{fmt, str};
+use serde_derive::{Deserialize, Serialize};
+
+/// The DMG boot ROM is overlaid on top of the first 256 bytes of the cartridge.
+pub const BOOT_ROM_SIZE: usize = 0x100;
+
+/// The models of Game Boy whose boot ROMs leave the CPU registers in different states.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GameBoyModel {
+    /// The original Japanese DMG boot ROM
+    Dmg0,
+    #[default]
+    Dmg,
+    /// Game Boy Pocket
+    Mgb,
+    /// Super Game Boy
+    Sgb,
+}
+
+impl GameBoyModel {
+    /// The AF, BC, DE and HL register pairs as the boot ROM leaves them when it jumps to the
+    /// cartridge.
+    pub(super) fn post_boot_registers(self) -> [u16; 4] {
+        match self {
+            Self::Dmg0 => [0x0100, 0xFF13, 0x00C1, 0x8403],
+            Self::Dmg => [0x01B0, 0x0013, 0x00D8, 0x014D],
+            Self::Mgb => [0xFFB0, 0x0013, 0x00D8, 0x014D],
+            Self::Sgb => [0x0100, 0x0014, 0x0000, 0xC060],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseGameBoyModelError;
+
+impl fmt::Display for ParseGameBoyModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected one of dmg0, dmg, mgb or sgb")
+    }
+}
+
+impl core::error::Error for ParseGameBoyModelError {}
+
+impl str::FromStr for GameBoyModel {
+    type Err = ParseGameBoyModelError;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "dmg0" => Ok(Self::Dmg0),
+            "dmg" => Ok(Self::Dmg),
+            "mgb" => Ok(Self::Mgb),
+            "sgb" => Ok(Self::Sgb),
+            _ => Err(ParseGameBoyModelError),
+        }
+    }
+}
+
+/// How the emulator gets to the point of running the cartridge.
+#[derive(Debug, Clone)]
+pub enum Boot {
+    /// Start from power-on and run the given boot ROM.
+    BootRom(BootRom),
+    /// Skip the boot ROM and start in the state the given model's boot ROM leaves behind.
+    PostBoot(GameBoyModel),
+}
+
+impl Default for Boot {
+    fn default() -> Self {
+        Self::PostBoot(GameBoyModel::default())
+    }
+}
+
+/// An image of the boot ROM, which runs when the Game Boy is switched on.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BootRom {
+    data: Vec<u8>,
+}
+
+impl fmt::Debug for BootRom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BootRom")
+    }
+}
+
+impl BootRom {
+    pub fn new(data: &[u8]) -> Result<Self> {
+        if data.len() != BOOT_ROM_SIZE {
+            return Err(Error::WrongBootRomLength {
+                expected: BOOT_ROM_SIZE,
+                actual: data.len(),
+            });
+        }
+        Ok(Self {
+            data: data.to_vec(),
+        })
+    }
+
+    pub fn from_storage(storage: &mut impl PersistentStorage, key: &str) -> Result<Self> {
+        let mut file = storage.open(OpenMode::Read, key)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+        Self::new(&data)
+    }
+}
+
+/// The boot ROM as it appears on the memory bus. It stays mapped over the cartridge until
+/// something is written to 0xFF50.
+#[derive(Default, Serialize, Deserialize)]
+pub(super) struct BootRomOverlay {
+    boot_rom: Option<BootRom>,
+}
+
+impl BootRomOverlay {
+    pub(super) fn map(&mut self, boot_rom: BootRom) {
+        self.boot_rom = Some(boot_rom);
+    }
+
+    pub(super) fn is_mapped(&self) -> bool {
+        self.boot_rom.is_some()
+    }
+}
+
+/// This implementation is for the 0xFF50 register, which can only be written to.
+impl MemoryMappedHardware for BootRomOverlay {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        assert_eq!(address, 0);
+        0xFF
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_value(&mut self, address: u16, value: u8) {
+        assert_eq!(address, 0);
+        // Once unmapped, the boot ROM can't be mapped back in until power is cycled.
+        if value != 0 {
+            self.boot_rom = None;
+        }
+    }
+}
+
+/// This implementation is where reads for the bottom of the cartridge go
+impl<'a, T: MemoryMappedHardware> MemoryMappedHardware for (&'a BootRomOverlay, &'a T) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        let (overlay, game_pak) = self;
+        match &overlay.boot_rom {
+            Some(boot_rom) => boot_rom.data[address as usize],
+            None => game_pak.read_value(address),
+        }
+    }
+
+    fn set_value(&mut self, _address: u16, _value: u8) {
+        unreachable!()
+    }
+}
+
+/// This implementation is where writes for the bottom of the cartridge go. The boot ROM is read
+/// only, so they always make it to the cartridge.
+impl<'a, T: MemoryMappedHardware> MemoryMappedHardware for (&'a mut BootRomOverlay, &'a mut T) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        let (overlay, game_pak) = self;
+        match &overlay.boot_rom {
+            Some(boot_rom) => boot_rom.data[address as usize],
+            None => game_pak.read_value(address),
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_value(&mut self, address: u16, value: u8) {
+        let (_overlay, game_pak) = self;
+        game_pak.set_value(address, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{
+        lcd_controller::LcdControlFlag, GameBoyEmulator, GamePak, MemoryAccessor as _,
+        NullGameBoyOps,
+    };
+    use crate::lr35902_emulator::Intel8080Register;
+
+    fn test_boot_rom() -> BootRom {
+        let mut data = vec![0x00; BOOT_ROM_SIZE];
+        let program = [
+            0x31, 0xFE, 0xFF, // LD SP, 0xFFFE
+            0x3E, 0x42, // LD A, 0x42
+            0xE0, 0x80, // LDH (0x80), A
+            0x3E, 0x91, // LD A, 0x91
+            0xE0, 0x40, // LDH (0x40), A
+            0xC3, 0xFC, 0x00, // JP 0x00FC
+        ];
+        data[..program.len()].copy_from_slice(&program);
+        data[0xFC..].copy_from_slice(&[
+            0x3E, 0x01, // LD A, 0x01
+            0xE0, 0x50, // LDH (0x50), A
+        ]);
+        BootRom::new(&data).unwrap()
+    }
+
+    fn load_test_game_pak(ops: &mut NullGameBoyOps) {
+        let mut rom = vec![0x00; 0x8000];
+        rom[0x0000] = 0xAB;
+        rom[0x0100..0x0102].copy_from_slice(&[0x18, 0xFE]); // JR -2
+        let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+        ops.load_game_pak(game_pak);
+    }
+
+    #[test]
+    fn boot_rom_runs_from_power_on() {
+        let mut ops = NullGameBoyOps::null();
+        load_test_game_pak(&mut ops);
+        let mut e = GameBoyEmulator::new_with_boot(Boot::BootRom(test_boot_rom()));
+
+        assert!(e.running_boot_rom());
+        assert_eq!(e.cpu.read_program_counter(), 0x0000);
+        assert_eq!(ops.memory_map(&e.bridge).read_memory(0x0000), 0x31);
+        assert!(!e
+            .bridge
+            .lcd_controller
+            .registers
+            .lcdc
+            .read_flag(LcdControlFlag::DisplayOn));
+
+        while e.cpu.read_program_counter() != 0x0100 {
+            assert!(e.elapsed_cycles() < 1_000, "boot ROM never finished");
+            e.tick(&mut ops);
+        }
+
+        assert!(!e.running_boot_rom());
+        let memory_map = ops.memory_map(&e.bridge);
+        assert_eq!(memory_map.read_memory(0x0000), 0xAB);
+        assert_eq!(memory_map.read_memory(0xFF80), 0x42);
+        assert_eq!(memory_map.read_memory(0xFF40), 0x91);
+        assert_eq!(memory_map.read_memory(0xFF50), 0xFF);
+    }
+
+    #[test]
+    fn boot_rom_stays_unmapped() {
+        let mut ops = NullGameBoyOps::null();
+        load_test_game_pak(&mut ops);
+        let mut e = GameBoyEmulator::new_with_boot(Boot::BootRom(test_boot_rom()));
+
+        let mut memory_map = ops.memory_map_mut(&mut e.bridge);
+        memory_map.set_memory(0xFF50, 0x00);
+        assert_eq!(memory_map.read_memory(0x0000), 0x31);
+        memory_map.set_memory(0xFF50, 0x01);
+        assert_eq!(memory_map.read_memory(0x0000), 0xAB);
+        memory_map.set_memory(0xFF50, 0x00);
+        assert_eq!(memory_map.read_memory(0x0000), 0xAB);
+    }
+
+    #[test]
+    fn boot_rom_wrong_length() {
+        assert!(matches!(
+            BootRom::new(&[0; 0x101]),
+            Err(Error::WrongBootRomLength {
+                expected: 0x100,
+                actual: 0x101
+            })
+        ));
+    }
+
+    #[test]
+    fn post_boot_registers() {
+        let registers = |model| {
+            let e = GameBoyEmulator::new_with_boot(Boot::PostBoot(model));
+            [
+                Intel8080Register::A,
+                Intel8080Register::B,
+                Intel8080Register::D,
+                Intel8080Register::H,
+                Intel8080Register::SP,
+            ]
+            .map(|r| e.cpu.read_register_pair(r))
+        };
+
+        assert_eq!(
+            registers(GameBoyModel::Dmg0),
+            [0x0100, 0xFF13, 0x00C1, 0x8403, 0xFFFE]
+        );
+        assert_eq!(
+            registers(GameBoyModel::Dmg),
+            [0x01B0, 0x0013, 0x00D8, 0x014D, 0xFFFE]
+        );
+        assert_eq!(
+            registers(GameBoyModel::Mgb),
+            [0xFFB0, 0x0013, 0x00D8, 0x014D, 0xFFFE]
+        );
+        assert_eq!(
+            registers(GameBoyModel::Sgb),
+            [0x0100, 0x0014, 0x0000, 0xC060, 0xFFFE]
+        );
+
+        let e = GameBoyEmulator::new();
+        assert_eq!(e.cpu.read_program_counter(), 0x0100);
+        assert!(!e.running_boot_rom());
+    }
+
+    #[test]
+    fn parse_model() {
+        assert_eq!("mgb".parse::<GameBoyModel>().unwrap(), GameBoyModel::Mgb);
+        assert!("cgb".parse::<GameBoyModel>().is_err());
+    }
+}
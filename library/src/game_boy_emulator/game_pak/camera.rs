@@ -0,0 +1,241 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::image_source::{CAMERA_SENSOR_HEIGHT, CAMERA_SENSOR_WIDTH};
+use super::{
+    BankOps, CartridgeRam, MemoryBankController, MemoryMappedBank, RomBank, SwitchableBank,
+};
+use crate::storage::PersistentStorage;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+/// Selecting this RAM bank maps the camera registers instead of RAM.
+const REGISTERS_BANK: usize = 0x10;
+
+const REGISTER_CONTROL: usize = 0x00;
+const REGISTER_EDGE_AND_GAIN: usize = 0x01;
+const REGISTER_EXPOSURE_HIGH: usize = 0x02;
+const REGISTER_EXPOSURE_LOW: usize = 0x03;
+const REGISTER_EDGE_RATIO_AND_INVERT: usize = 0x04;
+const REGISTER_DITHER_MATRIX: usize = 0x06;
+const NUMBER_OF_REGISTERS: usize = 0x36;
+
+const CONTROL_CAPTURE: u8 = 0x01;
+const EDGE_EXCLUSIVE: u8 = 0x80;
+const EDGE_HORIZONTAL: u8 = 0x20;
+const EDGE_VERTICAL: u8 = 0x40;
+const GAIN_MASK: u8 = 0x1F;
+const INVERT: u8 = 0x08;
+
+/// The edge enhancement ratios, in quarters.
+const EDGE_RATIOS: [i32; 8] = [2, 3, 4, 5, 8, 12, 16, 20];
+
+/// Where in RAM bank 0 the captured picture is written, as 16x14 tiles in the same format as the
+/// LCD's character data.
+const IMAGE_ADDRESS: u16 = 0x0100;
+
+/// The Pocket Camera mapper. It is like MBC3 without the clock, with 128kB of RAM and the
+/// registers of the M64282FP image sensor behind RAM bank 0x10.
+///
+/// A picture is taken as soon as the game asks for it, rather than after the exposure time would
+/// have passed, so the game never sees the camera being busy.
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankControllerCamera {
+    ram_enable: bool,
+    registers_select: bool,
+    registers: Vec<u8>,
+    switchable_bank: SwitchableBank<RomBank>,
+    ram: CartridgeRam,
+}
+
+impl From<MemoryBankControllerCamera> for MemoryBankController {
+    fn from(c: MemoryBankControllerCamera) -> Self {
+        Self::Camera(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankControllerCamera {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "POCKET CAMERA{:?}", self.ram)
+    }
+}
+
+impl MemoryBankControllerCamera {
+    pub(super) fn new<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
+        Self {
+            ram_enable: false,
+            registers_select: false,
+            registers: vec![0; NUMBER_OF_REGISTERS],
+            switchable_bank: SwitchableBank::new(banks, 1),
+            ram: ram.into(),
+        }
+    }
+
+    fn exposure(&self) -> i32 {
+        ((self.registers[REGISTER_EXPOSURE_HIGH] as i32) << 8)
+            | self.registers[REGISTER_EXPOSURE_LOW] as i32
+    }
+
+    /// Apply the exposure time and gain to the brightness of every pixel. An exposure of 0x1000
+    /// with no gain leaves it unchanged.
+    fn expose(&self, pixels: &[u8]) -> Vec<i32> {
+        let exposure = self.exposure();
+        let gain = (self.registers[REGISTER_EDGE_AND_GAIN] & GAIN_MASK) as i32 + 0x10;
+        pixels
+            .iter()
+            .map(|&p| p as i32 * exposure / 0x1000 * gain / 0x10)
+            .collect()
+    }
+
+    /// Enhance the edges by adding to each pixel how much brighter it is than its neighbours,
+    /// along the directions the registers select.
+    fn enhance_edges(&self, exposed: &[i32], x: usize, y: usize) -> i32 {
+        let pixel = |x: usize, y: usize| exposed[y * CAMERA_SENSOR_WIDTH + x];
+        let color = pixel(x, y);
+
+        let edge = self.registers[REGISTER_EDGE_AND_GAIN];
+        if edge & EDGE_EXCLUSIVE == 0 {
+            return color;
+        }
+
+        let mut neighbours = vec![];
+        if edge & EDGE_HORIZONTAL != 0 {
+            neighbours.push(pixel(x.saturating_sub(1), y));
+            neighbours.push(pixel((x + 1).min(CAMERA_SENSOR_WIDTH - 1), y));
+        }
+        if edge & EDGE_VERTICAL != 0 {
+            neighbours.push(pixel(x, y.saturating_sub(1)));
+            neighbours.push(pixel(x, (y + 1).min(CAMERA_SENSOR_HEIGHT - 1)));
+        }
+
+        let ratio =
+            EDGE_RATIOS[(self.registers[REGISTER_EDGE_RATIO_AND_INVERT] >> 4) as usize & 0x7];
+        let difference: i32 = neighbours.iter().map(|n| color - n).sum();
+        color + difference * ratio / 4
+    }
+
+    /// Turn a brightness into one of the four shades using the thresholds in the dither matrix
+    /// for the given position. Shade 3 is the darkest.
+    fn dither(&self, color: i32, x: usize, y: usize) -> u8 {
+        let entry = REGISTER_DITHER_MATRIX + ((x & 3) + (y & 3) * 4) * 3;
+        let thresholds = &self.registers[entry..entry + 3];
+        if color < thresholds[0] as i32 {
+            3
+        } else if color < thresholds[1] as i32 {
+            2
+        } else if color < thresholds[2] as i32 {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn capture(&mut self, ops: &mut BankOps<impl PersistentStorage>) {
+        let mut pixels = vec![0; CAMERA_SENSOR_WIDTH * CAMERA_SENSOR_HEIGHT];
+        ops.image_source.capture(&mut pixels);
+        let exposed = self.expose(&pixels);
+        let invert = self.registers[REGISTER_EDGE_RATIO_AND_INVERT] & INVERT != 0;
+
+        let mut shades = vec![0; pixels.len()];
+        for y in 0..CAMERA_SENSOR_HEIGHT {
+            for x in 0..CAMERA_SENSOR_WIDTH {
+                let mut color = self.enhance_edges(&exposed, x, y).clamp(0, 0xFF);
+                if invert {
+                    color = 0xFF - color;
+                }
+                shades[y * CAMERA_SENSOR_WIDTH + x] = self.dither(color, x, y);
+            }
+        }
+
+        let mut tile_data = vec![];
+        for tile_y in 0..CAMERA_SENSOR_HEIGHT / 8 {
+            for tile_x in 0..CAMERA_SENSOR_WIDTH / 8 {
+                for y in tile_y * 8..tile_y * 8 + 8 {
+                    let row = &shades[y * CAMERA_SENSOR_WIDTH + tile_x * 8..][..8];
+                    let bit_plane = |plane: u8| {
+                        row.iter()
+                            .fold(0, |byte, shade| (byte << 1) | ((shade >> plane) & 1))
+                    };
+                    tile_data.push(bit_plane(0));
+                    tile_data.push(bit_plane(1));
+                }
+            }
+        }
+
+        let ram_bank = self.ram.current_bank();
+        self.ram.switch_bank(0);
+        for (address, value) in (IMAGE_ADDRESS..).zip(tile_data) {
+            self.ram.set_bank_value(ops, address, value);
+        }
+        self.ram.switch_bank(ram_bank);
+    }
+
+    fn write_register(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        let register = (address & 0x7F) as usize;
+        if register >= NUMBER_OF_REGISTERS {
+            return;
+        }
+
+        self.registers[register] = value;
+        if register == REGISTER_CONTROL && value & CONTROL_CAPTURE != 0 {
+            self.capture(ops);
+            self.registers[REGISTER_CONTROL] &= !CONTROL_CAPTURE;
+        }
+    }
+}
+
+impl MemoryMappedBank for MemoryBankControllerCamera {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else if self.registers_select {
+            // Only the control register can be read, and it is mirrored every 0x80 bytes
+            if address & 0x7F == REGISTER_CONTROL as u16 {
+                self.registers[REGISTER_CONTROL]
+            } else {
+                0x00
+            }
+        } else {
+            // Unlike other MBCs, RAM can be read without being enabled
+            self.ram.read_bank_value(ops, address - 0xA000)
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0x2000 {
+            // RAM Enable
+            self.ram_enable = (value & 0x0F) == 0x0A;
+        } else if address < 0x4000 {
+            // Select ROM bank. Bank 0 can be mapped in to the switchable region.
+            self.switchable_bank
+                .switch_bank_wrapping((value & 0x3F) as usize);
+        } else if address < 0x6000 {
+            // Select RAM bank or the camera registers
+            let bank = value as usize & 0x1F;
+            self.registers_select = bank & REGISTERS_BANK != 0;
+            if !self.registers_select {
+                self.ram.switch_bank(bank);
+            }
+        } else if address < 0xA000 {
+            // nothing
+        } else if self.registers_select {
+            self.write_register(ops, address - 0xA000, value);
+        } else if self.ram_enable {
+            self.ram.set_bank_value(ops, address - 0xA000, value);
+        }
+    }
+}
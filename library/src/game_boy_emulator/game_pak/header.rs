@@ -0,0 +1,460 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{
+    GamePakError, BANK_SIZE, LOGO, MBC_TYPE_ADDRESS, NINTENDO_LOGO, RAM_SIZE_ADDRESS,
+    ROM_SIZE_ADDRESS, TITLE,
+};
+use alloc::string::String;
+use core::ops::Range;
+use core::str;
+use serde_derive::Serialize;
+
+const ENTRY_POINT: Range<usize> = 0x0100..0x0104;
+const MANUFACTURER_CODE: Range<usize> = 0x013F..0x0143;
+const CGB_FLAG_ADDRESS: usize = 0x0143;
+const NEW_LICENSEE_CODE: Range<usize> = 0x0144..0x0146;
+const SGB_FLAG_ADDRESS: usize = 0x0146;
+const DESTINATION_ADDRESS: usize = 0x014A;
+const OLD_LICENSEE_CODE_ADDRESS: usize = 0x014B;
+const MASK_ROM_VERSION_ADDRESS: usize = 0x014C;
+const HEADER_CHECKSUM_ADDRESS: usize = 0x014D;
+const GLOBAL_CHECKSUM: Range<usize> = 0x014E..0x0150;
+
+/// The header checksum covers the title through the mask ROM version.
+const HEADER_CHECKSUM_RANGE: Range<usize> = 0x0134..0x014D;
+
+/// This old licensee code means the new licensee code should be used instead.
+const USE_NEW_LICENSEE_CODE: u8 = 0x33;
+
+/// Whether the game makes use of the Game Boy Color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CgbSupport {
+    None,
+    /// Works on any Game Boy, but has extra features on the Game Boy Color.
+    Enhanced,
+    /// Only works on the Game Boy Color.
+    Only,
+}
+
+/// Where the cartridge was meant to be sold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+    Japan,
+    Overseas,
+    Unknown(u8),
+}
+
+/// A checksum from the header, along with the one computed from the ROM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Checksum<T> {
+    pub expected: T,
+    pub actual: T,
+}
+
+impl<T: PartialEq> Checksum<T> {
+    pub fn verified(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// The cartridge header found at 0x0100-0x014F of the ROM, describing the game and the hardware
+/// in the cartridge.
+#[derive(Clone, Debug, Serialize)]
+pub struct CartridgeHeader {
+    pub entry_point: [u8; 4],
+    /// If the Nintendo logo is intact. The boot ROM refuses to start the game otherwise.
+    pub logo_valid: bool,
+    pub title: String,
+    pub manufacturer_code: Option<String>,
+    pub cgb_support: CgbSupport,
+    pub sgb_support: bool,
+    pub old_licensee_code: u8,
+    /// Only used when the old licensee code is 0x33.
+    pub new_licensee_code: Option<String>,
+    pub cartridge_type: u8,
+    pub rom_size: u8,
+    pub ram_size: u8,
+    pub destination: Destination,
+    pub mask_rom_version: u8,
+    pub header_checksum: Checksum<u8>,
+    pub global_checksum: Checksum<u16>,
+}
+
+impl CartridgeHeader {
+    /// Parse the header out of the start of the given ROM. The global checksum is computed over
+    /// the whole thing.
+    pub fn from_rom(rom: &[u8]) -> Result<Self, GamePakError> {
+        if rom.len() < GLOBAL_CHECKSUM.end {
+            return Err(GamePakError::WrongRomLength {
+                expected: GLOBAL_CHECKSUM.end,
+                actual: rom.len(),
+            });
+        }
+
+        let cgb_support = match rom[CGB_FLAG_ADDRESS] {
+            0xC0 => CgbSupport::Only,
+            v if v & 0x80 != 0 => CgbSupport::Enhanced,
+            _ => CgbSupport::None,
+        };
+
+        // Newer cartridges have a shorter title followed by a four letter manufacturer code, but
+        // nothing marks which kind a cartridge is, so guess based on what the bytes look like.
+        let manufacturer_code = &rom[MANUFACTURER_CODE];
+        let has_manufacturer_code = cgb_support != CgbSupport::None
+            && manufacturer_code
+                .iter()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        let title = if has_manufacturer_code {
+            &rom[TITLE.start..MANUFACTURER_CODE.start]
+        } else if cgb_support != CgbSupport::None {
+            &rom[TITLE.start..CGB_FLAG_ADDRESS]
+        } else {
+            &rom[TITLE]
+        };
+        let title_end = title
+            .iter()
+            .position(|&c| c == b'\0')
+            .unwrap_or(title.len());
+        let title = str::from_utf8(&title[..title_end])
+            .map_err(|_| GamePakError::MalformedTitle(title.to_vec()))?;
+
+        let old_licensee_code = rom[OLD_LICENSEE_CODE_ADDRESS];
+        let new_licensee_code = (old_licensee_code == USE_NEW_LICENSEE_CODE)
+            .then(|| String::from_utf8_lossy(&rom[NEW_LICENSEE_CODE]).into());
+
+        let header_checksum = rom[HEADER_CHECKSUM_RANGE]
+            .iter()
+            .fold(0u8, |x, &b| x.wrapping_sub(b).wrapping_sub(1));
+        let global_checksum = rom
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !GLOBAL_CHECKSUM.contains(i))
+            .fold(0u16, |x, (_, &b)| x.wrapping_add(b as u16));
+
+        Ok(Self {
+            entry_point: rom[ENTRY_POINT].try_into().unwrap(),
+            logo_valid: rom[LOGO] == NINTENDO_LOGO,
+            title: title.into(),
+            manufacturer_code: has_manufacturer_code
+                .then(|| String::from_utf8_lossy(manufacturer_code).into()),
+            cgb_support,
+            sgb_support: rom[SGB_FLAG_ADDRESS] == 0x03,
+            old_licensee_code,
+            new_licensee_code,
+            cartridge_type: rom[MBC_TYPE_ADDRESS],
+            rom_size: rom[ROM_SIZE_ADDRESS],
+            ram_size: rom[RAM_SIZE_ADDRESS],
+            destination: match rom[DESTINATION_ADDRESS] {
+                0x00 => Destination::Japan,
+                0x01 => Destination::Overseas,
+                v => Destination::Unknown(v),
+            },
+            mask_rom_version: rom[MASK_ROM_VERSION_ADDRESS],
+            header_checksum: Checksum {
+                expected: rom[HEADER_CHECKSUM_ADDRESS],
+                actual: header_checksum,
+            },
+            global_checksum: Checksum {
+                expected: u16::from_be_bytes(rom[GLOBAL_CHECKSUM].try_into().unwrap()),
+                actual: global_checksum,
+            },
+        })
+    }
+
+    /// The name of the company that published the game, if we know it.
+    pub fn licensee_name(&self) -> Option<&'static str> {
+        match &self.new_licensee_code {
+            Some(code) => new_licensee_name(code),
+            None => old_licensee_name(self.old_licensee_code),
+        }
+    }
+
+    /// What hardware the cartridge has, if we know the cartridge type.
+    pub fn cartridge_type_name(&self) -> Option<&'static str> {
+        cartridge_type_name(self.cartridge_type)
+    }
+
+    pub fn rom_size_bytes(&self) -> Option<usize> {
+        Some(number_of_rom_banks(self.rom_size)? * BANK_SIZE as usize)
+    }
+
+    pub fn ram_size_bytes(&self) -> Option<usize> {
+        match self.ram_size {
+            0 => Some(0),
+            1 => Some(0x800),
+            2 => Some(0x2000),
+            3 => Some(0x8000),
+            4 => Some(0x20000),
+            5 => Some(0x10000),
+            _ => None,
+        }
+    }
+}
+
+pub(super) fn number_of_rom_banks(rom_size: u8) -> Option<usize> {
+    Some(match rom_size {
+        n if n <= 0x08 => 2usize << n,
+        0x52 => 72,
+        0x53 => 80,
+        0x54 => 96,
+        _ => return None,
+    })
+}
+
+fn cartridge_type_name(cartridge_type: u8) -> Option<&'static str> {
+    Some(match cartridge_type {
+        0x00 => "ROM ONLY",
+        0x01 => "MBC1",
+        0x02 => "MBC1+RAM",
+        0x03 => "MBC1+RAM+BATTERY",
+        0x05 => "MBC2",
+        0x06 => "MBC2+BATTERY",
+        0x08 => "ROM+RAM",
+        0x09 => "ROM+RAM+BATTERY",
+        0x0B => "MMM01",
+        0x0C => "MMM01+RAM",
+        0x0D => "MMM01+RAM+BATTERY",
+        0x0F => "MBC3+TIMER+BATTERY",
+        0x10 => "MBC3+TIMER+RAM+BATTERY",
+        0x11 => "MBC3",
+        0x12 => "MBC3+RAM",
+        0x13 => "MBC3+RAM+BATTERY",
+        0x19 => "MBC5",
+        0x1A => "MBC5+RAM",
+        0x1B => "MBC5+RAM+BATTERY",
+        0x1C => "MBC5+RUMBLE",
+        0x1D => "MBC5+RUMBLE+RAM",
+        0x1E => "MBC5+RUMBLE+RAM+BATTERY",
+        0x20 => "MBC6",
+        0x22 => "MBC7+SENSOR+RUMBLE+RAM+BATTERY",
+        0xFC => "POCKET CAMERA",
+        0xFD => "BANDAI TAMA5",
+        0xFE => "HuC3",
+        0xFF => "HuC1+RAM+BATTERY",
+        _ => return None,
+    })
+}
+
+fn new_licensee_name(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "00" => "None",
+        "01" => "Nintendo Research & Development 1",
+        "08" => "Capcom",
+        "13" => "EA (Electronic Arts)",
+        "18" => "Hudson Soft",
+        "19" => "B-AI",
+        "20" => "KSS",
+        "22" => "Planning Office WADA",
+        "24" => "PCM Complete",
+        "25" => "San-X",
+        "28" => "Kemco",
+        "29" => "SETA Corporation",
+        "30" => "Viacom",
+        "31" => "Nintendo",
+        "32" => "Bandai",
+        "33" => "Ocean Software/Acclaim Entertainment",
+        "34" => "Konami",
+        "35" => "HectorSoft",
+        "37" => "Taito",
+        "38" => "Hudson Soft",
+        "39" => "Banpresto",
+        "41" => "Ubi Soft",
+        "42" => "Atlus",
+        "44" => "Malibu Interactive",
+        "46" => "Angel",
+        "47" => "Bullet-Proof Software",
+        "49" => "Irem",
+        "50" => "Absolute",
+        "51" => "Acclaim Entertainment",
+        "52" => "Activision",
+        "53" => "Sammy USA Corporation",
+        "54" => "Konami",
+        "55" => "Hi Tech Expressions",
+        "56" => "LJN",
+        "57" => "Matchbox",
+        "58" => "Mattel",
+        "59" => "Milton Bradley Company",
+        "60" => "Titus Interactive",
+        "61" => "Virgin Games Ltd.",
+        "64" => "Lucasfilm Games",
+        "67" => "Ocean Software",
+        "69" => "EA (Electronic Arts)",
+        "70" => "Infogrames",
+        "71" => "Interplay Entertainment",
+        "72" => "Broderbund",
+        "73" => "Sculptured Software",
+        "75" => "The Sales Curve Limited",
+        "78" => "THQ",
+        "79" => "Accolade",
+        "80" => "Misawa Entertainment",
+        "83" => "LOZC G.",
+        "86" => "Tokuma Shoten",
+        "87" => "Tsukuda Original",
+        "91" => "Chunsoft Co.",
+        "92" => "Video System",
+        "93" => "Ocean Software/Acclaim Entertainment",
+        "95" => "Varie",
+        "96" => "Yonezawa/S'Pal",
+        "97" => "Kaneko",
+        "99" => "Pack-In-Video",
+        "9H" => "Bottom Up",
+        "A4" => "Konami (Yu-Gi-Oh!)",
+        "BL" => "MTO",
+        "DK" => "Kodansha",
+        _ => return None,
+    })
+}
+
+fn old_licensee_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x00 => "None",
+        0x01 => "Nintendo",
+        0x08 => "Capcom",
+        0x09 => "HOT-B",
+        0x0A => "Jaleco",
+        0x0B => "Coconuts Japan",
+        0x0C => "Elite Systems",
+        0x13 => "EA (Electronic Arts)",
+        0x18 => "Hudson Soft",
+        0x19 => "ITC Entertainment",
+        0x1A => "Yanoman",
+        0x1D => "Japan Clary",
+        0x1F => "Virgin Games Ltd.",
+        0x24 => "PCM Complete",
+        0x25 => "San-X",
+        0x28 => "Kemco",
+        0x29 => "SETA Corporation",
+        0x30 => "Infogrames",
+        0x31 => "Nintendo",
+        0x32 => "Bandai",
+        0x34 => "Konami",
+        0x35 => "HectorSoft",
+        0x38 => "Capcom",
+        0x39 => "Banpresto",
+        0x3C => "Entertainment Interactive",
+        0x3E => "Gremlin",
+        0x41 => "Ubi Soft",
+        0x42 => "Atlus",
+        0x44 => "Malibu Interactive",
+        0x46 => "Angel",
+        0x47 => "Spectrum HoloByte",
+        0x49 => "Irem",
+        0x4A => "Virgin Games Ltd.",
+        0x4D => "Malibu Interactive",
+        0x4F => "U.S. Gold",
+        0x50 => "Absolute",
+        0x51 => "Acclaim Entertainment",
+        0x52 => "Activision",
+        0x53 => "Sammy USA Corporation",
+        0x54 => "GameTek",
+        0x55 => "Park Place",
+        0x56 => "LJN",
+        0x57 => "Matchbox",
+        0x59 => "Milton Bradley Company",
+        0x5A => "Mindscape",
+        0x5B => "Romstar",
+        0x5C => "Naxat Soft",
+        0x5D => "Tradewest",
+        0x60 => "Titus Interactive",
+        0x61 => "Virgin Games Ltd.",
+        0x67 => "Ocean Software",
+        0x69 => "EA (Electronic Arts)",
+        0x6E => "Elite Systems",
+        0x6F => "Electro Brain",
+        0x70 => "Infogrames",
+        0x71 => "Interplay Entertainment",
+        0x72 => "Broderbund",
+        0x73 => "Sculptured Software",
+        0x75 => "The Sales Curve Limited",
+        0x78 => "THQ",
+        0x79 => "Accolade",
+        0x7A => "Triffix Entertainment",
+        0x7C => "MicroProse",
+        0x7F => "Kemco",
+        0x80 => "Misawa Entertainment",
+        0x83 => "LOZC G.",
+        0x86 => "Tokuma Shoten",
+        0x8B => "Bullet-Proof Software",
+        0x8C => "Vic Tokai Corp.",
+        0x8E => "Ape Inc.",
+        0x8F => "I'Max",
+        0x91 => "Chunsoft Co.",
+        0x92 => "Video System",
+        0x93 => "Tsubaraya Productions",
+        0x95 => "Varie",
+        0x96 => "Yonezawa/S'Pal",
+        0x97 => "Kemco",
+        0x99 => "Arc",
+        0x9A => "Nihon Bussan",
+        0x9B => "Tecmo",
+        0x9C => "Imagineer",
+        0x9D => "Banpresto",
+        0x9F => "Nova",
+        0xA1 => "Hori Electric",
+        0xA2 => "Bandai",
+        0xA4 => "Konami",
+        0xA6 => "Kawada",
+        0xA7 => "Takara",
+        0xA9 => "Technos Japan",
+        0xAA => "Broderbund",
+        0xAC => "Toei Animation",
+        0xAD => "Toho",
+        0xAF => "Namco",
+        0xB0 => "Acclaim Entertainment",
+        0xB1 => "ASCII Corporation or Nexsoft",
+        0xB2 => "Bandai",
+        0xB4 => "Square Enix",
+        0xB6 => "HAL Laboratory",
+        0xB7 => "SNK",
+        0xB9 => "Pony Canyon",
+        0xBA => "Culture Brain",
+        0xBB => "Sunsoft",
+        0xBD => "Sony Imagesoft",
+        0xBF => "Sammy Corporation",
+        0xC0 => "Taito",
+        0xC2 => "Kemco",
+        0xC3 => "Square",
+        0xC4 => "Tokuma Shoten",
+        0xC5 => "Data East",
+        0xC6 => "Tonkin House",
+        0xC8 => "Koei",
+        0xC9 => "UFL",
+        0xCA => "Ultra Games",
+        0xCB => "VAP, Inc.",
+        0xCC => "Use Corporation",
+        0xCD => "Meldac",
+        0xCE => "Pony Canyon",
+        0xCF => "Angel",
+        0xD0 => "Taito",
+        0xD1 => "SOFEL",
+        0xD2 => "Quest",
+        0xD3 => "Sigma Enterprises",
+        0xD4 => "ASK Kodansha Co.",
+        0xD6 => "Naxat Soft",
+        0xD7 => "Copya System",
+        0xD9 => "Banpresto",
+        0xDA => "Tomy",
+        0xDB => "LJN",
+        0xDD => "Nippon Computer Systems",
+        0xDE => "Human Ent.",
+        0xDF => "Altron",
+        0xE0 => "Jaleco",
+        0xE1 => "Towa Chiki",
+        0xE2 => "Yutaka",
+        0xE3 => "Varie",
+        0xE5 => "Epoch",
+        0xE7 => "Athena",
+        0xE8 => "Asmik Ace Entertainment",
+        0xE9 => "Natsume",
+        0xEA => "King Records",
+        0xEB => "Atlus",
+        0xEC => "Epic/Sony Records",
+        0xEE => "IGS",
+        0xF0 => "A Wave",
+        0xF3 => "Extreme Entertainment",
+        0xFF => "LJN",
+        _ => return None,
+    })
+}
@@ -0,0 +1,80 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::infrared::read_infrared_register;
+use super::{
+    BankOps, CartridgeRam, MemoryBankController, MemoryMappedBank, RomBank, SwitchableBank,
+};
+use crate::storage::PersistentStorage;
+use alloc::vec::Vec;
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankControllerHuC1 {
+    infrared_select: bool,
+    switchable_bank: SwitchableBank<RomBank>,
+    ram: CartridgeRam,
+}
+
+impl From<MemoryBankControllerHuC1> for MemoryBankController {
+    fn from(c: MemoryBankControllerHuC1) -> Self {
+        Self::HuC1(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankControllerHuC1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HuC1{:?}", self.ram)
+    }
+}
+
+impl MemoryBankControllerHuC1 {
+    pub(super) fn new<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
+        Self {
+            infrared_select: false,
+            switchable_bank: SwitchableBank::new(banks, 1),
+            ram: ram.into(),
+        }
+    }
+}
+
+impl MemoryMappedBank for MemoryBankControllerHuC1 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else if self.infrared_select {
+            read_infrared_register(&*ops.infrared)
+        } else {
+            self.ram.read_bank_value(ops, address - 0xA000)
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0x2000 {
+            // Select between RAM and the infrared register. There is no RAM enable.
+            self.infrared_select = value & 0x0F == 0x0E;
+        } else if address < 0x4000 {
+            // Select ROM bank. Unlike MBC1, bank 0 can be mapped in to the switchable region.
+            self.switchable_bank
+                .switch_bank_wrapping((value & 0x3F) as usize);
+        } else if address < 0x6000 {
+            // Select RAM bank
+            self.ram.switch_bank((value & 0x03) as usize);
+        } else if address < 0xA000 {
+            // nothing
+        } else if self.infrared_select {
+            ops.infrared.set_led(value & 0x01 != 0);
+        } else {
+            self.ram.set_bank_value(ops, address - 0xA000, value);
+        }
+    }
+}
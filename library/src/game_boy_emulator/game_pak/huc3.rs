@@ -0,0 +1,305 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::infrared::read_infrared_register;
+use super::rtc::ClockFooter;
+use super::{
+    BankOps, CartridgeRam, MemoryBankController, MemoryMappedBank, RomBank, SwitchableBank,
+};
+use crate::io;
+use crate::storage::PersistentStorage;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::Range;
+use serde_derive::{Deserialize, Serialize};
+
+/// The piezo speaker on HuC3 cartridges.
+pub trait ToneGenerator: Send {
+    fn play_tone(&mut self, tone: u8);
+}
+
+/// `ToneGenerator` that doesn't make a sound.
+#[derive(Default)]
+pub struct NullToneGenerator;
+
+impl ToneGenerator for NullToneGenerator {
+    fn play_tone(&mut self, _tone: u8) {}
+}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Where in the clock's nibble memory the time is copied to and from.
+const TIME_MINUTES: Range<u8> = 0x00..0x03;
+const TIME_DAYS: Range<u8> = 0x03..0x07;
+
+/// Where in the clock's nibble memory the alarm lives.
+const ALARM_MINUTES: Range<u8> = 0x58..0x5B;
+const ALARM_DAYS: Range<u8> = 0x5B..0x5F;
+const ALARM_ENABLED: u8 = 0x5F;
+
+/// Which tone the speaker plays.
+const TONE: u8 = 0x27;
+
+/// Size of the clock data appended after the cartridge RAM in the save file. The layout is the one
+/// SameBoy uses: a 64-bit unix timestamp followed by the minute of the day, the day counter, the
+/// alarm minute and the alarm day as little-endian 16-bit values and a byte saying if the alarm is
+/// enabled.
+pub(super) const HUC3_FOOTER_LEN: u64 = 17;
+
+const HUC3_TIMESTAMP_AT: usize = 0;
+
+/// The HuC3 real-time clock. It only counts minutes and days, and the game talks to it by writing
+/// commands that move values between the counters and a small memory of 4-bit values.
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct HuC3Clock {
+    pub(super) footer: ClockFooter,
+    minutes: u16,
+    days: u16,
+    memory: Vec<u8>,
+    address: u8,
+    result: u8,
+}
+
+impl HuC3Clock {
+    pub(super) fn new(offset: u64) -> Self {
+        Self {
+            footer: ClockFooter::new(offset, HUC3_TIMESTAMP_AT),
+            minutes: 0,
+            days: 0,
+            memory: vec![0; 0x100],
+            address: 0,
+            result: 0,
+        }
+    }
+
+    /// Load the clock from the footer in the save file. A footer that was never written (all
+    /// zeros) gives a fresh clock.
+    pub(super) fn load(offset: u64, file: &mut impl io::Read) -> io::Result<Self> {
+        let (footer, data) =
+            ClockFooter::load::<{ HUC3_FOOTER_LEN as usize }>(offset, HUC3_TIMESTAMP_AT, file)?;
+        let word = |i: usize| u16::from_le_bytes([data[i], data[i + 1]]);
+
+        let mut clock = Self::new(offset);
+        clock.footer = footer;
+        clock.minutes = word(8) % MINUTES_PER_DAY;
+        clock.days = word(10);
+        clock.store(ALARM_MINUTES, word(12));
+        clock.store(ALARM_DAYS, word(14));
+        clock.memory[ALARM_ENABLED as usize] = data[16] & 0x01;
+        Ok(clock)
+    }
+
+    fn to_footer(&self) -> [u8; HUC3_FOOTER_LEN as usize] {
+        let mut data = [0; HUC3_FOOTER_LEN as usize];
+        self.footer.write_timestamp(&mut data);
+        data[8..10].copy_from_slice(&self.minutes.to_le_bytes());
+        data[10..12].copy_from_slice(&self.days.to_le_bytes());
+        data[12..14].copy_from_slice(&self.load_nibbles(ALARM_MINUTES).to_le_bytes());
+        data[14..16].copy_from_slice(&self.load_nibbles(ALARM_DAYS).to_le_bytes());
+        data[16] = self.memory[ALARM_ENABLED as usize] & 0x01;
+        data
+    }
+
+    /// Read a little-endian value spread out over consecutive nibbles of memory.
+    fn load_nibbles(&self, range: Range<u8>) -> u16 {
+        range
+            .rev()
+            .fold(0, |acc, a| (acc << 4) | self.memory[a as usize] as u16)
+    }
+
+    fn store(&mut self, range: Range<u8>, mut value: u16) {
+        for a in range {
+            self.memory[a as usize] = (value & 0x0F) as u8;
+            value >>= 4;
+        }
+    }
+
+    /// Advance the counters to the given time. Time going backwards is ignored, and any seconds
+    /// that don't add up to a minute are left to be counted next time.
+    fn catch_up(&mut self, now: u64) {
+        let timestamp = self.footer.timestamp.unwrap_or(now);
+        let elapsed_minutes = now.saturating_sub(timestamp) / 60;
+        let minutes = self.minutes as u64 + elapsed_minutes;
+        self.minutes = (minutes % MINUTES_PER_DAY as u64) as u16;
+        self.days = self
+            .days
+            .wrapping_add((minutes / MINUTES_PER_DAY as u64) as u16);
+        self.footer.timestamp = Some(if now < timestamp {
+            now
+        } else {
+            timestamp + elapsed_minutes * 60
+        });
+    }
+
+    fn extended_command(&mut self, ops: &mut BankOps<impl PersistentStorage>, argument: u8) {
+        match argument {
+            // Copy the current time into memory
+            0x0 => {
+                self.catch_up(ops.clock.now());
+                self.store(TIME_MINUTES, self.minutes);
+                self.store(TIME_DAYS, self.days);
+                self.footer.save(ops, &self.to_footer());
+            }
+            // Set the current time from memory
+            0x1 => {
+                self.minutes = self.load_nibbles(TIME_MINUTES) % MINUTES_PER_DAY;
+                self.days = self.load_nibbles(TIME_DAYS);
+                self.footer.timestamp = Some(ops.clock.now());
+                self.footer.save(ops, &self.to_footer());
+            }
+            // Status, the clock is always ready
+            0x2 => self.result = 0x1,
+            // Play the tone selected in memory
+            0xE => ops.tone.play_tone(self.memory[TONE as usize]),
+            _ => {}
+        }
+    }
+
+    /// Handle a command written to the clock. The upper nibble is the command and the lower nibble
+    /// its argument.
+    pub(super) fn command(&mut self, ops: &mut BankOps<impl PersistentStorage>, value: u8) {
+        let argument = value & 0x0F;
+        match value >> 4 {
+            // Read from memory and increment the address
+            0x1 => {
+                self.result = self.memory[self.address as usize];
+                self.address = self.address.wrapping_add(1);
+            }
+            // Write to memory, and for 0x3 increment the address
+            0x2 | 0x3 => {
+                self.memory[self.address as usize] = argument;
+                if ALARM_MINUTES.start <= self.address && self.address <= ALARM_ENABLED {
+                    self.footer.save(ops, &self.to_footer());
+                }
+                if value >> 4 == 0x3 {
+                    self.address = self.address.wrapping_add(1);
+                }
+            }
+            // Set the lower or upper nibble of the address
+            0x4 => self.address = (self.address & 0xF0) | argument,
+            0x5 => self.address = (self.address & 0x0F) | (argument << 4),
+            0x6 => self.extended_command(ops, argument),
+            _ => {}
+        }
+    }
+
+    pub(super) fn result(&self) -> u8 {
+        self.result
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankControllerHuC3 {
+    mode: u8,
+    switchable_bank: SwitchableBank<RomBank>,
+    ram: CartridgeRam,
+    pub(super) clock: HuC3Clock,
+}
+
+impl From<MemoryBankControllerHuC3> for MemoryBankController {
+    fn from(c: MemoryBankControllerHuC3) -> Self {
+        Self::HuC3(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankControllerHuC3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HuC3{:?}", self.ram)
+    }
+}
+
+impl MemoryBankControllerHuC3 {
+    pub(super) fn new<R: Into<CartridgeRam>>(
+        banks: Vec<RomBank>,
+        ram: R,
+        clock: HuC3Clock,
+    ) -> Self {
+        Self {
+            mode: 0,
+            switchable_bank: SwitchableBank::new(banks, 1),
+            ram: ram.into(),
+            clock,
+        }
+    }
+}
+
+impl MemoryMappedBank for MemoryBankControllerHuC3 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else {
+            match self.mode {
+                0x0 | 0xA => self.ram.read_bank_value(ops, address - 0xA000),
+                0xC => self.clock.result(),
+                // The clock is always ready for the next command
+                0xD => 0x01,
+                0xE => read_infrared_register(&*ops.infrared),
+                _ => 0xFF,
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0x2000 {
+            // Select what is mapped at 0xA000. 0x0 is read-only RAM and 0xA is read-write RAM.
+            // 0xB and 0xC are for writing commands and reading their results, 0xD is the clock's
+            // status, and 0xE is the infrared register.
+            self.mode = value & 0x0F;
+        } else if address < 0x4000 {
+            // Select ROM bank
+            self.switchable_bank
+                .switch_bank_wrapping((value & 0x7F) as usize);
+        } else if address < 0x6000 {
+            // Select RAM bank
+            self.ram.switch_bank((value & 0x03) as usize);
+        } else if address < 0xA000 {
+            // nothing
+        } else {
+            match self.mode {
+                0xA => self.ram.set_bank_value(ops, address - 0xA000, value),
+                0xB => self.clock.command(ops, value),
+                0xE => ops.infrared.set_led(value & 0x01 != 0),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn huc3_footer_round_trip() {
+    let mut clock = HuC3Clock::new(0);
+    clock.footer.timestamp = Some(1000);
+    clock.minutes = 100;
+    clock.days = 0x1234;
+    clock.store(ALARM_MINUTES, 0x0567);
+    clock.store(ALARM_DAYS, 0x89AB);
+    clock.memory[ALARM_ENABLED as usize] = 0x01;
+    let footer = clock.to_footer();
+
+    let loaded = HuC3Clock::load(0, &mut &footer[..]).unwrap();
+    assert_eq!(loaded.footer.timestamp, Some(1000));
+    assert_eq!(loaded.minutes, 100);
+    assert_eq!(loaded.days, 0x1234);
+    assert_eq!(loaded.memory, clock.memory);
+}
+
+#[test]
+fn huc3_clock_catch_up_keeps_leftover_seconds() {
+    let mut clock = HuC3Clock::new(0);
+    clock.footer.timestamp = Some(0);
+    clock.minutes = MINUTES_PER_DAY - 1;
+
+    clock.catch_up(90);
+    assert_eq!((clock.minutes, clock.days), (0, 1));
+
+    clock.catch_up(120);
+    assert_eq!((clock.minutes, clock.days), (1, 1));
+}
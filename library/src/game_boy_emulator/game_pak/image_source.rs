@@ -0,0 +1,70 @@
+// Copyright 2026 Remi Bernotavicius
+
+/// Width in pixels of the Pocket Camera's image sensor, as far as the cartridge can see.
+pub const CAMERA_SENSOR_WIDTH: usize = 128;
+
+/// Height in pixels of the Pocket Camera's image sensor, as far as the cartridge can see.
+pub const CAMERA_SENSOR_HEIGHT: usize = 112;
+
+/// Where the Pocket Camera gets its pictures from.
+pub trait ImageSource: Send {
+    /// Fill in the brightness of every pixel the sensor sees, row by row. 0 is black and 255 is
+    /// white.
+    fn capture(&mut self, pixels: &mut [u8]);
+}
+
+/// `ImageSource` which always sees the same pattern of vertical bars fading from black to white
+/// at the top, and a horizontal gradient at the bottom.
+#[derive(Default)]
+pub struct TestPatternImageSource;
+
+impl ImageSource for TestPatternImageSource {
+    fn capture(&mut self, pixels: &mut [u8]) {
+        for (y, row) in pixels.chunks_exact_mut(CAMERA_SENSOR_WIDTH).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = if y < CAMERA_SENSOR_HEIGHT / 2 {
+                    (x / (CAMERA_SENSOR_WIDTH / 4) * 0x55) as u8
+                } else {
+                    (x * 0xFF / (CAMERA_SENSOR_WIDTH - 1)) as u8
+                };
+            }
+        }
+    }
+}
+
+/// `ImageSource` which always sees the same picture, loaded from a BMP file. The picture is
+/// stretched to fit the sensor and turned to grayscale.
+#[cfg(feature = "bitmap-renderer")]
+pub struct BmpImageSource {
+    image: bmp::Image,
+}
+
+#[cfg(feature = "bitmap-renderer")]
+impl BmpImageSource {
+    pub fn new(image: bmp::Image) -> Self {
+        Self { image }
+    }
+
+    pub fn from_reader(reader: &mut impl crate::io::Read) -> crate::io::Result<Self> {
+        Ok(Self::new(
+            bmp::from_reader(reader).map_err(crate::io::Error::other)?,
+        ))
+    }
+}
+
+#[cfg(feature = "bitmap-renderer")]
+impl ImageSource for BmpImageSource {
+    fn capture(&mut self, pixels: &mut [u8]) {
+        let width = self.image.get_width() as usize;
+        let height = self.image.get_height() as usize;
+        for (y, row) in pixels.chunks_exact_mut(CAMERA_SENSOR_WIDTH).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let p = self.image.get_pixel(
+                    (x * width / CAMERA_SENSOR_WIDTH) as u32,
+                    (y * height / CAMERA_SENSOR_HEIGHT) as u32,
+                );
+                *pixel = ((p.r as u32 * 299 + p.g as u32 * 587 + p.b as u32 * 114) / 1000) as u8;
+            }
+        }
+    }
+}
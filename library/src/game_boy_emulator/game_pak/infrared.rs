@@ -0,0 +1,44 @@
+// Copyright 2026 Remi Bernotavicius
+
+/// Whatever is on the other side of a cartridge's infrared LED and sensor, usually another
+/// cartridge.
+pub trait InfraredPeer: Send {
+    /// The cartridge turned its LED on or off.
+    fn set_led(&mut self, on: bool);
+
+    /// Whether the cartridge's sensor currently sees any light.
+    fn light_detected(&self) -> bool;
+}
+
+/// `InfraredPeer` with nobody there. The sensor never sees anything.
+#[derive(Default)]
+pub struct NullInfraredPeer;
+
+impl InfraredPeer for NullInfraredPeer {
+    fn set_led(&mut self, _on: bool) {}
+
+    fn light_detected(&self) -> bool {
+        false
+    }
+}
+
+/// `InfraredPeer` where the sensor sees the cartridge's own LED, as if it were pointed at a mirror.
+#[derive(Default)]
+pub struct LoopbackInfraredPeer {
+    led: bool,
+}
+
+impl InfraredPeer for LoopbackInfraredPeer {
+    fn set_led(&mut self, on: bool) {
+        self.led = on;
+    }
+
+    fn light_detected(&self) -> bool {
+        self.led
+    }
+}
+
+/// The value read from the infrared register of HuC carts. The upper bits always read as set.
+pub(super) fn read_infrared_register(peer: &dyn InfraredPeer) -> u8 {
+    0xC0 | peer.light_detected() as u8
+}
@@ -0,0 +1,297 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{
+    load_sram_from_file, BankOps, MemoryBankController, MemoryMappedBank, RomBank, SramChunk,
+};
+use crate::game_boy_emulator::memory_controller::{MemoryChunk, MemoryMappedHardware as _};
+use crate::io::{self, Seek as _, SeekFrom, Write as _};
+use crate::storage::PersistentStorage;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+/// MBC6 maps ROM and flash in 8kB banks.
+const WINDOW_SIZE: u16 = 0x2000;
+
+/// MBC6 maps RAM in 4kB banks.
+pub(super) const RAM_WINDOW_SIZE: u16 = 0x1000;
+
+/// Size of the flash chip, which is kept in the save file after the RAM.
+pub(super) const FLASH_SIZE: u64 = 0x100000;
+
+/// The flash is erased in 128kB sectors.
+const FLASH_SECTOR_SIZE: usize = 0x20000;
+
+const FLASH_MANUFACTURER_ID: u8 = 0xC2;
+const FLASH_DEVICE_ID: u8 = 0x81;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum FlashMode {
+    Read,
+    Identify,
+}
+
+/// The Macronix MX29F008 flash chip. It understands the usual JEDEC command sequences: each
+/// command is preceded by writing 0xAA to 0x5555 and 0x55 to 0x2AAA. Programming and erasing
+/// finish instantly.
+#[derive(Serialize, Deserialize)]
+struct Flash {
+    banks: Vec<SramChunk>,
+    mode: FlashMode,
+    unlock_step: u8,
+    erase_armed: bool,
+    program_armed: bool,
+}
+
+impl Flash {
+    fn new(offset: u64) -> Self {
+        let banks = (0..FLASH_SIZE / WINDOW_SIZE as u64)
+            .map(|b| {
+                let memory = MemoryChunk::new(vec![0xFF; WINDOW_SIZE as usize]);
+                SramChunk::new(offset + b * WINDOW_SIZE as u64, memory)
+            })
+            .collect();
+        Self {
+            banks,
+            mode: FlashMode::Read,
+            unlock_step: 0,
+            erase_armed: false,
+            program_armed: false,
+        }
+    }
+
+    fn read(&self, bank: usize, address: u16) -> u8 {
+        match self.mode {
+            FlashMode::Identify => match address & 0xFF {
+                0x00 => FLASH_MANUFACTURER_ID,
+                0x01 => FLASH_DEVICE_ID,
+                _ => 0x00,
+            },
+            FlashMode::Read => self.banks[bank % self.banks.len()]
+                .memory
+                .read_value(address),
+        }
+    }
+
+    fn fill(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        banks: core::ops::Range<usize>,
+        value: u8,
+    ) {
+        let banks = &mut self.banks[banks];
+        for bank in banks.iter_mut() {
+            bank.memory.as_mut_slice().fill(value);
+        }
+
+        // The banks are next to each other in the save file, so they can be written in one go.
+        if let (Some(file), Some(first)) = (&mut ops.sram_file, banks.first()) {
+            file.seek(SeekFrom::Start(first.offset)).unwrap();
+            file.write_all(&vec![value; banks.len() * WINDOW_SIZE as usize])
+                .unwrap();
+        }
+    }
+
+    fn write(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        write_enable: bool,
+        bank: usize,
+        address: u16,
+        value: u8,
+    ) {
+        let bank = bank % self.banks.len();
+        if self.program_armed {
+            // Programming can only clear bits
+            self.program_armed = false;
+            if write_enable {
+                let old = self.banks[bank].memory.read_value(address);
+                self.banks[bank].set_bank_value(ops, address, old & value);
+            }
+            return;
+        }
+
+        // The command addresses only look at the lower 15 address bits.
+        let flash_address = (bank * WINDOW_SIZE as usize + address as usize) & 0x7FFF;
+        match (self.unlock_step, flash_address, value) {
+            (_, _, 0xF0) => {
+                self.mode = FlashMode::Read;
+                self.unlock_step = 0;
+                self.erase_armed = false;
+            }
+            (0, 0x5555, 0xAA) => self.unlock_step = 1,
+            (1, 0x2AAA, 0x55) => self.unlock_step = 2,
+            (2, 0x5555, 0x90) => {
+                self.mode = FlashMode::Identify;
+                self.unlock_step = 0;
+            }
+            (2, 0x5555, 0xA0) => {
+                self.program_armed = true;
+                self.unlock_step = 0;
+            }
+            (2, 0x5555, 0x80) => {
+                self.erase_armed = true;
+                self.unlock_step = 0;
+            }
+            (2, 0x5555, 0x10) if self.erase_armed => {
+                if write_enable {
+                    self.fill(ops, 0..self.banks.len(), 0xFF);
+                }
+                self.erase_armed = false;
+                self.unlock_step = 0;
+            }
+            (2, _, 0x30) if self.erase_armed => {
+                if write_enable {
+                    let banks_per_sector = FLASH_SECTOR_SIZE / WINDOW_SIZE as usize;
+                    let first = bank / banks_per_sector * banks_per_sector;
+                    self.fill(ops, first..first + banks_per_sector, 0xFF);
+                }
+                self.erase_armed = false;
+                self.unlock_step = 0;
+            }
+            _ => {
+                self.unlock_step = 0;
+                self.erase_armed = false;
+            }
+        }
+    }
+}
+
+/// One of the two 8kB windows at 0x4000 and 0x6000, which can each show ROM or flash.
+#[derive(Default, Serialize, Deserialize)]
+struct Window {
+    bank: usize,
+    flash: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankController6 {
+    ram_enable: bool,
+    flash_enable: bool,
+    flash_write_enable: bool,
+    ram_banks: [usize; 2],
+    windows: [Window; 2],
+    rom_banks: Vec<RomBank>,
+    ram: Vec<SramChunk>,
+    flash: Flash,
+}
+
+impl From<MemoryBankController6> for MemoryBankController {
+    fn from(c: MemoryBankController6) -> Self {
+        Self::Six(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankController6 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MBC6+RAM+FLASH")
+    }
+}
+
+impl MemoryBankController6 {
+    pub(super) fn new(rom_banks: Vec<RomBank>, ram: Vec<SramChunk>, flash_offset: u64) -> Self {
+        Self {
+            ram_enable: false,
+            flash_enable: false,
+            flash_write_enable: false,
+            ram_banks: [0, 0],
+            windows: [Window::default(), Window::default()],
+            rom_banks,
+            ram,
+            flash: Flash::new(flash_offset),
+        }
+    }
+
+    /// Load the contents of the flash from the save file.
+    pub(super) fn load_flash(&mut self, file: &mut impl io::Read) -> io::Result<()> {
+        load_sram_from_file(&mut self.flash.banks, file)
+    }
+
+    /// Write the whole contents of the flash to the save file.
+    pub(super) fn store_flash(&self, file: &mut (impl io::Write + io::Seek)) -> io::Result<()> {
+        for bank in &self.flash.banks {
+            file.seek(io::SeekFrom::Start(bank.offset))?;
+            file.write_all(bank.memory.as_slice())?;
+        }
+        Ok(())
+    }
+
+    fn read_rom(&self, ops: &BankOps<impl PersistentStorage>, bank: usize, address: u16) -> u8 {
+        // The ROM is split up in to 16kB banks, so each one holds two of these
+        let rom_bank = &self.rom_banks[bank / 2 % self.rom_banks.len()];
+        rom_bank.read_bank_value(ops, (bank % 2) as u16 * WINDOW_SIZE + address)
+    }
+}
+
+impl MemoryMappedBank for MemoryBankController6 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.rom_banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            let window = &self.windows[((address - 0x4000) / WINDOW_SIZE) as usize];
+            let address = address % WINDOW_SIZE;
+            if !window.flash {
+                self.read_rom(ops, window.bank, address)
+            } else if self.flash_enable {
+                self.flash.read(window.bank, address)
+            } else {
+                0xFF
+            }
+        } else if self.ram_enable && !self.ram.is_empty() {
+            let bank = self.ram_banks[((address - 0xA000) / RAM_WINDOW_SIZE) as usize];
+            self.ram[bank % self.ram.len()].read_bank_value(ops, address % RAM_WINDOW_SIZE)
+        } else {
+            0xFF
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0x0400 {
+            // RAM Enable
+            self.ram_enable = (value & 0x0F) == 0x0A;
+        } else if address < 0x0800 {
+            // Select RAM bank A
+            self.ram_banks[0] = (value & 0x07) as usize;
+        } else if address < 0x0C00 {
+            // Select RAM bank B
+            self.ram_banks[1] = (value & 0x07) as usize;
+        } else if address < 0x1000 {
+            // Flash Enable
+            self.flash_enable = value & 0x01 != 0;
+        } else if address == 0x1000 {
+            // Flash Write Enable
+            self.flash_write_enable = value & 0x01 != 0;
+        } else if address < 0x2000 {
+            // nothing
+        } else if address < 0x4000 {
+            // Select ROM / flash bank and whether it is ROM or flash for windows A and B
+            let window = &mut self.windows[((address - 0x2000) / 0x1000) as usize];
+            if address & 0x0800 == 0 {
+                window.bank = (value & 0x7F) as usize;
+            } else {
+                window.flash = value == 0x08;
+            }
+        } else if address < 0x8000 {
+            let window = &self.windows[((address - 0x4000) / WINDOW_SIZE) as usize];
+            if window.flash && self.flash_enable {
+                let bank = window.bank;
+                let write_enable = self.flash_write_enable;
+                self.flash
+                    .write(ops, write_enable, bank, address % WINDOW_SIZE, value);
+            }
+        } else if address < 0xA000 {
+            // nothing
+        } else if self.ram_enable && !self.ram.is_empty() {
+            let bank = self.ram_banks[((address - 0xA000) / RAM_WINDOW_SIZE) as usize];
+            let len = self.ram.len();
+            self.ram[bank % len].set_bank_value(ops, address % RAM_WINDOW_SIZE, value);
+        }
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{BankOps, MemoryBankController, MemoryMappedBank, RomBank, SramChunk, SwitchableBank};
+use crate::game_boy_emulator::memory_controller::MemoryMappedHardware as _;
+use crate::storage::PersistentStorage;
+use alloc::vec::Vec;
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+/// What the accelerometer reads when the Game Boy is held level.
+const ACCELEROMETER_CENTER: u16 = 0x81D0;
+
+/// How much the accelerometer reading changes when tilted all the way in one direction.
+const ACCELEROMETER_RANGE: f32 = 0x70 as f32;
+
+/// What the accelerometer reads after being erased and before being latched.
+const ACCELEROMETER_ERASED: u16 = 0x8000;
+
+#[derive(Serialize, Deserialize)]
+struct Accelerometer {
+    x: u16,
+    y: u16,
+    erased: bool,
+}
+
+impl Accelerometer {
+    fn new() -> Self {
+        Self {
+            x: ACCELEROMETER_ERASED,
+            y: ACCELEROMETER_ERASED,
+            erased: false,
+        }
+    }
+
+    /// Writing 0x55 to the erase register and then 0xAA to the latch register captures the
+    /// current tilt.
+    fn erase(&mut self, value: u8) {
+        if value == 0x55 {
+            self.x = ACCELEROMETER_ERASED;
+            self.y = ACCELEROMETER_ERASED;
+            self.erased = true;
+        }
+    }
+
+    fn latch(&mut self, ops: &BankOps<impl PersistentStorage>, value: u8) {
+        if self.erased && value == 0xAA {
+            let (x, y) = ops.tilt.tilt();
+            let reading = |t: f32| (ACCELEROMETER_CENTER as f32 + t * ACCELEROMETER_RANGE) as u16;
+            self.x = reading(x.clamp(-1.0, 1.0));
+            self.y = reading(y.clamp(-1.0, 1.0));
+            self.erased = false;
+        }
+    }
+}
+
+const EEPROM_CS: u8 = 0x80;
+const EEPROM_CLK: u8 = 0x40;
+const EEPROM_DI: u8 = 0x02;
+const EEPROM_DO: u8 = 0x01;
+
+/// Size of the 93LC56 EEPROM, which is organized as 128 16-bit words.
+pub(super) const EEPROM_SIZE: u16 = 256;
+
+/// Number of bits of a command after the start bit, two bits of opcode and eight of address.
+const EEPROM_COMMAND_BITS: u8 = 10;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum EepromState {
+    /// Waiting for the start bit
+    Idle,
+    /// Shifting in the opcode and address
+    Command { command: u16, bits: u8 },
+    /// Shifting out words starting at the given address
+    Read { address: u8, word: u16, bits: u8 },
+    /// Shifting in a word to write to the given address, or all of them
+    Write {
+        address: Option<u8>,
+        word: u16,
+        bits: u8,
+    },
+    /// Finished a command, waiting for chip select to go low
+    Done,
+}
+
+/// The 93LC56 serial EEPROM that MBC7 cartridges use instead of RAM. The game bit-bangs the
+/// chip-select, clock and data lines, and commands are shifted in on the rising edge of the
+/// clock. The words are kept little-endian in the save file.
+#[derive(Serialize, Deserialize)]
+struct Eeprom {
+    memory: SramChunk,
+    state: EepromState,
+    write_enable: bool,
+    cs: bool,
+    clk: bool,
+    di: bool,
+    data_out: bool,
+}
+
+impl Eeprom {
+    fn new(memory: SramChunk) -> Self {
+        Self {
+            memory,
+            state: EepromState::Idle,
+            write_enable: false,
+            cs: false,
+            clk: false,
+            di: false,
+            data_out: true,
+        }
+    }
+
+    fn read_word(&self, address: u8) -> u16 {
+        let address = (address & 0x7F) as u16 * 2;
+        u16::from_le_bytes([
+            self.memory.memory.read_value(address),
+            self.memory.memory.read_value(address + 1),
+        ])
+    }
+
+    fn write_word(&mut self, ops: &mut BankOps<impl PersistentStorage>, address: u8, word: u16) {
+        if !self.write_enable {
+            return;
+        }
+        let address = (address & 0x7F) as u16 * 2;
+        let [low, high] = word.to_le_bytes();
+        self.memory.set_bank_value(ops, address, low);
+        self.memory.set_bank_value(ops, address + 1, high);
+    }
+
+    fn write_all(&mut self, ops: &mut BankOps<impl PersistentStorage>, word: u16) {
+        for address in 0..(EEPROM_SIZE / 2) as u8 {
+            self.write_word(ops, address, word);
+        }
+    }
+
+    fn read(&self) -> u8 {
+        let mut value = 0;
+        if self.cs {
+            value |= EEPROM_CS;
+        }
+        if self.clk {
+            value |= EEPROM_CLK;
+        }
+        if self.di {
+            value |= EEPROM_DI;
+        }
+        if self.data_out {
+            value |= EEPROM_DO;
+        }
+        value
+    }
+
+    fn run_command(&mut self, ops: &mut BankOps<impl PersistentStorage>, command: u16) {
+        let address = command as u8;
+        self.state = EepromState::Done;
+        match command >> 8 {
+            // READ, a dummy zero bit comes out before the word
+            0b10 => {
+                self.data_out = false;
+                self.state = EepromState::Read {
+                    address,
+                    word: self.read_word(address),
+                    bits: 16,
+                };
+            }
+            // WRITE
+            0b01 => {
+                self.state = EepromState::Write {
+                    address: Some(address),
+                    word: 0,
+                    bits: 0,
+                };
+            }
+            // ERASE
+            0b11 => self.write_word(ops, address, 0xFFFF),
+            _ => match address >> 6 {
+                // EWDS, disable writing
+                0b00 => self.write_enable = false,
+                // WRAL, write all
+                0b01 => {
+                    self.state = EepromState::Write {
+                        address: None,
+                        word: 0,
+                        bits: 0,
+                    };
+                }
+                // ERAL, erase all
+                0b10 => self.write_all(ops, 0xFFFF),
+                // EWEN, enable writing
+                _ => self.write_enable = true,
+            },
+        }
+    }
+
+    fn clock_rising_edge(&mut self, ops: &mut BankOps<impl PersistentStorage>) {
+        match self.state {
+            EepromState::Idle => {
+                if self.di {
+                    self.state = EepromState::Command {
+                        command: 0,
+                        bits: 0,
+                    };
+                }
+            }
+            EepromState::Command { command, bits } => {
+                let command = (command << 1) | self.di as u16;
+                let bits = bits + 1;
+                if bits == EEPROM_COMMAND_BITS {
+                    self.run_command(ops, command);
+                } else {
+                    self.state = EepromState::Command { command, bits };
+                }
+            }
+            EepromState::Read {
+                address,
+                word,
+                bits,
+            } => {
+                self.data_out = word & 0x8000 != 0;
+                self.state = if bits == 1 {
+                    // Keep on reading the next word
+                    let address = address.wrapping_add(1) & 0x7F;
+                    EepromState::Read {
+                        address,
+                        word: self.read_word(address),
+                        bits: 16,
+                    }
+                } else {
+                    EepromState::Read {
+                        address,
+                        word: word << 1,
+                        bits: bits - 1,
+                    }
+                };
+            }
+            EepromState::Write {
+                address,
+                word,
+                bits,
+            } => {
+                let word = (word << 1) | self.di as u16;
+                let bits = bits + 1;
+                if bits == 16 {
+                    match address {
+                        Some(address) => self.write_word(ops, address, word),
+                        None => self.write_all(ops, word),
+                    }
+                    self.state = EepromState::Done;
+                } else {
+                    self.state = EepromState::Write {
+                        address,
+                        word,
+                        bits,
+                    };
+                }
+            }
+            EepromState::Done => {}
+        }
+    }
+
+    fn write(&mut self, ops: &mut BankOps<impl PersistentStorage>, value: u8) {
+        let cs = value & EEPROM_CS != 0;
+        let clk = value & EEPROM_CLK != 0;
+        self.di = value & EEPROM_DI != 0;
+
+        if !cs {
+            // Dropping chip select abandons whatever command was going on. The chip is always
+            // ready since writes finish instantly.
+            self.state = EepromState::Idle;
+            self.data_out = true;
+        } else if self.cs && !self.clk && clk {
+            self.clock_rising_edge(ops);
+        }
+        self.cs = cs;
+        self.clk = clk;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankController7 {
+    ram_enable1: bool,
+    ram_enable2: bool,
+    switchable_bank: SwitchableBank<RomBank>,
+    accelerometer: Accelerometer,
+    eeprom: Eeprom,
+}
+
+impl From<MemoryBankController7> for MemoryBankController {
+    fn from(c: MemoryBankController7) -> Self {
+        Self::Seven(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankController7 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MBC7+SENSOR+EEPROM")
+    }
+}
+
+impl MemoryBankController7 {
+    pub(super) fn new(banks: Vec<RomBank>, eeprom: SramChunk) -> Self {
+        Self {
+            ram_enable1: false,
+            ram_enable2: false,
+            switchable_bank: SwitchableBank::new(banks, 1),
+            accelerometer: Accelerometer::new(),
+            eeprom: Eeprom::new(eeprom),
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn registers_enabled(&self) -> bool {
+        self.ram_enable1 && self.ram_enable2
+    }
+}
+
+impl MemoryMappedBank for MemoryBankController7 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else if address >= 0xB000 || !self.registers_enabled() {
+            0xFF
+        } else {
+            // The registers are selected by address bits 4-7 and mirrored through 0xA000-0xAFFF
+            match (address >> 4) & 0x0F {
+                0x2 => self.accelerometer.x as u8,
+                0x3 => (self.accelerometer.x >> 8) as u8,
+                0x4 => self.accelerometer.y as u8,
+                0x5 => (self.accelerometer.y >> 8) as u8,
+                0x6 => 0x00,
+                0x8 => self.eeprom.read(),
+                _ => 0xFF,
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0x2000 {
+            // RAM enable 1
+            self.ram_enable1 = value == 0x0A;
+        } else if address < 0x4000 {
+            // Select ROM bank
+            self.switchable_bank
+                .switch_bank_wrapping((value & 0x7F) as usize);
+        } else if address < 0x6000 {
+            // RAM enable 2
+            self.ram_enable2 = value == 0x40;
+        } else if address < 0xA000 {
+            // nothing
+        } else if address < 0xB000 && self.registers_enabled() {
+            match (address >> 4) & 0x0F {
+                0x0 => self.accelerometer.erase(value),
+                0x1 => self.accelerometer.latch(ops, value),
+                0x8 => self.eeprom.write(ops, value),
+                _ => {}
+            }
+        }
+    }
+}
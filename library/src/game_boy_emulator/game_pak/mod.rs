@@ -4,12 +4,52 @@ use super::memory_controller::{MemoryChunk, MemoryMappedHardware};
 use crate::io::{self, Read as _, Seek as _, SeekFrom, Write as _};
 use crate::storage::{OpenMode, PersistentStorage, StorageFile as _};
 use alloc::boxed::Box;
-use alloc::{format, string::String, vec, vec::Vec};
+use alloc::{format, vec, vec::Vec};
+use camera::MemoryBankControllerCamera;
 use core::borrow::Borrow;
 use core::fmt;
 use core::ops::Range;
 use core::str;
+use header::number_of_rom_banks;
+use huc1::MemoryBankControllerHuC1;
+use huc3::{HuC3Clock, MemoryBankControllerHuC3, NullToneGenerator, HUC3_FOOTER_LEN};
+use infrared::NullInfraredPeer;
+use mbc6::{MemoryBankController6, FLASH_SIZE, RAM_WINDOW_SIZE};
+use mbc7::{MemoryBankController7, EEPROM_SIZE};
+use rtc::{RealTimeClock, RTC_FOOTER_LEN};
+use rumble::NullRumble;
 use serde_derive::{Deserialize, Serialize};
+use tama5::{MemoryBankControllerTama5, Tama5Clock, TAMA5_FOOTER_LEN, TAMA5_RAM_SIZE};
+use tilt::LevelTiltSensor;
+
+pub use header::{CartridgeHeader, CgbSupport, Checksum, Destination};
+pub use huc3::ToneGenerator;
+#[cfg(feature = "bitmap-renderer")]
+pub use image_source::BmpImageSource;
+pub use image_source::{
+    ImageSource, TestPatternImageSource, CAMERA_SENSOR_HEIGHT, CAMERA_SENSOR_WIDTH,
+};
+pub use infrared::{InfraredPeer, LoopbackInfraredPeer};
+#[cfg(feature = "std")]
+pub use rtc::SystemWallClock;
+pub use rtc::{FrozenWallClock, WallClock};
+pub use rumble::Rumble;
+#[cfg(feature = "std")]
+pub use tilt::SharedTilt;
+pub use tilt::TiltSensor;
+
+mod camera;
+mod header;
+mod huc1;
+mod huc3;
+mod image_source;
+mod infrared;
+mod mbc6;
+mod mbc7;
+mod rtc;
+mod rumble;
+mod tama5;
+mod tilt;
 
 pub fn rom_hash(rom: &[u8]) -> u32 {
     crate::util::super_fast_hash(rom)
@@ -30,6 +70,12 @@ impl RomChunk {
 struct BankOps<Storage: PersistentStorage> {
     sram_file: Option<Storage::File>,
     rom_memory: Vec<RomChunk>,
+    clock: Box<dyn WallClock>,
+    rumble: Box<dyn Rumble>,
+    infrared: Box<dyn InfraredPeer>,
+    tone: Box<dyn ToneGenerator>,
+    tilt: Box<dyn TiltSensor>,
+    image_source: Box<dyn ImageSource>,
 }
 
 trait MemoryMappedBank {
@@ -75,6 +121,13 @@ enum MemoryBankController {
     Two(MemoryBankController2),
     Three(MemoryBankController3),
     Five(MemoryBankController5),
+    Six(MemoryBankController6),
+    Seven(MemoryBankController7),
+    Mmm01(MemoryBankControllerMmm01),
+    HuC1(MemoryBankControllerHuC1),
+    HuC3(MemoryBankControllerHuC3),
+    Camera(MemoryBankControllerCamera),
+    Tama5(MemoryBankControllerTama5),
 }
 
 impl fmt::Debug for MemoryBankController {
@@ -85,6 +138,13 @@ impl fmt::Debug for MemoryBankController {
             Self::Two(r) => write!(f, "{r:?}"),
             Self::Three(r) => write!(f, "{r:?}"),
             Self::Five(r) => write!(f, "{r:?}"),
+            Self::Six(r) => write!(f, "{r:?}"),
+            Self::Seven(r) => write!(f, "{r:?}"),
+            Self::Mmm01(r) => write!(f, "{r:?}"),
+            Self::HuC1(r) => write!(f, "{r:?}"),
+            Self::HuC3(r) => write!(f, "{r:?}"),
+            Self::Camera(r) => write!(f, "{r:?}"),
+            Self::Tama5(r) => write!(f, "{r:?}"),
         }
     }
 }
@@ -98,6 +158,13 @@ impl MemoryMappedBank for MemoryBankController {
             Self::Two(r) => r.read_bank_value(ops, address),
             Self::Three(r) => r.read_bank_value(ops, address),
             Self::Five(r) => r.read_bank_value(ops, address),
+            Self::Six(r) => r.read_bank_value(ops, address),
+            Self::Seven(r) => r.read_bank_value(ops, address),
+            Self::Mmm01(r) => r.read_bank_value(ops, address),
+            Self::HuC1(r) => r.read_bank_value(ops, address),
+            Self::HuC3(r) => r.read_bank_value(ops, address),
+            Self::Camera(r) => r.read_bank_value(ops, address),
+            Self::Tama5(r) => r.read_bank_value(ops, address),
         }
     }
 
@@ -114,6 +181,13 @@ impl MemoryMappedBank for MemoryBankController {
             Self::Two(r) => r.set_bank_value(ops, address, value),
             Self::Three(r) => r.set_bank_value(ops, address, value),
             Self::Five(r) => r.set_bank_value(ops, address, value),
+            Self::Six(r) => r.set_bank_value(ops, address, value),
+            Self::Seven(r) => r.set_bank_value(ops, address, value),
+            Self::Mmm01(r) => r.set_bank_value(ops, address, value),
+            Self::HuC1(r) => r.set_bank_value(ops, address, value),
+            Self::HuC3(r) => r.set_bank_value(ops, address, value),
+            Self::Camera(r) => r.set_bank_value(ops, address, value),
+            Self::Tama5(r) => r.set_bank_value(ops, address, value),
         }
     }
 }
@@ -183,6 +257,14 @@ impl<T> SwitchableBank<T> {
         assert!(new_bank < self.banks.len());
         self.current_bank = new_bank;
     }
+
+    /// Switch banks ignoring any bank number bits that don't correspond to a bank.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn switch_bank_wrapping(&mut self, new_bank: usize) {
+        if !self.banks.is_empty() {
+            self.switch_bank(new_bank % self.banks.len());
+        }
+    }
 }
 
 impl SwitchableBank<SramChunk> {
@@ -243,12 +325,26 @@ enum RomOrRam {
     Ram,
 }
 
+/// How the bank number registers of an MBC1 are wired to the ROM address lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mbc1Wiring {
+    /// Pick based on the contents of the ROM.
+    #[default]
+    Detect,
+    Standard,
+    /// The wiring used by multicart compilations (MBC1M). The upper bank number bits are shifted
+    /// by 4 instead of 5, so each sub-game sees 16 banks.
+    Multicart,
+}
+
 #[derive(Serialize, Deserialize)]
 struct MemoryBankController1 {
     rom_bank_number: usize,
     ram_bank_number: usize,
     rom_ram_select: RomOrRam,
     ram_enable: bool,
+    multicart: bool,
+    lower_bank: usize,
     switchable_bank: SwitchableBank<RomBank>,
     ram: CartridgeRam,
 }
@@ -263,7 +359,7 @@ impl MemoryMappedBank for MemoryBankController1 {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
         if address < 0x4000 {
-            self.switchable_bank.banks[0].read_bank_value(ops, address)
+            self.switchable_bank.banks[self.lower_bank].read_bank_value(ops, address)
         } else if address < 0xA000 {
             self.switchable_bank.read_bank_value(ops, address - 0x4000)
         } else if self.ram_enable {
@@ -285,26 +381,23 @@ impl MemoryMappedBank for MemoryBankController1 {
             self.ram_enable = (value & 0x0F) == 0x0A;
         } else if address < 0x4000 {
             // Select ROM bank (lower 5 bits)
-            self.rom_bank_number &= !0x1F;
-            self.rom_bank_number |= (value as usize) & 0x1F;
-            self.update_rom_bank();
-        } else if address < 0x6000 {
-            // Either select RAM bank or select ROM bank (6th and 7th bit)
-            if self.rom_ram_select == RomOrRam::Ram {
-                self.ram_bank_number = value as usize;
-                self.update_ram_bank();
-            } else {
-                self.rom_bank_number &= !(0x03 << 5);
-                self.rom_bank_number |= ((value as usize) & 0x03) << 5;
-                self.update_rom_bank();
+            self.rom_bank_number = (value as usize) & 0x1F;
+            if self.rom_bank_number == 0 {
+                self.rom_bank_number = 1;
             }
+            self.update_banks();
+        } else if address < 0x6000 {
+            // Select RAM bank or upper bits of ROM bank
+            self.ram_bank_number = (value as usize) & 0x03;
+            self.update_banks();
         } else if address < 0x8000 {
-            // Either select RAM bank or upper bits of ROM bank
-            if value > 0 {
+            // Select whether the upper bits also apply to RAM and the first ROM bank
+            if value & 0x01 != 0 {
                 self.rom_ram_select = RomOrRam::Ram;
             } else {
                 self.rom_ram_select = RomOrRam::Rom;
             }
+            self.update_banks();
         } else if address < 0xA000 {
             // nothing
         } else {
@@ -315,7 +408,11 @@ impl MemoryMappedBank for MemoryBankController1 {
 
 impl fmt::Debug for MemoryBankController1 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MBC1{:?}", self.ram)
+        if self.multicart {
+            write!(f, "MBC1M{:?}", self.ram)
+        } else {
+            write!(f, "MBC1{:?}", self.ram)
+        }
     }
 }
 
@@ -326,11 +423,39 @@ impl MemoryBankController1 {
             ram_bank_number: 0,
             rom_ram_select: RomOrRam::Rom,
             ram_enable: false,
+            multicart: false,
+            lower_bank: 0,
             switchable_bank: SwitchableBank::new(banks, 1),
             ram: ram.into(),
         }
     }
 
+    fn new_multicart<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
+        MemoryBankController1 {
+            multicart: true,
+            ..Self::new(banks, ram)
+        }
+    }
+
+    /// Update the mapped banks based on the MBC1 bank registers. The upper bits register is
+    /// combined with the lower bits to select the switchable ROM bank. When RAM banking mode is
+    /// selected, it also selects the RAM bank and the bank mapped at 0x0000.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn update_banks(&mut self) {
+        let (shift, mask) = if self.multicart { (4, 0x0F) } else { (5, 0x1F) };
+        let upper_bits = self.ram_bank_number << shift;
+        self.switchable_bank
+            .switch_bank_wrapping(upper_bits | (self.rom_bank_number & mask));
+
+        if self.rom_ram_select == RomOrRam::Ram {
+            self.lower_bank = upper_bits % self.switchable_bank.banks.len();
+            self.ram.switch_bank(self.ram_bank_number);
+        } else {
+            self.lower_bank = 0;
+            self.ram.switch_bank(0);
+        }
+    }
+
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn update_rom_bank(&mut self) {
         if self.rom_bank_number == 0 {
@@ -355,6 +480,8 @@ enum ClockOrRam {
 struct MemoryBankController3 {
     inner: MemoryBankController1,
     clock_ram_select: ClockOrRam,
+    rtc_register: u8,
+    rtc: Option<RealTimeClock>,
 }
 
 impl From<MemoryBankController3> for MemoryBankController {
@@ -365,7 +492,11 @@ impl From<MemoryBankController3> for MemoryBankController {
 
 impl fmt::Debug for MemoryBankController3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MBC3{:?}", self.inner.ram)
+        if self.rtc.is_some() {
+            write!(f, "MBC3+TIMER{:?}", self.inner.ram)
+        } else {
+            write!(f, "MBC3{:?}", self.inner.ram)
+        }
     }
 }
 
@@ -374,6 +505,8 @@ impl MemoryBankController3 {
         MemoryBankController3 {
             inner: MemoryBankController1::new(banks, ram),
             clock_ram_select: ClockOrRam::Ram,
+            rtc_register: 0,
+            rtc: None,
         }
     }
 }
@@ -385,8 +518,11 @@ impl MemoryMappedBank for MemoryBankController3 {
             self.inner.read_bank_value(ops, address)
         } else if address < 0xC000 {
             if self.clock_ram_select == ClockOrRam::Clock {
-                // Read current RTC register
-                0xFF
+                // Read latched RTC register
+                match &self.rtc {
+                    Some(rtc) if self.inner.ram_enable => rtc.read_register(self.rtc_register),
+                    _ => 0xFF,
+                }
             } else {
                 self.inner.ram.read_bank_value(ops, address - 0xA000)
             }
@@ -415,16 +551,23 @@ impl MemoryMappedBank for MemoryBankController3 {
                 self.inner.ram_bank_number = value as usize;
                 self.inner.update_ram_bank();
             } else {
-                self.clock_ram_select = ClockOrRam::Clock;
                 // Select RTC register
+                self.clock_ram_select = ClockOrRam::Clock;
+                self.rtc_register = value;
             }
         } else if address < 0x8000 {
             // Latch clock data
+            if let Some(rtc) = &mut self.rtc {
+                rtc.write_latch(ops, value);
+            }
         } else if address < 0xA000 {
             self.inner.set_bank_value(ops, address, value);
         } else if address < 0xC000 {
             if self.clock_ram_select == ClockOrRam::Clock {
                 // Write to RTC register
+                if let Some(rtc) = self.rtc.as_mut().filter(|_| self.inner.ram_enable) {
+                    rtc.write_register(ops, self.rtc_register, value);
+                }
             } else {
                 self.inner.ram.set_bank_value(ops, address - 0xA000, value);
             }
@@ -627,6 +770,8 @@ impl MemoryMappedBank for MemoryBankController2 {
 #[derive(Serialize, Deserialize)]
 struct MemoryBankController5 {
     inner: MemoryBankController1,
+    has_rumble: bool,
+    rumbling: bool,
 }
 
 impl From<MemoryBankController5> for MemoryBankController {
@@ -637,7 +782,11 @@ impl From<MemoryBankController5> for MemoryBankController {
 
 impl fmt::Debug for MemoryBankController5 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MBC5{:?}", self.inner.ram)
+        if self.has_rumble {
+            write!(f, "MBC5+RUMBLE{:?}", self.inner.ram)
+        } else {
+            write!(f, "MBC5{:?}", self.inner.ram)
+        }
     }
 }
 
@@ -645,6 +794,31 @@ impl MemoryBankController5 {
     fn new<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
         MemoryBankController5 {
             inner: MemoryBankController1::new(banks, ram),
+            has_rumble: false,
+            rumbling: false,
+        }
+    }
+
+    fn new_with_rumble<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
+        MemoryBankController5 {
+            has_rumble: true,
+            ..Self::new(banks, ram)
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn update_rom_bank(&mut self) {
+        // Unlike MBC1, bank 0 can be mapped in to the switchable region
+        self.inner
+            .switchable_bank
+            .switch_bank_wrapping(self.inner.rom_bank_number);
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_rumble(&mut self, ops: &mut BankOps<impl PersistentStorage>, rumbling: bool) {
+        if self.rumbling != rumbling {
+            self.rumbling = rumbling;
+            ops.rumble.set_rumble(rumbling);
         }
     }
 }
@@ -669,15 +843,20 @@ impl MemoryMappedBank for MemoryBankController5 {
             // Select ROM bank (lower 8 bits)
             self.inner.rom_bank_number &= !0xFF;
             self.inner.rom_bank_number |= value as usize;
-            self.inner.update_rom_bank();
+            self.update_rom_bank();
         } else if address < 0x4000 {
             // Select ROM bank (9th bit)
             self.inner.rom_bank_number &= !(0x01 << 8);
             self.inner.rom_bank_number |= ((value & 0x01) as usize) << 8;
-            self.inner.update_rom_bank();
+            self.update_rom_bank();
         } else if address < 0x6000 {
-            // Select RAM bank
-            self.inner.ram_bank_number = value as usize;
+            // Select RAM bank. On rumble carts the 4th bit drives the motor instead.
+            if self.has_rumble {
+                self.set_rumble(ops, value & 0x08 != 0);
+                self.inner.ram_bank_number = (value & 0x07) as usize;
+            } else {
+                self.inner.ram_bank_number = (value & 0x0F) as usize;
+            }
             self.inner.update_ram_bank();
         } else if address < 0xA000 {
             // nothing
@@ -688,6 +867,173 @@ impl MemoryMappedBank for MemoryBankController5 {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct MemoryBankControllerMmm01 {
+    mapped: bool,
+    ram_enable: bool,
+    /// ROM bank number bits 0-4
+    rom_bank_low: usize,
+    /// ROM bank number bits 5-8
+    rom_bank_high: usize,
+    /// Which of the ROM bank number bits 1-4 the sub-game can't change
+    rom_bank_mask: usize,
+    ram_bank_number: usize,
+    /// Which of the RAM bank number bits 0-1 the sub-game can't change
+    ram_bank_mask: usize,
+    mbc1_mode: bool,
+    mbc1_mode_locked: bool,
+    lower_bank: usize,
+    switchable_bank: SwitchableBank<RomBank>,
+    ram: CartridgeRam,
+}
+
+impl From<MemoryBankControllerMmm01> for MemoryBankController {
+    fn from(c: MemoryBankControllerMmm01) -> Self {
+        Self::Mmm01(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankControllerMmm01 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MMM01{:?}", self.ram)
+    }
+}
+
+impl MemoryBankControllerMmm01 {
+    fn new<R: Into<CartridgeRam>>(banks: Vec<RomBank>, ram: R) -> Self {
+        let mut mbc = MemoryBankControllerMmm01 {
+            mapped: false,
+            ram_enable: false,
+            rom_bank_low: 0,
+            rom_bank_high: 0,
+            rom_bank_mask: 0,
+            ram_bank_number: 0,
+            ram_bank_mask: 0,
+            mbc1_mode: false,
+            mbc1_mode_locked: false,
+            lower_bank: 0,
+            switchable_bank: SwitchableBank::new(banks, 0),
+            ram: ram.into(),
+        };
+        mbc.update_banks();
+        mbc
+    }
+
+    /// The bits of the lower ROM bank number register which can currently be written to.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn writable_rom_bank_bits(&self) -> usize {
+        if self.mapped {
+            0x1F & !self.rom_bank_mask
+        } else {
+            0x1F
+        }
+    }
+
+    /// The bits of the RAM bank number register which can currently be written to.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn writable_ram_bank_bits(&self) -> usize {
+        if self.mapped {
+            0x03 & !self.ram_bank_mask
+        } else {
+            0x0F
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn update_banks(&mut self) {
+        let number_of_banks = self.switchable_bank.banks.len();
+        if !self.mapped {
+            // The menu lives in the last 32kB, which is all that is visible until a game is mapped.
+            self.lower_bank = number_of_banks - 2;
+            self.switchable_bank.switch_bank(number_of_banks - 1);
+        } else {
+            let writable = self.writable_rom_bank_bits();
+            let bank = (self.rom_bank_high << 5) | self.rom_bank_low;
+            let game_first_bank = bank & !writable;
+
+            // Like MBC1, the sub-game can't select its first bank in the switchable region
+            let mut switchable = bank;
+            if bank & writable == 0 {
+                switchable |= 0x01;
+            }
+            self.lower_bank = game_first_bank % number_of_banks;
+            self.switchable_bank.switch_bank_wrapping(switchable);
+        }
+
+        // Like MBC1, the sub-game can only switch RAM banks in MBC1 mode 1
+        if self.mapped && !self.mbc1_mode {
+            let writable = self.writable_ram_bank_bits();
+            self.ram.switch_bank(self.ram_bank_number & !writable);
+        } else {
+            self.ram.switch_bank(self.ram_bank_number);
+        }
+    }
+}
+
+impl MemoryMappedBank for MemoryBankControllerMmm01 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[self.lower_bank].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else if self.ram_enable {
+            self.ram.read_bank_value(ops, address - 0xA000)
+        } else {
+            0xFF
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        let value = value as usize;
+        if address < 0x2000 {
+            // Enable RAM. Before mapping also sets the RAM bank mask, and maps the game.
+            self.ram_enable = (value & 0x0F) == 0x0A;
+            if !self.mapped {
+                self.ram_bank_mask = (value >> 4) & 0x03;
+                self.mapped = value & 0x40 != 0;
+            }
+        } else if address < 0x4000 {
+            // Select ROM bank (lower 5 bits). Before mapping also bits 5-6.
+            let writable = self.writable_rom_bank_bits();
+            self.rom_bank_low = (self.rom_bank_low & !writable) | (value & writable);
+            if !self.mapped {
+                self.rom_bank_high &= !0x03;
+                self.rom_bank_high |= (value >> 5) & 0x03;
+            }
+        } else if address < 0x6000 {
+            // Select RAM bank. Before mapping also the upper RAM bank bits, ROM bank bits 7-8,
+            // and whether the mode can be changed.
+            let writable = self.writable_ram_bank_bits();
+            self.ram_bank_number = (self.ram_bank_number & !writable) | (value & writable);
+            if !self.mapped {
+                self.rom_bank_high &= !0x0C;
+                self.rom_bank_high |= ((value >> 4) & 0x03) << 2;
+                self.mbc1_mode_locked = value & 0x40 != 0;
+            }
+        } else if address < 0x8000 {
+            // Select MBC1 mode. Before mapping also the ROM bank mask.
+            if !self.mapped || !self.mbc1_mode_locked {
+                self.mbc1_mode = value & 0x01 != 0;
+            }
+            if !self.mapped {
+                self.rom_bank_mask = (value >> 1) & 0x1E;
+            }
+        } else if address < 0xA000 {
+            // nothing
+        } else if self.ram_enable {
+            self.ram.set_bank_value(ops, address - 0xA000, value as u8);
+        }
+        self.update_banks();
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 enum CartridgeRam {
     No(NoRam),
@@ -733,8 +1079,18 @@ impl MemoryMappedBank for CartridgeRam {
 impl CartridgeRam {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn switch_bank(&mut self, bank: usize) {
-        if let Self::Volatile(r) = self {
-            r.switch_bank(bank);
+        match self {
+            Self::No(_) => {}
+            Self::Volatile(r) => r.switch_bank(bank),
+            Self::NonVolatile(r) => r.switch_bank(bank),
+        }
+    }
+
+    fn current_bank(&self) -> usize {
+        match self {
+            Self::No(_) => 0,
+            Self::Volatile(r) => r.switchable_bank.current_bank,
+            Self::NonVolatile(r) => r.0.current_bank,
         }
     }
 }
@@ -787,7 +1143,7 @@ impl VolatileRam {
     }
 
     fn switch_bank(&mut self, bank: usize) {
-        self.switchable_bank.switch_bank(bank)
+        self.switchable_bank.switch_bank_wrapping(bank)
     }
 }
 
@@ -861,6 +1217,10 @@ impl NonVolatileRam {
     fn new(ram: Vec<SramChunk>) -> Self {
         Self(SwitchableBank::new(ram, 0))
     }
+
+    fn switch_bank(&mut self, bank: usize) {
+        self.0.switch_bank_wrapping(bank)
+    }
 }
 
 impl MemoryMappedBank for NonVolatileRam {
@@ -885,7 +1245,7 @@ impl MemoryMappedBank for NonVolatileRam {
 }
 
 pub struct GamePak<Storage: PersistentStorage> {
-    title: String,
+    header: CartridgeHeader,
     hash: u32,
     ops: BankOps<Storage>,
     mbc: MemoryBankController,
@@ -935,21 +1295,62 @@ const TITLE: Range<usize> = Range {
     start: 0x0134,
     end: 0x0144,
 };
+const LOGO: Range<usize> = Range {
+    start: 0x0104,
+    end: 0x0134,
+};
 
-fn get_number_of_banks(rom: &[u8]) -> usize {
-    let number_of_banks = match rom[ROM_SIZE_ADDRESS] {
-        n if n <= 0x08 => 2usize.pow(n as u32 + 1),
-        0x52 => 72,
-        0x53 => 80,
-        0x54 => 96,
-        v => panic!("Unknown ROM size {}", v),
-    };
-    assert_eq!(
-        number_of_banks,
-        rom.len() / (BANK_SIZE as usize),
-        "ROM wrong size"
-    );
-    number_of_banks
+const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+/// Size of the ROM region each sub-game of an MBC1 multicart can see.
+const MBC1_MULTICART_GAME_SIZE: usize = 0x40000;
+
+/// MBC1 multicarts have a cartridge header (with the logo) for each sub-game at the start of every
+/// 256kB block. A regular MBC1 game won't have a logo anywhere but the start of the ROM.
+fn is_mbc1_multicart(rom: &[u8]) -> bool {
+    rom.chunks_exact(MBC1_MULTICART_GAME_SIZE)
+        .skip(1)
+        .any(|game| game[LOGO] == NINTENDO_LOGO)
+}
+
+/// The part of the ROM where the cartridge header describing the whole cartridge lives. This is
+/// normally the start of the ROM, but MMM01 carts put it with the menu in the last 32kB, and the
+/// start of the ROM has the header of the first sub-game instead.
+fn header_bank(rom: &[u8]) -> &[u8] {
+    if rom.len() > 2 * BANK_SIZE as usize {
+        let menu = &rom[rom.len() - 2 * BANK_SIZE as usize..];
+        if matches!(menu[MBC_TYPE_ADDRESS], 0x0B..=0x0D) {
+            return menu;
+        }
+    }
+    rom
+}
+
+fn get_number_of_banks(rom: &[u8]) -> Result<usize, GamePakError> {
+    // Every ROM has at least two banks, and the header is in the first one
+    let min_len = 2 * BANK_SIZE as usize;
+    if rom.len() < min_len {
+        return Err(GamePakError::WrongRomLength {
+            expected: min_len,
+            actual: rom.len(),
+        });
+    }
+
+    let rom_size = header_bank(rom)[ROM_SIZE_ADDRESS];
+    let number_of_banks =
+        number_of_rom_banks(rom_size).ok_or(GamePakError::UnknownRomSize(rom_size))?;
+    let expected = number_of_banks * BANK_SIZE as usize;
+    if rom.len() != expected {
+        return Err(GamePakError::WrongRomLength {
+            expected,
+            actual: rom.len(),
+        });
+    }
+    Ok(number_of_banks)
 }
 
 fn make_banks(number_of_banks: usize) -> Vec<RomBank> {
@@ -960,8 +1361,8 @@ fn make_banks(number_of_banks: usize) -> Vec<RomBank> {
     banks
 }
 
-fn banks_and_chunks_from_rom(rom: &[u8]) -> (Vec<RomBank>, Vec<RomChunk>) {
-    let number_of_banks = get_number_of_banks(rom);
+fn banks_and_chunks_from_rom(rom: &[u8]) -> Result<(Vec<RomBank>, Vec<RomChunk>), GamePakError> {
+    let number_of_banks = get_number_of_banks(rom)?;
     let banks = make_banks(number_of_banks);
 
     let mut chunks = Vec::new();
@@ -971,11 +1372,13 @@ fn banks_and_chunks_from_rom(rom: &[u8]) -> (Vec<RomBank>, Vec<RomChunk>) {
         chunks.push(RomChunk(Box::new(rom[start..end].to_vec())));
     }
 
-    (banks, chunks)
+    Ok((banks, chunks))
 }
 
-fn banks_and_chunks_from_static_rom(rom: &'static [u8]) -> (Vec<RomBank>, Vec<RomChunk>) {
-    let number_of_banks = get_number_of_banks(rom);
+fn banks_and_chunks_from_static_rom(
+    rom: &'static [u8],
+) -> Result<(Vec<RomBank>, Vec<RomChunk>), GamePakError> {
+    let number_of_banks = get_number_of_banks(rom)?;
     let banks = make_banks(number_of_banks);
 
     let mut chunks = Vec::new();
@@ -985,7 +1388,7 @@ fn banks_and_chunks_from_static_rom(rom: &'static [u8]) -> (Vec<RomBank>, Vec<Ro
         chunks.push(RomChunk(Box::new(&rom[start..end])));
     }
 
-    (banks, chunks)
+    Ok((banks, chunks))
 }
 
 #[derive(Copy, Clone)]
@@ -995,15 +1398,36 @@ struct RamDescription {
 }
 
 impl RamDescription {
-    fn from_bytes(ram_size: u8, controller: u8) -> Self {
+    fn from_bytes(ram_size: u8, controller: u8) -> Result<Self, GamePakError> {
         if controller == 0x6 {
-            return Self {
+            return Ok(Self {
                 bank_size: 512,
                 number_of_banks: 1,
-            };
+            });
+        }
+
+        if controller == 0x20 {
+            return Ok(Self {
+                bank_size: RAM_WINDOW_SIZE,
+                number_of_banks: 8,
+            });
+        }
+
+        if controller == 0x22 {
+            return Ok(Self {
+                bank_size: EEPROM_SIZE,
+                number_of_banks: 1,
+            });
+        }
+
+        if controller == 0xFD {
+            return Ok(Self {
+                bank_size: TAMA5_RAM_SIZE,
+                number_of_banks: 1,
+            });
         }
 
-        match ram_size {
+        Ok(match ram_size {
             0 => Self {
                 bank_size: 0,
                 number_of_banks: 0,
@@ -1023,16 +1447,28 @@ impl RamDescription {
                 bank_size: 0x2000,
                 number_of_banks: 4,
             },
-            v => panic!("Unknown RAM byte {:x}", v),
-        }
+            // 8kB 16 Banks = 128kB
+            4 => Self {
+                bank_size: 0x2000,
+                number_of_banks: 16,
+            },
+            // 8kB 8 Banks = 64kB
+            5 => Self {
+                bank_size: 0x2000,
+                number_of_banks: 8,
+            },
+            v => return Err(GamePakError::UnknownRamSize(v)),
+        })
     }
 
-    fn total_len(&self) -> u16 {
-        self.bank_size * self.number_of_banks as u16
+    fn total_len(&self) -> u64 {
+        self.bank_size as u64 * self.number_of_banks as u64
     }
 
     fn into_ram(self) -> Vec<MemoryChunk> {
-        vec![MemoryChunk::from_range(0..self.bank_size); self.number_of_banks]
+        (0..self.number_of_banks)
+            .map(|_| MemoryChunk::from_range(0..self.bank_size))
+            .collect()
     }
 
     fn into_sram(self) -> Vec<SramChunk> {
@@ -1051,15 +1487,58 @@ fn load_sram_from_file(sram: &mut [SramChunk], file: &mut impl io::Read) -> io::
     Ok(())
 }
 
+/// Why a `GamePak` couldn't be loaded.
+#[derive(Debug)]
+pub enum GamePakError {
+    Io(io::Error),
+    /// The cartridge type in the header isn't one we know about.
+    UnknownMemoryBankController(u8),
+    /// The ROM size in the header isn't one we know about.
+    UnknownRomSize(u8),
+    /// The RAM size in the header isn't one we know about.
+    UnknownRamSize(u8),
+    /// The title in the header isn't valid UTF-8.
+    MalformedTitle(Vec<u8>),
+    /// The ROM isn't the size the header says it is.
+    WrongRomLength {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl From<io::Error> for GamePakError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl fmt::Display for GamePakError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::UnknownMemoryBankController(v) => write!(f, "unknown cartridge type {v:#04x}"),
+            Self::UnknownRomSize(v) => write!(f, "unknown ROM size {v:#04x}"),
+            Self::UnknownRamSize(v) => write!(f, "unknown RAM size {v:#04x}"),
+            Self::MalformedTitle(t) => write!(f, "malformed title {t:?}"),
+            Self::WrongRomLength { expected, actual } => {
+                write!(f, "ROM is {actual} bytes but should be {expected}")
+            }
+        }
+    }
+}
+
 impl<Storage: PersistentStorage> GamePak<Storage> {
-    pub fn from_storage(storage: &mut Storage, key: &str) -> io::Result<Self> {
+    pub fn from_storage(storage: &mut Storage, key: &str) -> Result<Self, GamePakError> {
         let mut rom_file = storage.open(OpenMode::Read, key)?;
         let mut rom: Vec<u8> = vec![];
         rom_file.read_to_end(&mut rom)?;
         GamePak::new(&rom, storage, Some(&format!("{key}.sav")))
     }
 
-    pub fn from_storage_without_sav(storage: &mut Storage, key: &str) -> io::Result<Self> {
+    pub fn from_storage_without_sav(
+        storage: &mut Storage,
+        key: &str,
+    ) -> Result<Self, GamePakError> {
         let mut rom_file = storage.open(OpenMode::Read, key)?;
         let mut rom: Vec<u8> = vec![];
         rom_file.read_to_end(&mut rom)?;
@@ -1070,18 +1549,40 @@ impl<Storage: PersistentStorage> GamePak<Storage> {
         rom: &'static [u8],
         storage: &mut Storage,
         sram_key: Option<&str>,
-    ) -> io::Result<Self> {
-        assert_eq!(rom.len() % (BANK_SIZE as usize), 0, "ROM wrong size");
+    ) -> Result<Self, GamePakError> {
+        Self::new_static_with_mbc1_wiring(rom, storage, sram_key, Mbc1Wiring::Detect)
+    }
 
-        let (rom_banks, rom_chunks) = banks_and_chunks_from_static_rom(rom);
-        Self::inner_new(rom, storage, rom_banks, rom_chunks, sram_key)
+    /// Like `new_static`, but if the cartridge uses an MBC1 this picks how its banks are wired
+    /// instead of guessing.
+    pub fn new_static_with_mbc1_wiring(
+        rom: &'static [u8],
+        storage: &mut Storage,
+        sram_key: Option<&str>,
+        mbc1_wiring: Mbc1Wiring,
+    ) -> Result<Self, GamePakError> {
+        let (rom_banks, rom_chunks) = banks_and_chunks_from_static_rom(rom)?;
+        Self::inner_new(rom, storage, rom_banks, rom_chunks, sram_key, mbc1_wiring)
     }
 
-    pub fn new(rom: &[u8], storage: &mut Storage, sram_key: Option<&str>) -> io::Result<Self> {
-        assert_eq!(rom.len() % (BANK_SIZE as usize), 0, "ROM wrong size");
+    pub fn new(
+        rom: &[u8],
+        storage: &mut Storage,
+        sram_key: Option<&str>,
+    ) -> Result<Self, GamePakError> {
+        Self::new_with_mbc1_wiring(rom, storage, sram_key, Mbc1Wiring::Detect)
+    }
 
-        let (rom_banks, rom_chunks) = banks_and_chunks_from_rom(rom);
-        Self::inner_new(rom, storage, rom_banks, rom_chunks, sram_key)
+    /// Like `new`, but if the cartridge uses an MBC1 this picks how its banks are wired instead of
+    /// guessing.
+    pub fn new_with_mbc1_wiring(
+        rom: &[u8],
+        storage: &mut Storage,
+        sram_key: Option<&str>,
+        mbc1_wiring: Mbc1Wiring,
+    ) -> Result<Self, GamePakError> {
+        let (rom_banks, rom_chunks) = banks_and_chunks_from_rom(rom)?;
+        Self::inner_new(rom, storage, rom_banks, rom_chunks, sram_key, mbc1_wiring)
     }
 
     fn inner_new(
@@ -1090,101 +1591,226 @@ impl<Storage: PersistentStorage> GamePak<Storage> {
         rom_banks: Vec<RomBank>,
         rom_chunks: Vec<RomChunk>,
         sram_key: Option<&str>,
-    ) -> io::Result<Self> {
+        mbc1_wiring: Mbc1Wiring,
+    ) -> Result<Self, GamePakError> {
         let hash = rom_hash(rom);
-        let title_slice = &rom[TITLE];
-        let title_end = title_slice
-            .iter()
-            .position(|&c| c == b'\0')
-            .unwrap_or(title_slice.len());
-        let title = str::from_utf8(&title_slice[..title_end])
-            .unwrap_or_else(|_| panic!("Malformed title {:?}", title_slice))
-            .into();
+        let header = header_bank(rom);
+        let cartridge_header = CartridgeHeader::from_rom(header)?;
+
+        // Cartridges with a clock or flash keep it in the save file after the RAM
+        let footer_len = match header[MBC_TYPE_ADDRESS] {
+            0x0F | 0x10 => RTC_FOOTER_LEN,
+            0x20 => FLASH_SIZE,
+            0xFD => TAMA5_FOOTER_LEN,
+            0xFE => HUC3_FOOTER_LEN,
+            _ => 0,
+        };
 
         let mut sram_file = None;
+        let mut footer = None;
         let mut get_sram = |ram_descr: RamDescription| -> io::Result<Vec<SramChunk>> {
             let mut sram = ram_descr.into_sram();
             if let Some(sram_key) = sram_key {
                 let mut file = storage.open(OpenMode::ReadWrite, sram_key)?;
-                file.set_len(ram_descr.total_len() as u64)?;
+                let existing_len = file.seek(SeekFrom::End(0))?;
+                file.seek(SeekFrom::Start(0))?;
+                file.set_len(ram_descr.total_len() + footer_len)?;
                 load_sram_from_file(&mut sram, &mut file)?;
+                // A save file from before the footer was written gets a fresh one
+                if existing_len >= ram_descr.total_len() + footer_len {
+                    let mut footer_bytes = vec![0; footer_len as usize];
+                    file.read_exact(&mut footer_bytes)?;
+                    footer = Some(footer_bytes);
+                }
                 sram_file = Some(file);
             }
             Ok(sram)
         };
 
-        /*
-         *  0x00  ROM ONLY
-         *  0x01  MBC1
-         *  0x02  MBC1+RAM
-         *  0x03  MBC1+RAM+BATTERY
-         *  0x05  MBC2
-         *  0x06  MBC2+BATTERY
-         *  0x08  ROM+RAM
-         *  0x09  ROM+RAM+BATTERY
-         *  0x0B  MMM01
-         *  0x0C  MMM01+RAM
-         *  0x0D  MMM01+RAM+BATTERY
-         *  0x0F  MBC3+TIMER+BATTERY
-         *  0x10  MBC3+TIMER+RAM+BATTERY
-         *  0x11  MBC3
-         *  0x12  MBC3+RAM
-         *  0x13  MBC3+RAM+BATTERY
-         *  0x19  MBC5
-         *  0x1A  MBC5+RAM
-         *  0x1B  MBC5+RAM+BATTERY
-         *  0x1C  MBC5+RUMBLE
-         *  0x1D  MBC5+RUMBLE+RAM
-         *  0x1E  MBC5+RUMBLE+RAM+BATTERY
-         *  0x20  MBC6
-         *  0x22  MBC7+SENSOR+RUMBLE+RAM+BATTERY
-         *  0xFC  POCKET CAMERA
-         *  0xFD  BANDAI TAMA5
-         *  0xFE  HuC3
-         *  0xFF  HuC1+RAM+BATTERY
-         */
-
-        let ram_descr = RamDescription::from_bytes(rom[RAM_SIZE_ADDRESS], rom[MBC_TYPE_ADDRESS]);
+        // The names of the cartridge types are in `header::cartridge_type_name`
+        let ram_descr =
+            RamDescription::from_bytes(header[RAM_SIZE_ADDRESS], header[MBC_TYPE_ADDRESS])?;
         let vram = || VolatileRam::new(ram_descr.into_ram());
         let viram = VolatileInternalRam::new;
         let nvram = NonVolatileRam::new;
         let nviram = NonVolatileInternalRam::new;
+        let multicart = match mbc1_wiring {
+            Mbc1Wiring::Detect => is_mbc1_multicart(rom),
+            Mbc1Wiring::Standard => false,
+            Mbc1Wiring::Multicart => true,
+        };
 
-        let mbc: MemoryBankController = match rom[MBC_TYPE_ADDRESS] {
+        let mut mbc: MemoryBankController = match header[MBC_TYPE_ADDRESS] {
             0x00 => MemoryBankController0::new(rom_banks).into(),
+            0x01 if multicart => MemoryBankController1::new_multicart(rom_banks, NoRam).into(),
             0x01 => MemoryBankController1::new(rom_banks, NoRam).into(),
+            0x02 if multicart => MemoryBankController1::new_multicart(rom_banks, vram()).into(),
             0x02 => MemoryBankController1::new(rom_banks, vram()).into(),
+            0x03 if multicart => {
+                let sram = get_sram(ram_descr)?;
+                MemoryBankController1::new_multicart(rom_banks, nvram(sram)).into()
+            }
             0x03 => MemoryBankController1::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
             0x05 => MemoryBankController2::new(rom_banks, viram()).into(),
+            0x0B => MemoryBankControllerMmm01::new(rom_banks, NoRam).into(),
+            0x0C => MemoryBankControllerMmm01::new(rom_banks, vram()).into(),
+            0x0D => {
+                let sram = get_sram(ram_descr)?;
+                MemoryBankControllerMmm01::new(rom_banks, nvram(sram)).into()
+            }
             0x06 => MemoryBankController2::new(rom_banks, nviram(get_sram(ram_descr)?)).into(),
+            0x0F => {
+                // The save file is still needed for the clock
+                get_sram(ram_descr)?;
+                MemoryBankController3::new(rom_banks, NoRam).into()
+            }
+            0x10 => MemoryBankController3::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
             0x11 => MemoryBankController3::new(rom_banks, NoRam).into(),
             0x12 => MemoryBankController3::new(rom_banks, vram()).into(),
             0x13 => MemoryBankController3::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
-            0x1b => MemoryBankController5::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
-            v => panic!("Unknown Memory Bank Controller {:#x}", v),
+            0x19 => MemoryBankController5::new(rom_banks, NoRam).into(),
+            0x1A => MemoryBankController5::new(rom_banks, vram()).into(),
+            0x1B => MemoryBankController5::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
+            0x1C => MemoryBankController5::new_with_rumble(rom_banks, NoRam).into(),
+            0x1D => MemoryBankController5::new_with_rumble(rom_banks, vram()).into(),
+            0x1E => {
+                let sram = get_sram(ram_descr)?;
+                MemoryBankController5::new_with_rumble(rom_banks, nvram(sram)).into()
+            }
+            0x20 => {
+                let sram = get_sram(ram_descr)?;
+                MemoryBankController6::new(rom_banks, sram, ram_descr.total_len()).into()
+            }
+            0x22 => {
+                let eeprom = get_sram(ram_descr)?.remove(0);
+                MemoryBankController7::new(rom_banks, eeprom).into()
+            }
+            0xFC => {
+                let sram = get_sram(ram_descr)?;
+                MemoryBankControllerCamera::new(rom_banks, nvram(sram)).into()
+            }
+            0xFD => {
+                let eeprom = get_sram(ram_descr)?.remove(0);
+                let clock = Tama5Clock::new(ram_descr.total_len());
+                MemoryBankControllerTama5::new(rom_banks, eeprom, clock).into()
+            }
+            0xFE => {
+                let sram = get_sram(ram_descr)?;
+                let clock = HuC3Clock::new(ram_descr.total_len());
+                MemoryBankControllerHuC3::new(rom_banks, nvram(sram), clock).into()
+            }
+            0xFF => MemoryBankControllerHuC1::new(rom_banks, nvram(get_sram(ram_descr)?)).into(),
+            v => return Err(GamePakError::UnknownMemoryBankController(v)),
         };
 
+        let footer_offset = ram_descr.total_len();
+        match &mut mbc {
+            MemoryBankController::Three(mbc3) if footer_len > 0 => {
+                mbc3.rtc = Some(match &footer {
+                    Some(footer) => RealTimeClock::load(footer_offset, &mut &footer[..])?,
+                    None => RealTimeClock::new(footer_offset),
+                });
+            }
+            MemoryBankController::HuC3(huc3) => {
+                if let Some(footer) = &footer {
+                    huc3.clock = HuC3Clock::load(footer_offset, &mut &footer[..])?;
+                }
+            }
+            MemoryBankController::Tama5(tama5) => {
+                if let Some(footer) = &footer {
+                    tama5.clock = Tama5Clock::load(footer_offset, &mut &footer[..])?;
+                }
+            }
+            MemoryBankController::Six(mbc6) => match (&footer, &mut sram_file) {
+                (Some(footer), _) => mbc6.load_flash(&mut &footer[..])?,
+                // A new flash chip comes erased
+                (None, Some(file)) => mbc6.store_flash(file)?,
+                (None, None) => {}
+            },
+            _ => {}
+        }
+
         let ops = BankOps {
             sram_file,
             rom_memory: rom_chunks,
+            clock: rtc::default_wall_clock(),
+            rumble: Box::new(NullRumble),
+            infrared: Box::new(NullInfraredPeer),
+            tone: Box::new(NullToneGenerator),
+            tilt: Box::new(LevelTiltSensor),
+            image_source: Box::new(TestPatternImageSource),
         };
 
         Ok(GamePak {
             ops,
-            title,
+            header: cartridge_header,
             hash,
             mbc,
         })
     }
 
+    /// The title from the header. Replays are matched against it, so for any cartridge that loaded
+    /// before the header was parsed this is the whole title area up to the first NUL, as it always
+    /// was. Leaving out the manufacturer code only makes a difference when the title runs into the
+    /// CGB flag, which isn't valid UTF-8.
     pub fn title(&self) -> &str {
-        &self.title
+        &self.header.title
     }
 
     pub fn hash(&self) -> u32 {
         self.hash
     }
 
+    pub fn header(&self) -> &CartridgeHeader {
+        &self.header
+    }
+
+    /// Start the cartridge's real-time clock, if it has a fresh one, counting from now. This waits
+    /// until the cartridge is loaded so the wall-clock can be replaced first.
+    pub(crate) fn start_clock(&mut self) {
+        let clock = &self.ops.clock;
+        match &mut self.mbc {
+            MemoryBankController::Three(mbc3) => {
+                if let Some(rtc) = &mut mbc3.rtc {
+                    rtc.footer.start(clock.now());
+                }
+            }
+            MemoryBankController::HuC3(huc3) => huc3.clock.footer.start(clock.now()),
+            MemoryBankController::Tama5(tama5) => tama5.clock.footer.start(clock.now()),
+            _ => {}
+        }
+    }
+
+    /// Replace the source of time used by the cartridge's real-time clock, if it has one.
+    pub fn set_wall_clock(&mut self, clock: impl WallClock + 'static) {
+        self.ops.clock = Box::new(clock);
+    }
+
+    /// Set where the motor of rumble cartridges is connected to.
+    pub fn set_rumble(&mut self, rumble: impl Rumble + 'static) {
+        self.ops.rumble = Box::new(rumble);
+    }
+
+    /// Set what the infrared LED and sensor of HuC cartridges are pointed at.
+    pub fn set_infrared_peer(&mut self, peer: impl InfraredPeer + 'static) {
+        self.ops.infrared = Box::new(peer);
+    }
+
+    /// Set where the speaker of HuC3 cartridges is connected to.
+    pub fn set_tone_generator(&mut self, tone: impl ToneGenerator + 'static) {
+        self.ops.tone = Box::new(tone);
+    }
+
+    /// Set where the accelerometer of MBC7 cartridges gets its tilt from.
+    pub fn set_tilt_sensor(&mut self, tilt: impl TiltSensor + 'static) {
+        self.ops.tilt = Box::new(tilt);
+    }
+
+    /// Set where the Pocket Camera gets its pictures from.
+    pub fn set_image_source(&mut self, image_source: impl ImageSource + 'static) {
+        self.ops.image_source = Box::new(image_source);
+    }
+
     pub fn save_state<W: io::Write>(&self, mut writer: W) -> super::Result<()> {
         crate::codec::serialize_into(&mut writer, &self.hash)?;
         crate::codec::serialize_into(&mut writer, &self.mbc)?;
@@ -1202,6 +1828,9 @@ impl<Storage: PersistentStorage> GamePak<Storage> {
 
 impl<Storage: PersistentStorage> fmt::Debug for GamePak<Storage> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "GamePak({:?}, {:?})", self.title, self.mbc)
+        write!(f, "GamePak({:?}, {:?})", self.header.title, self.mbc)
     }
 }
+
+#[cfg(test)]
+mod tests;
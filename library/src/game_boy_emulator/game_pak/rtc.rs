@@ -0,0 +1,374 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::BankOps;
+use crate::io::{self, Seek as _, SeekFrom, Write as _};
+use crate::storage::PersistentStorage;
+use serde_derive::{Deserialize, Serialize};
+
+/// Source of the current time for cartridges with a real-time clock. Returns seconds since the
+/// unix epoch.
+pub trait WallClock: Send {
+    fn now(&self) -> u64;
+}
+
+/// `WallClock` backed by the host's system time.
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct SystemWallClock;
+
+#[cfg(feature = "std")]
+impl WallClock for SystemWallClock {
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// `WallClock` that is stuck at one moment in time.
+#[derive(Default)]
+pub struct FrozenWallClock(pub u64);
+
+impl WallClock for FrozenWallClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+pub(super) fn default_wall_clock() -> alloc::boxed::Box<dyn WallClock> {
+    alloc::boxed::Box::new(SystemWallClock)
+}
+
+#[cfg(not(feature = "std"))]
+pub(super) fn default_wall_clock() -> alloc::boxed::Box<dyn WallClock> {
+    alloc::boxed::Box::new(FrozenWallClock::default())
+}
+
+/// Where a cartridge's clock is kept in the save file, and the time the clock was last brought up
+/// to date. Each kind of clock lays its footer out differently, but they all keep a 64-bit unix
+/// timestamp in it somewhere, which is zero if the clock has never been started.
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct ClockFooter {
+    offset: u64,
+    timestamp_at: usize,
+    pub(super) timestamp: Option<u64>,
+}
+
+impl ClockFooter {
+    pub(super) fn new(offset: u64, timestamp_at: usize) -> Self {
+        Self {
+            offset,
+            timestamp_at,
+            timestamp: None,
+        }
+    }
+
+    /// Read the clock's footer from the save file, and pick the timestamp out of it.
+    pub(super) fn load<const LEN: usize>(
+        offset: u64,
+        timestamp_at: usize,
+        file: &mut impl io::Read,
+    ) -> io::Result<(Self, [u8; LEN])> {
+        let mut data = [0; LEN];
+        file.read_exact(&mut data)?;
+
+        let mut footer = Self::new(offset, timestamp_at);
+        let timestamp = u64::from_le_bytes(data[timestamp_at..][..8].try_into().unwrap());
+        footer.timestamp = (timestamp != 0).then_some(timestamp);
+        Ok((footer, data))
+    }
+
+    /// Fill in the timestamp in the clock's footer.
+    pub(super) fn write_timestamp(&self, data: &mut [u8]) {
+        data[self.timestamp_at..][..8].copy_from_slice(&self.timestamp.unwrap_or(0).to_le_bytes());
+    }
+
+    pub(super) fn save(&self, ops: &mut BankOps<impl PersistentStorage>, data: &[u8]) {
+        if let Some(file) = &mut ops.sram_file {
+            file.seek(SeekFrom::Start(self.offset)).unwrap();
+            file.write_all(data).unwrap();
+        }
+    }
+
+    /// Start counting from the given time, unless the clock is already running.
+    pub(super) fn start(&mut self, now: u64) {
+        self.timestamp.get_or_insert(now);
+    }
+}
+
+const DAY_HIGH_MASK: u8 = 0x01;
+const HALT_FLAG: u8 = 0x40;
+const CARRY_FLAG: u8 = 0x80;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct RtcRegisters {
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    day_low: u8,
+    day_high: u8,
+}
+
+impl RtcRegisters {
+    fn days(&self) -> u64 {
+        (((self.day_high & DAY_HIGH_MASK) as u64) << 8) | self.day_low as u64
+    }
+
+    fn set_days(&mut self, days: u64) {
+        if days > 0x1FF {
+            self.day_high |= CARRY_FLAG;
+        }
+        let days = days & 0x1FF;
+        self.day_low = days as u8;
+        self.day_high = (self.day_high & !DAY_HIGH_MASK) | (days >> 8) as u8;
+    }
+
+    fn halted(&self) -> bool {
+        self.day_high & HALT_FLAG != 0
+    }
+
+    /// Whether all the counters are within the range the clock counts through on its own.
+    fn normalized(&self) -> bool {
+        self.seconds < 60 && self.minutes < 60 && self.hours < 24
+    }
+
+    /// Advance by one second the way the hardware does. Out of range counters keep counting until
+    /// they wrap around at their bit width without carrying into the next counter.
+    fn tick(&mut self) {
+        self.seconds = (self.seconds + 1) & 0x3F;
+        if self.seconds != 60 {
+            return;
+        }
+        self.seconds = 0;
+
+        self.minutes = (self.minutes + 1) & 0x3F;
+        if self.minutes != 60 {
+            return;
+        }
+        self.minutes = 0;
+
+        self.hours = (self.hours + 1) & 0x1F;
+        if self.hours != 24 {
+            return;
+        }
+        self.hours = 0;
+
+        self.set_days(self.days() + 1);
+    }
+
+    fn advance(&mut self, mut seconds: u64) {
+        while seconds > 0 && !self.normalized() {
+            self.tick();
+            seconds -= 1;
+        }
+        if seconds == 0 {
+            return;
+        }
+
+        let total = self.seconds as u64
+            + self.minutes as u64 * 60
+            + self.hours as u64 * 60 * 60
+            + self.days() * 60 * 60 * 24
+            + seconds;
+        self.seconds = (total % 60) as u8;
+        self.minutes = (total / 60 % 60) as u8;
+        self.hours = (total / (60 * 60) % 24) as u8;
+        self.set_days(total / (60 * 60 * 24));
+    }
+
+    fn read(&self, register: u8) -> u8 {
+        match register {
+            0x08 => self.seconds,
+            0x09 => self.minutes,
+            0x0A => self.hours,
+            0x0B => self.day_low,
+            0x0C => self.day_high,
+            _ => 0xFF,
+        }
+    }
+
+    fn write(&mut self, register: u8, value: u8) {
+        match register {
+            0x08 => self.seconds = value & 0x3F,
+            0x09 => self.minutes = value & 0x3F,
+            0x0A => self.hours = value & 0x1F,
+            0x0B => self.day_low = value,
+            0x0C => self.day_high = value & (CARRY_FLAG | HALT_FLAG | DAY_HIGH_MASK),
+            _ => {}
+        }
+    }
+
+    fn to_footer(self, out: &mut [u8]) {
+        let values = [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            self.day_low,
+            self.day_high,
+        ];
+        for (chunk, v) in out.chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&(v as u32).to_le_bytes());
+        }
+    }
+
+    fn from_footer(data: &[u8]) -> Self {
+        let mut values = data
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()) as u8);
+        let mut next = || values.next().unwrap();
+        Self {
+            seconds: next() & 0x3F,
+            minutes: next() & 0x3F,
+            hours: next() & 0x1F,
+            day_low: next(),
+            day_high: next() & (CARRY_FLAG | HALT_FLAG | DAY_HIGH_MASK),
+        }
+    }
+}
+
+/// Size of the clock data appended after the cartridge RAM in the save file. The layout is the one
+/// used by most other emulators: the current and latched seconds, minutes, hours, day low and day
+/// high registers as little-endian 32-bit values, followed by a 64-bit unix timestamp.
+pub(super) const RTC_FOOTER_LEN: u64 = 48;
+
+const RTC_TIMESTAMP_AT: usize = 40;
+
+/// The MBC3 real-time clock. The live registers are only brought up to date with the wall-clock
+/// when they are latched or written to, which is fine since the game only ever sees the latched
+/// copy. A fresh clock starts counting once the cartridge is loaded.
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct RealTimeClock {
+    pub(super) footer: ClockFooter,
+    registers: RtcRegisters,
+    latched: RtcRegisters,
+    latch_armed: bool,
+}
+
+impl RealTimeClock {
+    pub(super) fn new(offset: u64) -> Self {
+        Self {
+            footer: ClockFooter::new(offset, RTC_TIMESTAMP_AT),
+            registers: RtcRegisters::default(),
+            latched: RtcRegisters::default(),
+            latch_armed: false,
+        }
+    }
+
+    /// Load the clock from the footer in the save file. A footer that was never written (all
+    /// zeros) gives a fresh clock.
+    pub(super) fn load(offset: u64, file: &mut impl io::Read) -> io::Result<Self> {
+        let (footer, data) =
+            ClockFooter::load::<{ RTC_FOOTER_LEN as usize }>(offset, RTC_TIMESTAMP_AT, file)?;
+
+        let mut rtc = Self::new(offset);
+        if footer.timestamp.is_some() {
+            rtc.registers = RtcRegisters::from_footer(&data[0..20]);
+            rtc.latched = RtcRegisters::from_footer(&data[20..40]);
+            rtc.footer = footer;
+        }
+        Ok(rtc)
+    }
+
+    fn to_footer(&self) -> [u8; RTC_FOOTER_LEN as usize] {
+        let mut data = [0; RTC_FOOTER_LEN as usize];
+        self.registers.to_footer(&mut data[0..20]);
+        self.latched.to_footer(&mut data[20..40]);
+        self.footer.write_timestamp(&mut data);
+        data
+    }
+
+    /// Advance the live registers to the given time. Time going backwards is ignored.
+    fn catch_up(&mut self, now: u64) {
+        let elapsed = now.saturating_sub(self.footer.timestamp.unwrap_or(now));
+        if !self.registers.halted() {
+            self.registers.advance(elapsed);
+        }
+        self.footer.timestamp = Some(now);
+    }
+
+    /// Handle a write to the latch register. Writing 0x00 then 0x01 copies the live registers into
+    /// the latched ones.
+    pub(super) fn write_latch(&mut self, ops: &mut BankOps<impl PersistentStorage>, value: u8) {
+        if self.latch_armed && value == 0x01 {
+            self.catch_up(ops.clock.now());
+            self.latched = self.registers;
+            self.footer.save(ops, &self.to_footer());
+        }
+        self.latch_armed = value == 0x00;
+    }
+
+    pub(super) fn read_register(&self, register: u8) -> u8 {
+        self.latched.read(register)
+    }
+
+    pub(super) fn write_register(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        register: u8,
+        value: u8,
+    ) {
+        self.catch_up(ops.clock.now());
+        self.registers.write(register, value);
+        self.latched.write(register, value);
+        self.footer.save(ops, &self.to_footer());
+    }
+}
+
+#[test]
+fn rtc_registers_advance_carries() {
+    let mut regs = RtcRegisters {
+        seconds: 59,
+        minutes: 59,
+        hours: 23,
+        day_low: 0xFF,
+        day_high: 0x01,
+    };
+    regs.advance(1);
+    assert_eq!(
+        regs,
+        RtcRegisters {
+            seconds: 0,
+            minutes: 0,
+            hours: 0,
+            day_low: 0,
+            day_high: CARRY_FLAG,
+        }
+    );
+}
+
+#[test]
+fn rtc_registers_advance_out_of_range() {
+    let mut regs = RtcRegisters {
+        seconds: 62,
+        minutes: 59,
+        ..Default::default()
+    };
+    regs.advance(2);
+    assert_eq!(regs.seconds, 0);
+    assert_eq!(regs.minutes, 59);
+
+    regs.advance(60 * 60 + 1);
+    assert_eq!((regs.seconds, regs.minutes, regs.hours), (1, 59, 1));
+}
+
+#[test]
+fn rtc_footer_round_trip() {
+    let mut rtc = RealTimeClock::new(0);
+    rtc.footer.timestamp = Some(1000);
+    rtc.registers = RtcRegisters {
+        seconds: 1,
+        minutes: 2,
+        hours: 3,
+        day_low: 4,
+        day_high: 0x41,
+    };
+    rtc.latched.seconds = 9;
+    let footer = rtc.to_footer();
+
+    let loaded = RealTimeClock::load(0, &mut &footer[..]).unwrap();
+    assert_eq!(loaded.registers, rtc.registers);
+    assert_eq!(loaded.latched, rtc.latched);
+    assert_eq!(loaded.footer.timestamp, Some(1000));
+}
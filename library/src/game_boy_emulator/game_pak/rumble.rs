@@ -0,0 +1,14 @@
+// Copyright 2026 Remi Bernotavicius
+
+/// Something that can be shaken by a cartridge's rumble motor.
+pub trait Rumble: Send {
+    fn set_rumble(&mut self, on: bool);
+}
+
+/// `Rumble` that goes nowhere.
+#[derive(Default)]
+pub struct NullRumble;
+
+impl Rumble for NullRumble {
+    fn set_rumble(&mut self, _on: bool) {}
+}
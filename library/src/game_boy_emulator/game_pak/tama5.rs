@@ -0,0 +1,353 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::rtc::ClockFooter;
+use super::{BankOps, MemoryBankController, MemoryMappedBank, RomBank, SramChunk, SwitchableBank};
+use crate::game_boy_emulator::memory_controller::MemoryMappedHardware as _;
+use crate::io;
+use crate::storage::PersistentStorage;
+use alloc::vec::Vec;
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+/// Size of the EEPROM the game keeps its save data in.
+pub(super) const TAMA5_RAM_SIZE: u16 = 32;
+
+/// Size of the clock data appended after the EEPROM in the save file: the clock's time as seconds
+/// since the start of 2000 and the unix timestamp it was last updated at, both 64-bit
+/// little-endian.
+pub(super) const TAMA5_FOOTER_LEN: u64 = 16;
+
+const TAMA5_TIMESTAMP_AT: usize = 8;
+
+const REGISTER_ROM_BANK_LOW: u8 = 0x0;
+const REGISTER_ROM_BANK_HIGH: u8 = 0x1;
+const REGISTER_DATA_LOW: u8 = 0x4;
+const REGISTER_DATA_HIGH: u8 = 0x5;
+const REGISTER_COMMAND: u8 = 0x6;
+const REGISTER_ADDRESS: u8 = 0x7;
+const REGISTER_STATUS: u8 = 0xA;
+const REGISTER_RESULT_LOW: u8 = 0xC;
+const REGISTER_RESULT_HIGH: u8 = 0xD;
+
+const COMMAND_WRITE_RAM: u8 = 0x0;
+const COMMAND_READ_RAM: u8 = 0x1;
+const COMMAND_WRITE_CLOCK: u8 = 0x2;
+const COMMAND_READ_CLOCK: u8 = 0x3;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The clock has a register for each digit of the date and time, plus the day of the week.
+const CLOCK_REGISTERS: usize = 13;
+const REGISTER_WEEKDAY: usize = 0x6;
+
+/// Days from 1970-01-01 to 2000-01-01, the earliest date the clock can hold.
+const EPOCH_DAYS: i64 = 10957;
+
+/// The date and time broken up in to the fields the clock's registers show.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct DateTime {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+}
+
+impl DateTime {
+    fn from_seconds(seconds: u64) -> Self {
+        let days = (seconds / SECONDS_PER_DAY) as i64 + EPOCH_DAYS;
+        let (year, month, day) = civil_from_days(days);
+        let time = seconds % SECONDS_PER_DAY;
+        Self {
+            year,
+            month,
+            day,
+            hour: (time / 3600) as i64,
+            minute: (time / 60 % 60) as i64,
+            second: (time % 60) as i64,
+        }
+    }
+
+    fn to_seconds(self) -> u64 {
+        let days = days_from_civil(self.year, self.month, self.day) - EPOCH_DAYS;
+        let time = self.hour * 3600 + self.minute * 60 + self.second;
+        (days * SECONDS_PER_DAY as i64 + time).max(0) as u64
+    }
+
+    /// Read the date and time from the clock's digit registers. Months out of range are pinned to
+    /// the nearest month, and days past the end of the month carry over into the next one.
+    fn from_digits(digits: &[u8; CLOCK_REGISTERS]) -> Self {
+        let number = |ones: usize| (digits[ones + 1] * 10 + digits[ones]) as i64;
+        Self {
+            year: 2000 + number(0xB),
+            month: number(0x9).clamp(1, 12),
+            day: number(0x7).max(1),
+            hour: number(0x4),
+            minute: number(0x2),
+            second: number(0x0),
+        }
+    }
+
+    fn to_digits(self, weekday: u8) -> [u8; CLOCK_REGISTERS] {
+        let year = self.year.rem_euclid(100);
+        [
+            self.second % 10,
+            self.second / 10,
+            self.minute % 10,
+            self.minute / 10,
+            self.hour % 10,
+            self.hour / 10,
+            weekday as i64,
+            self.day % 10,
+            self.day / 10,
+            self.month % 10,
+            self.month / 10,
+            year % 10,
+            year / 10,
+        ]
+        .map(|d| d as u8)
+    }
+
+    fn weekday(self) -> i64 {
+        // 2000-01-01 was a Saturday
+        (days_from_civil(self.year, self.month, self.day) - EPOCH_DAYS + 6).rem_euclid(7)
+    }
+}
+
+/// Convert days since 1970-01-01 to a year, month and day in the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Convert a year, month and day in the proleptic Gregorian calendar to days since 1970-01-01.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = year - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The TC8521 real-time clock. Its registers each hold one BCD digit of the date and time. They
+/// keep the digits the game writes as they are, and are only worked out as a date again when the
+/// clock counts, so setting a date one digit at a time can pass through dates which don't exist.
+#[derive(Serialize, Deserialize)]
+pub(super) struct Tama5Clock {
+    pub(super) footer: ClockFooter,
+    digits: [u8; CLOCK_REGISTERS],
+}
+
+impl Tama5Clock {
+    pub(super) fn new(offset: u64) -> Self {
+        Self::from_seconds(offset, 0)
+    }
+
+    fn from_seconds(offset: u64, seconds: u64) -> Self {
+        let time = DateTime::from_seconds(seconds);
+        Self {
+            footer: ClockFooter::new(offset, TAMA5_TIMESTAMP_AT),
+            digits: time.to_digits(time.weekday() as u8),
+        }
+    }
+
+    /// Load the clock from the footer in the save file. A footer that was never written (all
+    /// zeros) gives a fresh clock.
+    pub(super) fn load(offset: u64, file: &mut impl io::Read) -> io::Result<Self> {
+        let (footer, data) =
+            ClockFooter::load::<{ TAMA5_FOOTER_LEN as usize }>(offset, TAMA5_TIMESTAMP_AT, file)?;
+
+        let seconds = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let mut clock = Self::from_seconds(offset, seconds);
+        clock.footer = footer;
+        Ok(clock)
+    }
+
+    fn seconds(&self) -> u64 {
+        DateTime::from_digits(&self.digits).to_seconds()
+    }
+
+    fn to_footer(&self) -> [u8; TAMA5_FOOTER_LEN as usize] {
+        let mut data = [0; TAMA5_FOOTER_LEN as usize];
+        data[0..8].copy_from_slice(&self.seconds().to_le_bytes());
+        self.footer.write_timestamp(&mut data);
+        data
+    }
+
+    /// Advance the clock to the given time. Time going backwards is ignored. The day of the week
+    /// counts along with the date, from whatever it was set to.
+    fn catch_up(&mut self, now: u64) {
+        let timestamp = self.footer.timestamp.unwrap_or(now);
+        let elapsed = now.saturating_sub(timestamp);
+        if elapsed > 0 {
+            let seconds = self.seconds();
+            let new_seconds = seconds + elapsed;
+            let days = new_seconds / SECONDS_PER_DAY - seconds / SECONDS_PER_DAY;
+            let weekday = (self.digits[REGISTER_WEEKDAY] as u64 + days) % 7;
+            self.digits = DateTime::from_seconds(new_seconds).to_digits(weekday as u8);
+        }
+        self.footer.timestamp = Some(now);
+    }
+
+    fn read(&mut self, ops: &BankOps<impl PersistentStorage>, register: u8) -> u8 {
+        self.catch_up(ops.clock.now());
+        self.digits.get(register as usize).copied().unwrap_or(0)
+    }
+
+    /// Set one digit of the date or time.
+    fn write(&mut self, ops: &mut BankOps<impl PersistentStorage>, register: u8, digit: u8) {
+        self.catch_up(ops.clock.now());
+        if let Some(d) = self.digits.get_mut(register as usize) {
+            *d = digit;
+            self.footer.save(ops, &self.to_footer());
+        }
+    }
+}
+
+/// The Bandai TAMA5 mapper. Everything goes through two registers at 0xA000 and 0xA001: the game
+/// selects one of the mapper's registers by writing its number to 0xA001, and then reads or writes
+/// it a nibble at a time through 0xA000. The save data lives in a small EEPROM which is read and
+/// written a byte at a time through the data and result registers.
+#[derive(Serialize, Deserialize)]
+pub(super) struct MemoryBankControllerTama5 {
+    register: u8,
+    rom_bank: u8,
+    data: u8,
+    command: u8,
+    result: u8,
+    switchable_bank: SwitchableBank<RomBank>,
+    ram: SramChunk,
+    pub(super) clock: Tama5Clock,
+}
+
+impl From<MemoryBankControllerTama5> for MemoryBankController {
+    fn from(c: MemoryBankControllerTama5) -> Self {
+        Self::Tama5(c)
+    }
+}
+
+impl fmt::Debug for MemoryBankControllerTama5 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TAMA5")
+    }
+}
+
+impl MemoryBankControllerTama5 {
+    pub(super) fn new(banks: Vec<RomBank>, ram: SramChunk, clock: Tama5Clock) -> Self {
+        Self {
+            register: 0,
+            rom_bank: 0,
+            data: 0,
+            command: 0,
+            result: 0,
+            switchable_bank: SwitchableBank::new(banks, 1),
+            ram,
+            clock,
+        }
+    }
+
+    /// Run the command set up in the command register, on the address made up of its lowest bit
+    /// and the given nibble.
+    fn run_command(&mut self, ops: &mut BankOps<impl PersistentStorage>, address_low: u8) {
+        let address = ((self.command & 0x01) << 4) | address_low;
+        match self.command >> 1 {
+            COMMAND_WRITE_RAM => self.ram.set_bank_value(ops, address as u16, self.data),
+            COMMAND_READ_RAM => self.result = self.ram.memory.read_value(address as u16),
+            COMMAND_WRITE_CLOCK => self.clock.write(ops, address_low, self.data & 0x0F),
+            COMMAND_READ_CLOCK => self.result = self.clock.read(ops, address_low),
+            _ => {}
+        }
+    }
+
+    fn write_register(&mut self, ops: &mut BankOps<impl PersistentStorage>, value: u8) {
+        let value = value & 0x0F;
+        match self.register {
+            REGISTER_ROM_BANK_LOW => self.rom_bank = (self.rom_bank & 0x10) | value,
+            REGISTER_ROM_BANK_HIGH => self.rom_bank = (self.rom_bank & 0x0F) | (value & 0x01) << 4,
+            REGISTER_DATA_LOW => self.data = (self.data & 0xF0) | value,
+            REGISTER_DATA_HIGH => self.data = (self.data & 0x0F) | (value << 4),
+            REGISTER_COMMAND => self.command = value,
+            REGISTER_ADDRESS => self.run_command(ops, value),
+            _ => {}
+        }
+        if self.register <= REGISTER_ROM_BANK_HIGH {
+            self.switchable_bank
+                .switch_bank_wrapping(self.rom_bank as usize);
+        }
+    }
+}
+
+impl MemoryMappedBank for MemoryBankControllerTama5 {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_bank_value(&self, ops: &BankOps<impl PersistentStorage>, address: u16) -> u8 {
+        if address < 0x4000 {
+            self.switchable_bank.banks[0].read_bank_value(ops, address)
+        } else if address < 0x8000 {
+            self.switchable_bank.read_bank_value(ops, address - 0x4000)
+        } else if address & 0x1FFF == 0 {
+            match self.register {
+                // Commands finish instantly, so it is always ready
+                REGISTER_STATUS => 0xF1,
+                REGISTER_RESULT_LOW => 0xF0 | (self.result & 0x0F),
+                REGISTER_RESULT_HIGH => 0xF0 | (self.result >> 4),
+                _ => 0xFF,
+            }
+        } else {
+            0xFF
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_bank_value(
+        &mut self,
+        ops: &mut BankOps<impl PersistentStorage>,
+        address: u16,
+        value: u8,
+    ) {
+        if address < 0xA000 {
+            // nothing
+        } else if address & 0x1FFF == 0 {
+            self.write_register(ops, value);
+        } else if address & 0x1FFF == 1 {
+            self.register = value & 0x0F;
+        }
+    }
+}
+
+#[test]
+fn tama5_civil_date_round_trip() {
+    let time = DateTime {
+        year: 2024,
+        month: 2,
+        day: 29,
+        hour: 23,
+        minute: 59,
+        second: 58,
+    };
+    assert_eq!(DateTime::from_seconds(time.to_seconds()), time);
+    // 2024-02-29 was a Thursday
+    assert_eq!(time.weekday(), 4);
+}
+
+#[test]
+fn tama5_footer_round_trip() {
+    let mut clock = Tama5Clock::from_seconds(0, 123456789);
+    clock.footer.timestamp = Some(1000);
+    let footer = clock.to_footer();
+
+    let loaded = Tama5Clock::load(0, &mut &footer[..]).unwrap();
+    assert_eq!(loaded.seconds(), 123456789);
+    assert_eq!(loaded.digits, clock.digits);
+    assert_eq!(loaded.footer.timestamp, Some(1000));
+}
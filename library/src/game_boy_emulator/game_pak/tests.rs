@@ -0,0 +1,1424 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{
+    CartridgeHeader, CgbSupport, Destination, GamePak, GamePakError, ImageSource,
+    LoopbackInfraredPeer, Mbc1Wiring, Rumble, SharedTilt, ToneGenerator, WallClock, BANK_SIZE,
+    CAMERA_SENSOR_WIDTH, LOGO, MBC_TYPE_ADDRESS, NINTENDO_LOGO, RAM_SIZE_ADDRESS, ROM_SIZE_ADDRESS,
+};
+use crate::game_boy_emulator::MemoryMappedHardware as _;
+use crate::storage::fs::Fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Build a ROM with the given cartridge type and size bytes and nothing else in it.
+fn synthetic_rom(mbc_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let number_of_banks = 2usize << rom_size;
+    let mut rom = vec![0; number_of_banks * BANK_SIZE as usize];
+    rom[MBC_TYPE_ADDRESS] = mbc_type;
+    rom[ROM_SIZE_ADDRESS] = rom_size;
+    rom[RAM_SIZE_ADDRESS] = ram_size;
+    rom
+}
+
+/// A save file in a temporary directory, for checking what a cartridge keeps in it.
+struct TestSave {
+    dir: tempfile::TempDir,
+    fs: Fs,
+}
+
+impl TestSave {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let fs = Fs::new(Some(dir.path()));
+        Self { dir, fs }
+    }
+
+    fn path(&self) -> std::path::PathBuf {
+        self.dir.path().join("game.gb.sav")
+    }
+
+    /// Load the ROM with this as its save file.
+    fn load(&mut self, rom: &[u8]) -> GamePak<Fs> {
+        let sav = self.path();
+        GamePak::new(rom, &mut self.fs, Some(sav.to_str().unwrap())).unwrap()
+    }
+
+    fn contents(&self) -> Vec<u8> {
+        std::fs::read(self.path()).unwrap()
+    }
+}
+
+#[derive(Clone, Default)]
+struct TestClock(Arc<AtomicU64>);
+
+impl TestClock {
+    fn advance(&self, seconds: u64) {
+        self.0.fetch_add(seconds, Ordering::SeqCst);
+    }
+}
+
+impl WallClock for TestClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+fn latch_rtc(game_pak: &mut GamePak<Fs>) {
+    game_pak.set_value(0x6000, 0x00);
+    game_pak.set_value(0x6000, 0x01);
+}
+
+fn read_rtc(game_pak: &mut GamePak<Fs>, register: u8) -> u8 {
+    game_pak.set_value(0x4000, register);
+    game_pak.read_value(0xA000)
+}
+
+fn write_rtc(game_pak: &mut GamePak<Fs>, register: u8, value: u8) {
+    game_pak.set_value(0x4000, register);
+    game_pak.set_value(0xA000, value);
+}
+
+#[test]
+fn mbc3_rtc_counts_after_latch() {
+    let rom = synthetic_rom(0x10, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+    game_pak.set_wall_clock(clock.clone());
+
+    // Enable RAM and RTC
+    game_pak.set_value(0x0000, 0x0A);
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 0);
+
+    // Time passing doesn't change what is read until the next latch.
+    clock.advance(2 * 24 * 60 * 60 + 3 * 60 * 60 + 4 * 60 + 5);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 0);
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 5);
+    assert_eq!(read_rtc(&mut game_pak, 0x09), 4);
+    assert_eq!(read_rtc(&mut game_pak, 0x0A), 3);
+    assert_eq!(read_rtc(&mut game_pak, 0x0B), 2);
+    assert_eq!(read_rtc(&mut game_pak, 0x0C), 0);
+
+    // RAM banks are still accessible
+    game_pak.set_value(0x4000, 0x01);
+    game_pak.set_value(0xA000, 0x42);
+    assert_eq!(game_pak.read_value(0xA000), 0x42);
+}
+
+#[test]
+fn mbc3_rtc_counts_from_load() {
+    let rom = synthetic_rom(0x10, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+    game_pak.set_wall_clock(clock.clone());
+    game_pak.start_clock();
+
+    // Time passing before the game first looks at the clock still counts.
+    clock.advance(5);
+    game_pak.set_value(0x0000, 0x0A);
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 5);
+}
+
+#[test]
+fn mbc3_rtc_halt() {
+    let rom = synthetic_rom(0x0F, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    game_pak.set_wall_clock(clock.clone());
+    game_pak.set_value(0x0000, 0x0A);
+
+    write_rtc(&mut game_pak, 0x0C, 0x40);
+    write_rtc(&mut game_pak, 0x08, 30);
+    clock.advance(100);
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 30);
+
+    write_rtc(&mut game_pak, 0x0C, 0x00);
+    clock.advance(100);
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 10);
+    assert_eq!(read_rtc(&mut game_pak, 0x09), 2);
+}
+
+#[test]
+fn mbc3_rtc_disabled_reads_ff() {
+    let rom = synthetic_rom(0x0F, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_wall_clock(TestClock::default());
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x08), 0xFF);
+}
+
+#[test]
+fn mbc3_rtc_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0x10, 0, 2);
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+
+    {
+        let mut game_pak = save.load(&rom);
+        game_pak.set_wall_clock(clock.clone());
+        game_pak.set_value(0x0000, 0x0A);
+        write_rtc(&mut game_pak, 0x09, 7);
+        latch_rtc(&mut game_pak);
+    }
+
+    // 8kB of RAM followed by the 48 byte RTC footer
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x2000 + 48);
+    assert_eq!(&contents[0x2000 + 4..0x2000 + 8], &7u32.to_le_bytes());
+    assert_eq!(&contents[0x2000 + 24..0x2000 + 28], &7u32.to_le_bytes());
+    assert_eq!(&contents[0x2000 + 40..], &1_000_000u64.to_le_bytes());
+
+    clock.advance(60);
+
+    let mut game_pak = save.load(&rom);
+    game_pak.set_wall_clock(clock.clone());
+    game_pak.set_value(0x0000, 0x0A);
+
+    // The latched registers come back as they were saved.
+    assert_eq!(read_rtc(&mut game_pak, 0x09), 7);
+
+    latch_rtc(&mut game_pak);
+    assert_eq!(read_rtc(&mut game_pak, 0x09), 8);
+}
+
+/// Build a ROM where every bank starts with its own bank number.
+fn numbered_rom(mbc_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = synthetic_rom(mbc_type, rom_size, ram_size);
+    for (i, bank) in rom.chunks_mut(BANK_SIZE as usize).enumerate() {
+        bank[0] = i as u8;
+        bank[1] = (i >> 8) as u8;
+    }
+    rom
+}
+
+fn read_switchable_bank_number(game_pak: &GamePak<Fs>) -> usize {
+    game_pak.read_value(0x4000) as usize | (game_pak.read_value(0x4001) as usize) << 8
+}
+
+#[test]
+fn mbc5_nine_bit_rom_bank() {
+    // 8MB, 512 banks
+    let rom = numbered_rom(0x19, 0x08, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(read_switchable_bank_number(&game_pak), 1);
+
+    game_pak.set_value(0x2000, 0x23);
+    game_pak.set_value(0x3000, 0x01);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x123);
+
+    // Bank 0 can be mapped to the switchable region
+    game_pak.set_value(0x2000, 0x00);
+    game_pak.set_value(0x3000, 0x00);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0);
+}
+
+#[test]
+fn mbc5_sixteen_ram_banks() {
+    let rom = synthetic_rom(0x1A, 0, 4);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_value(0x0000, 0x0A);
+
+    for bank in 0..16 {
+        game_pak.set_value(0x4000, bank);
+        game_pak.set_value(0xA000, bank + 0x10);
+    }
+    for bank in 0..16 {
+        game_pak.set_value(0x4000, bank);
+        assert_eq!(game_pak.read_value(0xA000), bank + 0x10);
+    }
+}
+
+#[test]
+fn mbc5_battery_ram_banks_persisted() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0x1B, 0, 3);
+    let mut game_pak = save.load(&rom);
+    game_pak.set_value(0x0000, 0x0A);
+    game_pak.set_value(0x4000, 0x02);
+    game_pak.set_value(0xA001, 0x55);
+    drop(game_pak);
+
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x8000);
+    assert_eq!(contents[0x4001], 0x55);
+}
+
+#[derive(Clone, Default)]
+struct TestRumble(Arc<std::sync::Mutex<Vec<bool>>>);
+
+impl Rumble for TestRumble {
+    fn set_rumble(&mut self, on: bool) {
+        self.0.lock().unwrap().push(on);
+    }
+}
+
+#[test]
+fn mbc5_rumble() {
+    let rom = synthetic_rom(0x1D, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let rumble = TestRumble::default();
+    game_pak.set_rumble(rumble.clone());
+    game_pak.set_value(0x0000, 0x0A);
+
+    // The motor bit doesn't select a RAM bank
+    game_pak.set_value(0x4000, 0x09);
+    game_pak.set_value(0xA000, 0x42);
+    game_pak.set_value(0x4000, 0x01);
+    assert_eq!(game_pak.read_value(0xA000), 0x42);
+
+    game_pak.set_value(0x4000, 0x08);
+    game_pak.set_value(0x4000, 0x00);
+
+    assert_eq!(*rumble.0.lock().unwrap(), vec![true, false, true, false]);
+}
+
+fn read_lower_bank_number(game_pak: &GamePak<Fs>) -> usize {
+    game_pak.read_value(0x0000) as usize | (game_pak.read_value(0x0001) as usize) << 8
+}
+
+#[test]
+fn mbc1_large_rom_banking() {
+    // 2MB, 128 banks
+    let rom = numbered_rom(0x01, 0x06, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+
+    game_pak.set_value(0x2000, 0x00);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x01);
+
+    game_pak.set_value(0x2000, 0x12);
+    game_pak.set_value(0x4000, 0x03);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x72);
+    assert_eq!(read_lower_bank_number(&game_pak), 0x00);
+
+    // Bank 0x20 can't be selected
+    game_pak.set_value(0x2000, 0x00);
+    game_pak.set_value(0x4000, 0x01);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x21);
+
+    // In mode 1 the upper bits apply to the first bank too
+    game_pak.set_value(0x6000, 0x01);
+    assert_eq!(read_lower_bank_number(&game_pak), 0x20);
+}
+
+#[test]
+fn mbc1_ram_banking() {
+    // 32kB of RAM, 4 banks
+    let rom = synthetic_rom(0x03, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_value(0x0000, 0x0A);
+
+    // The RAM bank is only used in mode 1
+    game_pak.set_value(0x4000, 0x02);
+    game_pak.set_value(0xA000, 0x11);
+    game_pak.set_value(0x6000, 0x01);
+    game_pak.set_value(0xA000, 0x42);
+
+    // Going back to mode 0 maps RAM bank 0 again
+    game_pak.set_value(0x6000, 0x00);
+    assert_eq!(game_pak.read_value(0xA000), 0x11);
+
+    // Only the lowest bit of the mode is used
+    game_pak.set_value(0x6000, 0x02);
+    assert_eq!(game_pak.read_value(0xA000), 0x11);
+    game_pak.set_value(0x6000, 0x03);
+    assert_eq!(game_pak.read_value(0xA000), 0x42);
+}
+
+#[test]
+fn mbc1_static_rom_wiring() {
+    let rom: &'static [u8] = Box::leak(multicart_rom().into_boxed_slice());
+    let mut fs = Fs::default();
+    let game_pak = GamePak::new_static(rom, &mut fs, None).unwrap();
+    assert_eq!(format!("{game_pak:?}"), "GamePak(\"\", MBC1M)");
+
+    let game_pak =
+        GamePak::new_static_with_mbc1_wiring(rom, &mut fs, None, Mbc1Wiring::Standard).unwrap();
+    assert_eq!(format!("{game_pak:?}"), "GamePak(\"\", MBC1)");
+}
+
+fn multicart_rom() -> Vec<u8> {
+    // 1MB, 64 banks with four sub-games
+    let mut rom = numbered_rom(0x01, 0x05, 0);
+    for game in rom.chunks_mut(0x40000) {
+        game[LOGO].copy_from_slice(&NINTENDO_LOGO);
+    }
+    rom
+}
+
+#[test]
+fn mbc1_multicart_detected() {
+    let rom = multicart_rom();
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(format!("{game_pak:?}"), "GamePak(\"\", MBC1M)");
+
+    // Only 4 bits of the lower bank number are used
+    game_pak.set_value(0x2000, 0x13);
+    game_pak.set_value(0x4000, 0x02);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x23);
+
+    game_pak.set_value(0x6000, 0x01);
+    assert_eq!(read_lower_bank_number(&game_pak), 0x20);
+}
+
+#[test]
+fn mbc1_multicart_forced() {
+    let rom = multicart_rom();
+    let mut fs = Fs::default();
+    let mut game_pak =
+        GamePak::new_with_mbc1_wiring(&rom, &mut fs, None, Mbc1Wiring::Standard).unwrap();
+    game_pak.set_value(0x2000, 0x13);
+    game_pak.set_value(0x4000, 0x01);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x33);
+
+    let rom = numbered_rom(0x01, 0x05, 0);
+    let mut game_pak =
+        GamePak::new_with_mbc1_wiring(&rom, &mut fs, None, Mbc1Wiring::Multicart).unwrap();
+    game_pak.set_value(0x2000, 0x13);
+    game_pak.set_value(0x4000, 0x01);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x13);
+}
+
+/// Build a 256kB MMM01 ROM. The cartridge header is in the menu in the last 32kB.
+fn mmm01_rom(mbc_type: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = numbered_rom(0x01, 0x03, 0);
+    let menu_start = rom.len() - 2 * BANK_SIZE as usize;
+    let menu = &mut rom[menu_start..];
+    menu[MBC_TYPE_ADDRESS] = mbc_type;
+    menu[ROM_SIZE_ADDRESS] = 0x03;
+    menu[RAM_SIZE_ADDRESS] = ram_size;
+    rom
+}
+
+#[test]
+fn mmm01_boots_into_menu() {
+    let rom = mmm01_rom(0x0B, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(format!("{game_pak:?}"), "GamePak(\"\", MMM01)");
+
+    assert_eq!(read_lower_bank_number(&game_pak), 14);
+    assert_eq!(read_switchable_bank_number(&game_pak), 15);
+
+    // Selecting banks does nothing until the game is mapped
+    game_pak.set_value(0x2000, 0x08);
+    assert_eq!(read_lower_bank_number(&game_pak), 14);
+    assert_eq!(read_switchable_bank_number(&game_pak), 15);
+}
+
+#[test]
+fn mmm01_maps_game() {
+    let rom = mmm01_rom(0x0B, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+
+    // The game lives in banks 8 through 15, so fix ROM bank bits 3 and 4
+    game_pak.set_value(0x2000, 0x08);
+    game_pak.set_value(0x6000, 0x30);
+    game_pak.set_value(0x0000, 0x40);
+
+    assert_eq!(read_lower_bank_number(&game_pak), 8);
+    assert_eq!(read_switchable_bank_number(&game_pak), 9);
+
+    game_pak.set_value(0x2000, 0x03);
+    assert_eq!(read_switchable_bank_number(&game_pak), 11);
+
+    // The game can't reach outside of its banks
+    game_pak.set_value(0x2000, 0x1F);
+    assert_eq!(read_switchable_bank_number(&game_pak), 15);
+    assert_eq!(read_lower_bank_number(&game_pak), 8);
+
+    // The mapping can't be undone
+    game_pak.set_value(0x0000, 0x00);
+    game_pak.set_value(0x6000, 0x00);
+    game_pak.set_value(0x2000, 0x01);
+    assert_eq!(read_switchable_bank_number(&game_pak), 9);
+}
+
+#[test]
+fn mmm01_battery_ram() {
+    let mut save = TestSave::new();
+
+    let rom = mmm01_rom(0x0D, 3);
+    let mut game_pak = save.load(&rom);
+
+    // Give the game RAM banks 2 and 3 by fixing RAM bank bit 1
+    game_pak.set_value(0x4000, 0x02);
+    game_pak.set_value(0x0000, 0x6A);
+
+    game_pak.set_value(0x6000, 0x01);
+    game_pak.set_value(0x4000, 0x01);
+    game_pak.set_value(0xA000, 0x42);
+    drop(game_pak);
+
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x8000);
+    assert_eq!(contents[0x6000], 0x42);
+}
+
+#[test]
+fn huc1_banking() {
+    let rom = numbered_rom(0xFF, 0x05, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(format!("{game_pak:?}"), "GamePak(\"\", HuC1+RAM+BATTERY)");
+
+    game_pak.set_value(0x2000, 0x3F);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x3F);
+    game_pak.set_value(0x2000, 0x00);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0);
+
+    // RAM is always enabled
+    for bank in 0..4 {
+        game_pak.set_value(0x4000, bank);
+        game_pak.set_value(0xA000, bank + 0x10);
+    }
+    for bank in 0..4 {
+        game_pak.set_value(0x4000, bank);
+        assert_eq!(game_pak.read_value(0xA000), bank + 0x10);
+    }
+}
+
+#[test]
+fn huc1_infrared_loopback() {
+    let rom = synthetic_rom(0xFF, 0, 2);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_infrared_peer(LoopbackInfraredPeer::default());
+    game_pak.set_value(0xA000, 0x42);
+
+    game_pak.set_value(0x0000, 0x0E);
+    assert_eq!(game_pak.read_value(0xA000), 0xC0);
+    game_pak.set_value(0xA000, 0x01);
+    assert_eq!(game_pak.read_value(0xA000), 0xC1);
+    game_pak.set_value(0xA000, 0x00);
+    assert_eq!(game_pak.read_value(0xA000), 0xC0);
+
+    // Switching back to RAM finds it untouched
+    game_pak.set_value(0x0000, 0x0A);
+    assert_eq!(game_pak.read_value(0xA000), 0x42);
+}
+
+fn huc3_command(game_pak: &mut GamePak<Fs>, command: u8) -> u8 {
+    game_pak.set_value(0x0000, 0x0B);
+    game_pak.set_value(0xA000, command);
+    game_pak.set_value(0x0000, 0x0C);
+    game_pak.read_value(0xA000)
+}
+
+/// Read nibbles from the HuC3 clock's memory, starting at the given address.
+fn huc3_read_memory(game_pak: &mut GamePak<Fs>, address: u8, len: usize) -> Vec<u8> {
+    huc3_command(game_pak, 0x40 | (address & 0x0F));
+    huc3_command(game_pak, 0x50 | (address >> 4));
+    (0..len).map(|_| huc3_command(game_pak, 0x10)).collect()
+}
+
+/// Write nibbles to the HuC3 clock's memory, starting at the given address.
+fn huc3_write_memory(game_pak: &mut GamePak<Fs>, address: u8, nibbles: &[u8]) {
+    huc3_command(game_pak, 0x40 | (address & 0x0F));
+    huc3_command(game_pak, 0x50 | (address >> 4));
+    for n in nibbles {
+        huc3_command(game_pak, 0x30 | n);
+    }
+}
+
+#[test]
+fn huc3_rtc_counts_minutes_and_days() {
+    let rom = synthetic_rom(0xFE, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+    game_pak.set_wall_clock(clock.clone());
+
+    assert_eq!(huc3_command(&mut game_pak, 0x62), 0x01);
+
+    huc3_command(&mut game_pak, 0x60);
+    assert_eq!(huc3_read_memory(&mut game_pak, 0x00, 7), [0; 7]);
+
+    // 3 days, 0x123 minutes and some seconds that don't count yet
+    clock.advance(3 * 24 * 60 * 60 + 0x123 * 60 + 59);
+    huc3_command(&mut game_pak, 0x60);
+    assert_eq!(
+        huc3_read_memory(&mut game_pak, 0x00, 7),
+        [0x3, 0x2, 0x1, 0x3, 0x0, 0x0, 0x0]
+    );
+
+    // Set the clock to the last minute of day 0x100
+    huc3_write_memory(&mut game_pak, 0x00, &[0xF, 0x9, 0x5, 0x0, 0x0, 0x1, 0x0]);
+    huc3_command(&mut game_pak, 0x61);
+    clock.advance(60);
+    huc3_command(&mut game_pak, 0x60);
+    assert_eq!(
+        huc3_read_memory(&mut game_pak, 0x00, 7),
+        [0x0, 0x0, 0x0, 0x1, 0x0, 0x1, 0x0]
+    );
+
+    // RAM is still there
+    game_pak.set_value(0x0000, 0x0A);
+    game_pak.set_value(0x4000, 0x03);
+    game_pak.set_value(0xA000, 0x42);
+    game_pak.set_value(0x0000, 0x00);
+    assert_eq!(game_pak.read_value(0xA000), 0x42);
+}
+
+#[test]
+fn huc3_rtc_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0xFE, 0, 3);
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+
+    {
+        let mut game_pak = save.load(&rom);
+        game_pak.set_wall_clock(clock.clone());
+        huc3_write_memory(&mut game_pak, 0x00, &[0x5, 0x0, 0x0, 0x2, 0x0, 0x0, 0x0]);
+        huc3_command(&mut game_pak, 0x61);
+        huc3_write_memory(
+            &mut game_pak,
+            0x58,
+            &[0x1, 0x2, 0x3, 0x4, 0x0, 0x0, 0x0, 0x1],
+        );
+    }
+
+    // 32kB of RAM followed by the 17 byte clock footer
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x8000 + 17);
+    assert_eq!(&contents[0x8000..0x8008], &1_000_000u64.to_le_bytes());
+    assert_eq!(&contents[0x8008..0x800A], &5u16.to_le_bytes());
+    assert_eq!(&contents[0x800A..0x800C], &2u16.to_le_bytes());
+    assert_eq!(&contents[0x800C..0x800E], &0x321u16.to_le_bytes());
+    assert_eq!(&contents[0x800E..0x8010], &4u16.to_le_bytes());
+    assert_eq!(contents[0x8010], 1);
+
+    clock.advance(10 * 60);
+
+    let mut game_pak = save.load(&rom);
+    game_pak.set_wall_clock(clock.clone());
+    huc3_command(&mut game_pak, 0x60);
+    assert_eq!(
+        huc3_read_memory(&mut game_pak, 0x00, 7),
+        [0xF, 0x0, 0x0, 0x2, 0x0, 0x0, 0x0]
+    );
+    assert_eq!(
+        huc3_read_memory(&mut game_pak, 0x58, 8),
+        [0x1, 0x2, 0x3, 0x4, 0x0, 0x0, 0x0, 0x1]
+    );
+}
+
+#[derive(Clone, Default)]
+struct TestToneGenerator(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl ToneGenerator for TestToneGenerator {
+    fn play_tone(&mut self, tone: u8) {
+        self.0.lock().unwrap().push(tone);
+    }
+}
+
+#[test]
+fn huc3_tone() {
+    let rom = synthetic_rom(0xFE, 0, 3);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let tone = TestToneGenerator::default();
+    game_pak.set_tone_generator(tone.clone());
+
+    huc3_write_memory(&mut game_pak, 0x27, &[0x3]);
+    huc3_command(&mut game_pak, 0x6E);
+
+    assert_eq!(*tone.0.lock().unwrap(), vec![0x3]);
+}
+
+fn enable_mbc7_registers(game_pak: &mut GamePak<Fs>) {
+    game_pak.set_value(0x0000, 0x0A);
+    game_pak.set_value(0x4000, 0x40);
+}
+
+fn read_accelerometer(game_pak: &mut GamePak<Fs>) -> (u16, u16) {
+    game_pak.set_value(0xA000, 0x55);
+    game_pak.set_value(0xA010, 0xAA);
+    let read = |address| game_pak.read_value(address) as u16;
+    (
+        read(0xA020) | read(0xA030) << 8,
+        read(0xA040) | read(0xA050) << 8,
+    )
+}
+
+#[test]
+fn mbc7_accelerometer() {
+    let rom = synthetic_rom(0x22, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let tilt = SharedTilt::default();
+    game_pak.set_tilt_sensor(tilt.clone());
+
+    // Nothing is there until both enables are written
+    assert_eq!(game_pak.read_value(0xA020), 0xFF);
+    game_pak.set_value(0x0000, 0x0A);
+    assert_eq!(game_pak.read_value(0xA020), 0xFF);
+    game_pak.set_value(0x4000, 0x40);
+
+    assert_eq!(read_accelerometer(&mut game_pak), (0x81D0, 0x81D0));
+
+    tilt.set_tilt(1.0, -0.5);
+    assert_eq!(read_accelerometer(&mut game_pak), (0x8240, 0x8198));
+
+    // Without erasing first the latch does nothing
+    tilt.set_tilt(0.0, 0.0);
+    game_pak.set_value(0xA010, 0xAA);
+    assert_eq!(game_pak.read_value(0xA020), 0x40);
+
+    // Erasing resets the reading
+    game_pak.set_value(0xA000, 0x55);
+    assert_eq!(game_pak.read_value(0xA020), 0x00);
+    assert_eq!(game_pak.read_value(0xA030), 0x80);
+}
+
+const EEPROM_CS: u8 = 0x80;
+const EEPROM_CLK: u8 = 0x40;
+
+fn eeprom_clock_bit(game_pak: &mut GamePak<Fs>, bit: bool) -> bool {
+    let di = (bit as u8) << 1;
+    game_pak.set_value(0xA080, EEPROM_CS | di);
+    game_pak.set_value(0xA080, EEPROM_CS | EEPROM_CLK | di);
+    game_pak.read_value(0xA080) & 0x01 != 0
+}
+
+/// Select the EEPROM and send it the start bit followed by the given command bits.
+fn eeprom_command(game_pak: &mut GamePak<Fs>, command: u16) {
+    game_pak.set_value(0xA080, 0x00);
+    eeprom_clock_bit(game_pak, true);
+    for i in (0..10).rev() {
+        eeprom_clock_bit(game_pak, command & (1 << i) != 0);
+    }
+}
+
+fn eeprom_read(game_pak: &mut GamePak<Fs>, address: u8) -> u16 {
+    eeprom_command(game_pak, 0b10 << 8 | address as u16);
+    assert_eq!(game_pak.read_value(0xA080) & 0x01, 0);
+    (0..16).fold(0, |word, _| {
+        word << 1 | eeprom_clock_bit(game_pak, false) as u16
+    })
+}
+
+fn eeprom_write(game_pak: &mut GamePak<Fs>, address: u8, word: u16) {
+    eeprom_command(game_pak, 0b01 << 8 | address as u16);
+    for i in (0..16).rev() {
+        eeprom_clock_bit(game_pak, word & (1 << i) != 0);
+    }
+    game_pak.set_value(0xA080, 0x00);
+}
+
+#[test]
+fn mbc7_eeprom() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0x22, 0, 0);
+    let mut game_pak = save.load(&rom);
+    enable_mbc7_registers(&mut game_pak);
+
+    // Writes are ignored until enabled
+    eeprom_write(&mut game_pak, 0x05, 0x1234);
+    assert_eq!(eeprom_read(&mut game_pak, 0x05), 0x0000);
+
+    // EWEN
+    eeprom_command(&mut game_pak, 0b00_1100_0000);
+    eeprom_write(&mut game_pak, 0x05, 0x1234);
+    eeprom_write(&mut game_pak, 0x7F, 0xBEEF);
+    assert_eq!(eeprom_read(&mut game_pak, 0x05), 0x1234);
+    assert_eq!(eeprom_read(&mut game_pak, 0x7F), 0xBEEF);
+
+    // ERASE
+    eeprom_command(&mut game_pak, 0b11 << 8 | 0x7F);
+    assert_eq!(eeprom_read(&mut game_pak, 0x7F), 0xFFFF);
+    drop(game_pak);
+
+    let contents = save.contents();
+    assert_eq!(contents.len(), 256);
+    assert_eq!(&contents[0x0A..0x0C], &[0x34, 0x12]);
+
+    let mut game_pak = save.load(&rom);
+    enable_mbc7_registers(&mut game_pak);
+    assert_eq!(eeprom_read(&mut game_pak, 0x05), 0x1234);
+}
+
+#[test]
+fn camera_ram_and_registers() {
+    let rom = numbered_rom(0xFC, 0x05, 4);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(
+        format!("{game_pak:?}"),
+        "GamePak(\"\", POCKET CAMERA+RAM+BATTERY)"
+    );
+
+    game_pak.set_value(0x2000, 0x3F);
+    assert_eq!(read_switchable_bank_number(&game_pak), 0x3F);
+
+    // Writing RAM needs it to be enabled, but reading doesn't
+    game_pak.set_value(0xA000, 0x42);
+    assert_eq!(game_pak.read_value(0xA000), 0x00);
+    game_pak.set_value(0x0000, 0x0A);
+    for bank in 0..16 {
+        game_pak.set_value(0x4000, bank);
+        game_pak.set_value(0xA000, bank + 0x20);
+    }
+    game_pak.set_value(0x0000, 0x00);
+    for bank in 0..16 {
+        game_pak.set_value(0x4000, bank);
+        assert_eq!(game_pak.read_value(0xA000), bank + 0x20);
+    }
+
+    // Only the first register can be read, and it is mirrored
+    game_pak.set_value(0x4000, 0x10);
+    game_pak.set_value(0xA080, 0x06);
+    game_pak.set_value(0xA001, 0xFF);
+    assert_eq!(game_pak.read_value(0xA000), 0x06);
+    assert_eq!(game_pak.read_value(0xA180), 0x06);
+    assert_eq!(game_pak.read_value(0xA001), 0x00);
+}
+
+struct TestImageSource(fn(usize, usize) -> u8);
+
+impl ImageSource for TestImageSource {
+    fn capture(&mut self, pixels: &mut [u8]) {
+        for (i, p) in pixels.iter_mut().enumerate() {
+            *p = self.0(i % CAMERA_SENSOR_WIDTH, i / CAMERA_SENSOR_WIDTH);
+        }
+    }
+}
+
+/// Set up the camera registers with an exposure that leaves the brightness as is, and the same
+/// dithering thresholds everywhere, then take a picture.
+fn camera_capture(game_pak: &mut GamePak<Fs>, edge_and_gain: u8, edge_ratio_and_invert: u8) {
+    game_pak.set_value(0x0000, 0x0A);
+    game_pak.set_value(0x4000, 0x10);
+    game_pak.set_value(0xA001, edge_and_gain);
+    game_pak.set_value(0xA002, 0x10);
+    game_pak.set_value(0xA003, 0x00);
+    game_pak.set_value(0xA004, edge_ratio_and_invert);
+    for entry in 0..16 {
+        game_pak.set_value(0xA006 + entry * 3, 0x20);
+        game_pak.set_value(0xA007 + entry * 3, 0x60);
+        game_pak.set_value(0xA008 + entry * 3, 0xA0);
+    }
+    game_pak.set_value(0xA000, 0x01);
+    assert_eq!(game_pak.read_value(0xA000), 0x00);
+    game_pak.set_value(0x4000, 0x00);
+}
+
+/// Read the shade of a pixel of the picture in RAM.
+fn camera_shade(game_pak: &GamePak<Fs>, x: usize, y: usize) -> u8 {
+    let tile = (y / 8) * (CAMERA_SENSOR_WIDTH / 8) + x / 8;
+    let address = 0xA100 + (tile * 16 + (y % 8) * 2) as u16;
+    let bit = 7 - x % 8;
+    let low = (game_pak.read_value(address) >> bit) & 1;
+    let high = (game_pak.read_value(address + 1) >> bit) & 1;
+    (high << 1) | low
+}
+
+#[test]
+fn camera_capture_dithering() {
+    let rom = synthetic_rom(0xFC, 0, 4);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_image_source(TestImageSource(|x, _| (x * 2) as u8));
+
+    camera_capture(&mut game_pak, 0x00, 0x00);
+    assert_eq!(camera_shade(&game_pak, 0, 0), 3);
+    assert_eq!(camera_shade(&game_pak, 0x20, 5), 2);
+    assert_eq!(camera_shade(&game_pak, 0x40, 10), 1);
+    assert_eq!(camera_shade(&game_pak, 0x7F, 111), 0);
+
+    // The first and last rows of 8 pixels
+    assert_eq!(game_pak.read_value(0xA100), 0xFF);
+    assert_eq!(game_pak.read_value(0xA101), 0xFF);
+    assert_eq!(game_pak.read_value(0xAEFE), 0x00);
+    assert_eq!(game_pak.read_value(0xAEFF), 0x00);
+
+    // Inverted
+    camera_capture(&mut game_pak, 0x00, 0x08);
+    assert_eq!(camera_shade(&game_pak, 0, 0), 0);
+    assert_eq!(camera_shade(&game_pak, 0x7F, 111), 3);
+
+    // More gain makes it brighter
+    camera_capture(&mut game_pak, 0x10, 0x00);
+    assert_eq!(camera_shade(&game_pak, 0x20, 5), 1);
+}
+
+#[test]
+fn camera_capture_edge_enhancement() {
+    let rom = synthetic_rom(0xFC, 0, 4);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    game_pak.set_image_source(TestImageSource(|x, _| if x < 64 { 0x40 } else { 0x90 }));
+
+    camera_capture(&mut game_pak, 0x00, 0x20);
+    let row: Vec<_> = (62..66).map(|x| camera_shade(&game_pak, x, 50)).collect();
+    assert_eq!(row, [2, 2, 1, 1]);
+
+    // Horizontal edge enhancement at 100% makes the edge stand out
+    camera_capture(&mut game_pak, 0xA0, 0x20);
+    let row: Vec<_> = (62..66).map(|x| camera_shade(&game_pak, x, 50)).collect();
+    assert_eq!(row, [2, 3, 0, 1]);
+
+    // Vertical edge enhancement doesn't see it
+    camera_capture(&mut game_pak, 0xC0, 0x20);
+    let row: Vec<_> = (62..66).map(|x| camera_shade(&game_pak, x, 50)).collect();
+    assert_eq!(row, [2, 2, 1, 1]);
+}
+
+#[test]
+fn camera_capture_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0xFC, 0, 4);
+    let mut game_pak = save.load(&rom);
+    camera_capture(&mut game_pak, 0x00, 0x00);
+    drop(game_pak);
+
+    // The default test pattern starts with a black bar
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x20000);
+    assert_eq!(&contents[0x100..0x102], &[0xFF, 0xFF]);
+}
+
+#[cfg(feature = "bitmap-renderer")]
+#[test]
+fn camera_bmp_image_source() {
+    let mut image = bmp::Image::new(2, 1);
+    image.set_pixel(0, 0, bmp::Pixel::new(0xFF, 0xFF, 0xFF));
+    image.set_pixel(1, 0, bmp::Pixel::new(0, 0, 0));
+    let mut bmp_file = vec![];
+    image.to_writer(&mut bmp_file).unwrap();
+
+    let rom = synthetic_rom(0xFC, 0, 4);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let source = super::BmpImageSource::from_reader(&mut &bmp_file[..]).unwrap();
+    game_pak.set_image_source(source);
+
+    camera_capture(&mut game_pak, 0x00, 0x00);
+    assert_eq!(camera_shade(&game_pak, 63, 0), 0);
+    assert_eq!(camera_shade(&game_pak, 64, 111), 3);
+}
+
+/// Map flash banks 2 and 1 in to the two windows so that the flash command addresses 0x5555 and
+/// 0x2AAA are at 0x5555 and 0x6AAA, then send the unlock sequence followed by the command.
+fn mbc6_flash_command(game_pak: &mut GamePak<Fs>, command: u8) {
+    game_pak.set_value(0x2000, 2);
+    game_pak.set_value(0x2800, 0x08);
+    game_pak.set_value(0x3000, 1);
+    game_pak.set_value(0x3800, 0x08);
+    game_pak.set_value(0x5555, 0xAA);
+    game_pak.set_value(0x6AAA, 0x55);
+    game_pak.set_value(0x5555, command);
+}
+
+fn mbc6_enable_flash(game_pak: &mut GamePak<Fs>) {
+    game_pak.set_value(0x0C00, 0x01);
+    game_pak.set_value(0x1000, 0x01);
+}
+
+fn mbc6_program(game_pak: &mut GamePak<Fs>, bank: u8, address: u16, value: u8) {
+    mbc6_flash_command(game_pak, 0xA0);
+    game_pak.set_value(0x2000, bank);
+    game_pak.set_value(0x4000 + address, value);
+}
+
+#[test]
+fn mbc6_rom_and_ram_windows() {
+    let mut rom = synthetic_rom(0x20, 2, 0);
+    for bank in 0..16 {
+        rom[bank * 0x2000 + 0x100] = bank as u8;
+    }
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+
+    game_pak.set_value(0x2000, 3);
+    game_pak.set_value(0x3000, 12);
+    assert_eq!(game_pak.read_value(0x4100), 3);
+    assert_eq!(game_pak.read_value(0x6100), 12);
+
+    // RAM is disabled
+    assert_eq!(game_pak.read_value(0xA000), 0xFF);
+
+    game_pak.set_value(0x0000, 0x0A);
+    game_pak.set_value(0x0400, 3);
+    game_pak.set_value(0x0800, 5);
+    game_pak.set_value(0xA000, 0x11);
+    game_pak.set_value(0xB000, 0x22);
+    assert_eq!(game_pak.read_value(0xA000), 0x11);
+
+    game_pak.set_value(0x0400, 5);
+    assert_eq!(game_pak.read_value(0xA000), 0x22);
+}
+
+#[test]
+fn mbc6_flash_program_and_erase() {
+    let rom = synthetic_rom(0x20, 2, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+
+    // Flash can't be read until it is enabled
+    game_pak.set_value(0x2800, 0x08);
+    assert_eq!(game_pak.read_value(0x4000), 0xFF);
+    game_pak.set_value(0x0C00, 0x01);
+
+    // Without write enable, programming does nothing
+    mbc6_program(&mut game_pak, 5, 0x10, 0x5A);
+    assert_eq!(game_pak.read_value(0x4010), 0xFF);
+
+    game_pak.set_value(0x1000, 0x01);
+    mbc6_program(&mut game_pak, 5, 0x10, 0x5A);
+    assert_eq!(game_pak.read_value(0x4010), 0x5A);
+
+    // Programming can only clear bits
+    mbc6_program(&mut game_pak, 5, 0x10, 0x0F);
+    assert_eq!(game_pak.read_value(0x4010), 0x0A);
+
+    // Erasing sector 0 leaves the rest alone
+    mbc6_program(&mut game_pak, 17, 0x10, 0x33);
+    mbc6_flash_command(&mut game_pak, 0x80);
+    game_pak.set_value(0x5555, 0xAA);
+    game_pak.set_value(0x6AAA, 0x55);
+    game_pak.set_value(0x2000, 5);
+    game_pak.set_value(0x4000, 0x30);
+    assert_eq!(game_pak.read_value(0x4010), 0xFF);
+    game_pak.set_value(0x2000, 17);
+    assert_eq!(game_pak.read_value(0x4010), 0x33);
+
+    // Erase the whole chip
+    mbc6_flash_command(&mut game_pak, 0x80);
+    mbc6_flash_command(&mut game_pak, 0x10);
+    game_pak.set_value(0x2000, 17);
+    assert_eq!(game_pak.read_value(0x4010), 0xFF);
+}
+
+#[test]
+fn mbc6_flash_id() {
+    let rom = synthetic_rom(0x20, 2, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    mbc6_enable_flash(&mut game_pak);
+
+    mbc6_flash_command(&mut game_pak, 0x90);
+    assert_eq!(game_pak.read_value(0x4000), 0xC2);
+    assert_eq!(game_pak.read_value(0x4001), 0x81);
+
+    game_pak.set_value(0x4000, 0xF0);
+    assert_eq!(game_pak.read_value(0x4000), 0xFF);
+}
+
+#[test]
+fn mbc6_flash_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0x20, 2, 0);
+    {
+        let mut game_pak = save.load(&rom);
+        mbc6_enable_flash(&mut game_pak);
+        mbc6_program(&mut game_pak, 3, 0x20, 0x12);
+        game_pak.set_value(0x0000, 0x0A);
+        game_pak.set_value(0x0400, 1);
+        game_pak.set_value(0xA004, 0x34);
+    }
+
+    // 32kB of RAM followed by 1MB of flash which starts out erased
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x8000 + 0x100000);
+    assert_eq!(contents[0x1004], 0x34);
+    assert_eq!(contents[0x8000 + 3 * 0x2000 + 0x20], 0x12);
+    assert_eq!(contents[0x8000], 0xFF);
+
+    let mut game_pak = save.load(&rom);
+    mbc6_enable_flash(&mut game_pak);
+    game_pak.set_value(0x2800, 0x08);
+    game_pak.set_value(0x2000, 3);
+    assert_eq!(game_pak.read_value(0x4020), 0x12);
+    assert_eq!(game_pak.read_value(0x4021), 0xFF);
+}
+
+#[test]
+fn mbc6_flash_erase_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0x20, 2, 0);
+    {
+        let mut game_pak = save.load(&rom);
+        mbc6_enable_flash(&mut game_pak);
+        mbc6_program(&mut game_pak, 3, 0x20, 0x12);
+        mbc6_program(&mut game_pak, 17, 0x20, 0x34);
+
+        // Erase sector 0
+        mbc6_flash_command(&mut game_pak, 0x80);
+        game_pak.set_value(0x5555, 0xAA);
+        game_pak.set_value(0x6AAA, 0x55);
+        game_pak.set_value(0x2000, 0);
+        game_pak.set_value(0x4000, 0x30);
+    }
+
+    let contents = save.contents();
+    assert_eq!(contents.len(), 0x8000 + 0x100000);
+    assert_eq!(contents[0x8000 + 3 * 0x2000 + 0x20], 0xFF);
+    assert_eq!(contents[0x8000 + 17 * 0x2000 + 0x20], 0x34);
+}
+
+fn tama5_write(game_pak: &mut GamePak<Fs>, register: u8, value: u8) {
+    game_pak.set_value(0xA001, register);
+    game_pak.set_value(0xA000, value);
+}
+
+fn tama5_read(game_pak: &mut GamePak<Fs>, register: u8) -> u8 {
+    game_pak.set_value(0xA001, register);
+    game_pak.read_value(0xA000)
+}
+
+/// Run one of the TAMA5's commands on the given 5-bit address and return the result.
+fn tama5_command(game_pak: &mut GamePak<Fs>, command: u8, address: u8, data: u8) -> u8 {
+    tama5_write(game_pak, 0x4, data & 0x0F);
+    tama5_write(game_pak, 0x5, data >> 4);
+    tama5_write(game_pak, 0x6, (command << 1) | (address >> 4));
+    tama5_write(game_pak, 0x7, address & 0x0F);
+    assert_eq!(tama5_read(game_pak, 0xA), 0xF1);
+    (tama5_read(game_pak, 0xC) & 0x0F) | (tama5_read(game_pak, 0xD) << 4)
+}
+
+#[test]
+fn tama5_rom_banking() {
+    let mut rom = synthetic_rom(0xFD, 4, 0);
+    for bank in 0..32 {
+        rom[bank * BANK_SIZE as usize] = bank as u8;
+    }
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(game_pak.read_value(0x4000), 1);
+
+    tama5_write(&mut game_pak, 0x0, 0x3);
+    tama5_write(&mut game_pak, 0x1, 0x1);
+    assert_eq!(game_pak.read_value(0x4000), 0x13);
+}
+
+#[test]
+fn tama5_ram_read_write() {
+    let rom = synthetic_rom(0xFD, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+
+    tama5_command(&mut game_pak, 0, 0x13, 0x5A);
+    tama5_command(&mut game_pak, 0, 0x03, 0xA5);
+    assert_eq!(tama5_command(&mut game_pak, 1, 0x13, 0), 0x5A);
+    assert_eq!(tama5_command(&mut game_pak, 1, 0x03, 0), 0xA5);
+}
+
+#[test]
+fn tama5_rtc() {
+    let rom = synthetic_rom(0xFD, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+    game_pak.set_wall_clock(clock.clone());
+
+    // The clock starts at 2000-01-01 00:00:00, a Saturday
+    let read_time = |game_pak: &mut GamePak<Fs>| -> Vec<u8> {
+        (0x0..=0xC)
+            .map(|r| tama5_command(game_pak, 3, r, 0))
+            .collect()
+    };
+    assert_eq!(
+        read_time(&mut game_pak),
+        [0, 0, 0, 0, 0, 0, 6, 1, 0, 1, 0, 0, 0]
+    );
+
+    clock.advance(24 * 60 * 60 + 12 * 60 * 60 + 34 * 60 + 56);
+    assert_eq!(
+        read_time(&mut game_pak),
+        [6, 5, 4, 3, 2, 1, 0, 2, 0, 1, 0, 0, 0]
+    );
+
+    // Set the year to 2024 and the month to December. The day of the week is left alone.
+    tama5_command(&mut game_pak, 2, 0xC, 2);
+    tama5_command(&mut game_pak, 2, 0xB, 4);
+    tama5_command(&mut game_pak, 2, 0xA, 1);
+    tama5_command(&mut game_pak, 2, 0x9, 2);
+    assert_eq!(
+        read_time(&mut game_pak),
+        [6, 5, 4, 3, 2, 1, 0, 2, 0, 2, 1, 4, 2]
+    );
+}
+
+#[test]
+fn tama5_rtc_set_digit_by_digit() {
+    let rom = synthetic_rom(0xFD, 0, 0);
+    let mut fs = Fs::default();
+    let mut game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+    game_pak.set_wall_clock(clock.clone());
+
+    let read_time = |game_pak: &mut GamePak<Fs>| -> Vec<u8> {
+        (0x0..=0xC)
+            .map(|r| tama5_command(game_pak, 3, r, 0))
+            .collect()
+    };
+
+    // Start at 2000-09-30, then set 2024-10-31 23:59:59 a digit at a time, ones digits first.
+    // None of the dates it passes through are normalized along the way.
+    let digits = [
+        (0x9, 9),
+        (0x7, 0),
+        (0x8, 3),
+        (0x9, 0),
+        (0xA, 1),
+        (0x7, 1),
+        (0xB, 4),
+        (0xC, 2),
+        (0x4, 3),
+        (0x5, 2),
+        (0x2, 9),
+        (0x3, 5),
+        (0x0, 9),
+        (0x1, 5),
+    ];
+    for (register, digit) in digits {
+        tama5_command(&mut game_pak, 2, register, digit);
+    }
+    assert_eq!(
+        read_time(&mut game_pak),
+        [9, 5, 9, 5, 3, 2, 6, 1, 3, 0, 1, 4, 2]
+    );
+
+    // The next second carries into the next month, and the day of the week counts on too.
+    clock.advance(1);
+    assert_eq!(
+        read_time(&mut game_pak),
+        [0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 1, 4, 2]
+    );
+}
+
+#[test]
+fn tama5_persisted_in_sav() {
+    let mut save = TestSave::new();
+
+    let rom = synthetic_rom(0xFD, 0, 0);
+    let clock = TestClock::default();
+    clock.advance(1_000_000);
+
+    {
+        let mut game_pak = save.load(&rom);
+        game_pak.set_wall_clock(clock.clone());
+        tama5_command(&mut game_pak, 0, 0x1F, 0x77);
+        tama5_command(&mut game_pak, 2, 0x3, 4);
+    }
+
+    // 32 bytes of EEPROM followed by the 16 byte clock footer
+    let contents = save.contents();
+    assert_eq!(contents.len(), 32 + 16);
+    assert_eq!(contents[0x1F], 0x77);
+    assert_eq!(&contents[32..40], &(40u64 * 60).to_le_bytes());
+    assert_eq!(&contents[40..48], &1_000_000u64.to_le_bytes());
+
+    clock.advance(60);
+
+    let mut game_pak = save.load(&rom);
+    game_pak.set_wall_clock(clock.clone());
+    assert_eq!(tama5_command(&mut game_pak, 1, 0x1F, 0), 0x77);
+    assert_eq!(tama5_command(&mut game_pak, 3, 0x2, 0), 1);
+    assert_eq!(tama5_command(&mut game_pak, 3, 0x3, 0), 4);
+}
+
+#[test]
+fn error_unknown_memory_bank_controller() {
+    let rom = synthetic_rom(0x42, 0, 0);
+    let mut fs = Fs::default();
+    let error = GamePak::new(&rom, &mut fs, None).unwrap_err();
+    assert!(matches!(
+        error,
+        GamePakError::UnknownMemoryBankController(0x42)
+    ));
+}
+
+#[test]
+fn error_unknown_rom_size() {
+    let mut rom = synthetic_rom(0x00, 0, 0);
+    rom[ROM_SIZE_ADDRESS] = 0x20;
+    let mut fs = Fs::default();
+    let error = GamePak::new(&rom, &mut fs, None).unwrap_err();
+    assert!(matches!(error, GamePakError::UnknownRomSize(0x20)));
+}
+
+#[test]
+fn error_unknown_ram_size() {
+    let rom = synthetic_rom(0x03, 0, 0x07);
+    let mut fs = Fs::default();
+    let error = GamePak::new(&rom, &mut fs, None).unwrap_err();
+    assert!(matches!(error, GamePakError::UnknownRamSize(0x07)));
+}
+
+#[test]
+fn error_malformed_title() {
+    let mut rom = synthetic_rom(0x00, 0, 0);
+    rom[0x0134] = 0xFF;
+    let mut fs = Fs::default();
+    let error = GamePak::new(&rom, &mut fs, None).unwrap_err();
+    assert!(matches!(error, GamePakError::MalformedTitle(t) if t[0] == 0xFF));
+}
+
+#[test]
+fn error_wrong_rom_length() {
+    // The header says 4 banks but there are only 2
+    let mut rom = synthetic_rom(0x01, 0, 0);
+    rom[ROM_SIZE_ADDRESS] = 1;
+    let mut fs = Fs::default();
+    let error = GamePak::new(&rom, &mut fs, None).unwrap_err();
+    assert!(matches!(
+        error,
+        GamePakError::WrongRomLength {
+            expected: 0x10000,
+            actual: 0x8000
+        }
+    ));
+
+    // Too short to even have a header
+    let error = GamePak::new(&[0; 0x100], &mut fs, None).unwrap_err();
+    assert!(matches!(
+        error,
+        GamePakError::WrongRomLength {
+            expected: 0x8000,
+            actual: 0x100
+        }
+    ));
+
+    let rom: &'static [u8] = &[0; 0x8001];
+    let error = GamePak::new_static(rom, &mut fs, None).unwrap_err();
+    assert!(matches!(error, GamePakError::WrongRomLength { .. }));
+}
+
+#[test]
+fn error_io() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut fs = Fs::new(Some(dir.path()));
+    let error = GamePak::from_storage(&mut fs, "missing.gb").unwrap_err();
+    assert!(matches!(error, GamePakError::Io(_)));
+}
+
+#[test]
+fn cartridge_header() {
+    let mut rom = synthetic_rom(0x1B, 1, 3);
+    rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]);
+    rom[LOGO].copy_from_slice(&NINTENDO_LOGO);
+    rom[0x0134..0x0143].copy_from_slice(b"POKEMON_GLDAAUE");
+    rom[0x0143] = 0x80;
+    rom[0x0144..0x0146].copy_from_slice(b"01");
+    rom[0x0146] = 0x03;
+    rom[0x014A] = 0x01;
+    rom[0x014B] = 0x33;
+    rom[0x014C] = 0x02;
+    rom[0x014D] = rom[0x0134..0x014D]
+        .iter()
+        .fold(0u8, |x, &b| x.wrapping_sub(b).wrapping_sub(1));
+    let global: u16 = rom.iter().fold(0u16, |x, &b| x.wrapping_add(b as u16));
+    rom[0x014E..0x0150].copy_from_slice(&global.to_be_bytes());
+
+    let mut fs = Fs::default();
+    let game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    let header = game_pak.header();
+    assert_eq!(header.entry_point, [0x00, 0xC3, 0x50, 0x01]);
+    assert!(header.logo_valid);
+    assert_eq!(header.title, "POKEMON_GLD");
+    assert_eq!(header.manufacturer_code.as_deref(), Some("AAUE"));
+    assert_eq!(header.cgb_support, CgbSupport::Enhanced);
+    assert!(header.sgb_support);
+    assert_eq!(header.new_licensee_code.as_deref(), Some("01"));
+    assert_eq!(
+        header.licensee_name(),
+        Some("Nintendo Research & Development 1")
+    );
+    assert_eq!(header.cartridge_type_name(), Some("MBC5+RAM+BATTERY"));
+    assert_eq!(header.rom_size_bytes(), Some(0x10000));
+    assert_eq!(header.ram_size_bytes(), Some(0x8000));
+    assert_eq!(header.destination, Destination::Overseas);
+    assert_eq!(header.mask_rom_version, 2);
+    assert!(header.header_checksum.verified());
+    assert!(header.global_checksum.verified());
+}
+
+#[test]
+fn title_is_title_area_up_to_nul() {
+    let mut fs = Fs::default();
+
+    let mut rom = synthetic_rom(0x00, 0, 0);
+    rom[0x0134..0x0144].copy_from_slice(b"SIXTEEN LETTERS!");
+    let game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(game_pak.title(), "SIXTEEN LETTERS!");
+
+    // A CGB game with a manufacturer code after a short title
+    let mut rom = synthetic_rom(0x00, 0, 0);
+    rom[0x0134..0x0139].copy_from_slice(b"ZELDA");
+    rom[0x013F..0x0143].copy_from_slice(b"AZ7E");
+    rom[0x0143] = 0x80;
+    let game_pak = GamePak::new(&rom, &mut fs, None).unwrap();
+    assert_eq!(game_pak.title(), "ZELDA");
+    assert_eq!(game_pak.header().manufacturer_code.as_deref(), Some("AZ7E"));
+}
+
+#[test]
+fn cartridge_header_bad_checksums() {
+    let mut rom = synthetic_rom(0x00, 0, 0);
+    rom[0x0134..0x0138].copy_from_slice(b"TEST");
+    rom[0x014B] = 0x01;
+
+    let header = CartridgeHeader::from_rom(&rom).unwrap();
+    assert!(!header.logo_valid);
+    assert_eq!(header.title, "TEST");
+    assert_eq!(header.manufacturer_code, None);
+    assert_eq!(header.cgb_support, CgbSupport::None);
+    assert_eq!(header.licensee_name(), Some("Nintendo"));
+    assert_eq!(header.destination, Destination::Japan);
+
+    let expected_header_checksum = rom[0x0134..0x014D]
+        .iter()
+        .fold(0u8, |x, &b| x.wrapping_sub(b).wrapping_sub(1));
+    assert_eq!(header.header_checksum.expected, 0);
+    assert_eq!(header.header_checksum.actual, expected_header_checksum);
+    assert!(!header.header_checksum.verified());
+    assert_eq!(header.global_checksum.actual, 0x141);
+    assert!(!header.global_checksum.verified());
+}
+
+#[test]
+fn cartridge_header_too_short() {
+    let error = CartridgeHeader::from_rom(&[0; 0x100]).unwrap_err();
+    assert!(matches!(
+        error,
+        GamePakError::WrongRomLength {
+            expected: 0x150,
+            actual: 0x100
+        }
+    ));
+}
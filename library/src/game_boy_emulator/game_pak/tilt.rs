@@ -0,0 +1,37 @@
+// Copyright 2026 Remi Bernotavicius
+
+/// Something that knows which way the Game Boy is being tilted, for cartridges with an
+/// accelerometer. Returns the tilt along the x and y axes from -1.0 to 1.0, where positive x is
+/// tilted to the right and positive y is tilted towards the player.
+pub trait TiltSensor: Send {
+    fn tilt(&self) -> (f32, f32);
+}
+
+/// `TiltSensor` that is always held level.
+#[derive(Default)]
+pub struct LevelTiltSensor;
+
+impl TiltSensor for LevelTiltSensor {
+    fn tilt(&self) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+}
+
+/// `TiltSensor` that can be updated from somewhere else, like a frontend's input handling.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct SharedTilt(std::sync::Arc<std::sync::Mutex<(f32, f32)>>);
+
+#[cfg(feature = "std")]
+impl SharedTilt {
+    pub fn set_tilt(&self, x: f32, y: f32) {
+        *self.0.lock().unwrap() = (x.clamp(-1.0, 1.0), y.clamp(-1.0, 1.0));
+    }
+}
+
+#[cfg(feature = "std")]
+impl TiltSensor for SharedTilt {
+    fn tilt(&self) -> (f32, f32) {
+        *self.0.lock().unwrap()
+    }
+}
@@ -1,10 +1,17 @@
 // Copyright 2019 Remi Bernotavicius
 
 use super::{button_events_from_key_events, JoyPad, KeyEvent, MemoryMappedHardware, PlainJoyPad};
+use crate::game_boy_emulator::Rumble;
+#[cfg(feature = "std")]
+use crate::game_boy_emulator::SharedTilt;
+#[cfg(feature = "std")]
+use crate::rendering::Keycode;
 use alloc::{vec, vec::Vec};
 
 pub struct ControllerJoyPad {
     inner: PlainJoyPad,
+    #[cfg(feature = "std")]
+    tilt: TiltInput,
     #[cfg(feature = "gilrs")]
     gilrs: gilrs::Gilrs,
 }
@@ -21,11 +28,54 @@ impl ControllerJoyPad {
     pub fn new() -> Self {
         Self {
             inner: PlainJoyPad::new(),
+            #[cfg(feature = "std")]
+            tilt: TiltInput::default(),
             #[cfg(feature = "gilrs")]
             gilrs: gilrs::Gilrs::new().unwrap(),
         }
     }
 
+    /// Get a `Rumble` which shakes the game pads connected right now, if they support it.
+    pub fn rumble(&mut self) -> ControllerRumble {
+        ControllerRumble {
+            #[cfg(feature = "gilrs")]
+            effect: self.gilrs_rumble_effect(),
+        }
+    }
+
+    /// Get a `TiltSensor` which is tilted with the right analog stick or the I, J, K and L keys.
+    #[cfg(feature = "std")]
+    pub fn tilt(&self) -> SharedTilt {
+        self.tilt.shared.clone()
+    }
+
+    #[cfg(feature = "gilrs")]
+    fn gilrs_rumble_effect(&mut self) -> Option<gilrs::ff::Effect> {
+        use gilrs::ff::{BaseEffect, BaseEffectType, EffectBuilder};
+
+        let gamepads: Vec<_> = self
+            .gilrs
+            .gamepads()
+            .filter(|(_, g)| g.is_ff_supported())
+            .map(|(id, _)| id)
+            .collect();
+        if gamepads.is_empty() {
+            return None;
+        }
+
+        EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: u16::MAX,
+                },
+                ..Default::default()
+            })
+            .gamepads(&gamepads)
+            .finish(&mut self.gilrs)
+            .map_err(|e| log::warn!("failed to create rumble effect: {e}"))
+            .ok()
+    }
+
     #[cfg(feature = "gilrs")]
     fn read_gilrs_events(&mut self) -> Vec<super::ButtonEvent> {
         use super::{ButtonCode, ButtonEvent};
@@ -64,6 +114,15 @@ impl ControllerJoyPad {
                     button_events.push(ButtonEvent::Up(ButtonCode::Up));
                     button_events.push(ButtonEvent::Up(ButtonCode::Down));
                 }
+                EventType::AxisChanged(Axis::RightStickX, v, _) => {
+                    self.tilt.stick.0 = v;
+                    self.tilt.update();
+                }
+                EventType::AxisChanged(Axis::RightStickY, v, _) => {
+                    // Pushing the stick up tilts the Game Boy away from the player
+                    self.tilt.stick.1 = -v;
+                    self.tilt.update();
+                }
                 _ => (),
             };
         }
@@ -90,6 +149,64 @@ fn button_code_from_controller_button(button: gilrs::ev::Button) -> Option<super
     }
 }
 
+/// Tilt coming from the keyboard and the right analog stick.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct TiltInput {
+    shared: SharedTilt,
+    stick: (f32, f32),
+    left: bool,
+    right: bool,
+    away: bool,
+    towards: bool,
+}
+
+#[cfg(feature = "std")]
+impl TiltInput {
+    fn update(&self) {
+        let keys = |negative: bool, positive: bool| positive as u8 as f32 - negative as u8 as f32;
+        self.shared.set_tilt(
+            self.stick.0 + keys(self.left, self.right),
+            self.stick.1 + keys(self.away, self.towards),
+        );
+    }
+
+    fn respond_to_key_events(&mut self, key_events: &[KeyEvent]) {
+        for event in key_events {
+            let (code, down) = match event {
+                KeyEvent::Down(c) => (c, true),
+                KeyEvent::Up(c) => (c, false),
+            };
+            match code {
+                Keycode::J => self.left = down,
+                Keycode::L => self.right = down,
+                Keycode::I => self.away = down,
+                Keycode::K => self.towards = down,
+                _ => continue,
+            }
+            self.update();
+        }
+    }
+}
+
+pub struct ControllerRumble {
+    #[cfg(feature = "gilrs")]
+    effect: Option<gilrs::ff::Effect>,
+}
+
+impl Rumble for ControllerRumble {
+    #[allow(unused_variables)]
+    fn set_rumble(&mut self, on: bool) {
+        #[cfg(feature = "gilrs")]
+        if let Some(effect) = &self.effect {
+            let res = if on { effect.play() } else { effect.stop() };
+            if let Err(e) = res {
+                log::warn!("failed to rumble: {e}");
+            }
+        }
+    }
+}
+
 impl JoyPad for ControllerJoyPad {
     fn tick(&mut self, _now: u64, key_events: Vec<KeyEvent>) {
         let mut button_events = vec![];
@@ -101,10 +218,17 @@ impl JoyPad for ControllerJoyPad {
             panic!("No controller backend");
         }
 
+        #[cfg(feature = "std")]
+        self.tilt.respond_to_key_events(&key_events);
+
         button_events.extend(button_events_from_key_events(key_events));
         let button_events = self.inner.filter_events(button_events);
         self.inner.respond_to_events(button_events);
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        self.inner.take_interrupt()
+    }
 }
 
 impl MemoryMappedHardware for ControllerJoyPad {
@@ -5,7 +5,7 @@ use crate::rendering::Keycode;
 use alloc::{vec, vec::Vec};
 use serde_derive::{Deserialize, Serialize};
 
-pub use controller::ControllerJoyPad;
+pub use controller::{ControllerJoyPad, ControllerRumble};
 pub use plain::PlainJoyPad;
 pub use replay::{PlaybackJoyPad, RecordingJoyPad};
 
@@ -15,6 +15,10 @@ pub mod replay;
 
 pub trait JoyPad: MemoryMappedHardware {
     fn tick(&mut self, now: u64, key_events: Vec<KeyEvent>);
+
+    /// Returns true if one of the P1 input lines went low since the last time this was called,
+    /// which requests the joypad interrupt.
+    fn take_interrupt(&mut self) -> bool;
 }
 
 impl MemoryMappedHardware for &dyn JoyPad {
@@ -41,6 +45,10 @@ impl JoyPad for &mut dyn JoyPad {
     fn tick(&mut self, now: u64, key_events: Vec<KeyEvent>) {
         (*self).tick(now, key_events)
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        (*self).take_interrupt()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -114,3 +122,117 @@ enum ButtonState {
     #[default]
     NotPressed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{
+        assemble, GameBoyEmulator, GamePak, MemoryAccessor as _, NullGameBoyOps,
+    };
+
+    fn game_boy(program: &str) -> (GameBoyEmulator, NullGameBoyOps) {
+        let rom = assemble(program).unwrap().bin;
+        let mut ops = NullGameBoyOps::null();
+        let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+        ops.load_game_pak(game_pak);
+        ops.plug_in_joy_pad(PlainJoyPad::new());
+        (GameBoyEmulator::new(), ops)
+    }
+
+    fn run_for(e: &mut GameBoyEmulator, ops: &mut NullGameBoyOps, cycles: u64) {
+        let end = e.elapsed_cycles() + cycles;
+        while e.elapsed_cycles() < end {
+            e.tick(ops);
+        }
+    }
+
+    fn read_memory(e: &GameBoyEmulator, ops: &NullGameBoyOps, address: u16) -> u8 {
+        ops.memory_map(&e.bridge).read_memory(address)
+    }
+
+    #[test]
+    fn plain_joy_pad_interrupt_on_falling_edge() {
+        let mut joypad = PlainJoyPad::new();
+        joypad.set_value(0, 0x10);
+
+        joypad.respond_to_events(vec![ButtonEvent::Down(ButtonCode::Up)]);
+        assert!(!joypad.take_interrupt());
+
+        joypad.respond_to_events(vec![ButtonEvent::Down(ButtonCode::A)]);
+        assert!(joypad.take_interrupt());
+        assert!(!joypad.take_interrupt());
+
+        joypad.respond_to_events(vec![ButtonEvent::Up(ButtonCode::A)]);
+        assert!(!joypad.take_interrupt());
+
+        // Selecting a bank with a button already held down also pulls a line low.
+        joypad.set_value(0, 0x20);
+        assert!(joypad.take_interrupt());
+    }
+
+    #[test]
+    fn joypad_interrupt() {
+        let (mut e, mut ops) = game_boy(
+            "
+    SECTION joypad,ROM0[$0060]
+        ld   a,$42
+        ld   [$C000],a
+        reti
+    SECTION test,ROM0[$0100]
+        xor  a
+        ld   [$C000],a
+        ldh  [$FF0F],a
+        ld   a,$10
+        ldh  [$FF00],a
+        ldh  [$FFFF],a
+        ei
+    .loop
+        jr   .loop
+    ",
+        );
+        run_for(&mut e, &mut ops, 2_000);
+
+        // The direction keys aren't selected, so this doesn't change P1.
+        e.joypad_key_events.push(KeyEvent::Down(Keycode::Up));
+        run_for(&mut e, &mut ops, 2_000);
+        assert_eq!(read_memory(&e, &ops, 0xC000), 0x00);
+
+        e.joypad_key_events.push(KeyEvent::Down(Keycode::Z));
+        run_for(&mut e, &mut ops, 2_000);
+        assert_eq!(read_memory(&e, &ops, 0xC000), 0x42);
+    }
+
+    #[test]
+    fn stop_until_button_press() {
+        let (mut e, mut ops) = game_boy(
+            "
+    SECTION test,ROM0[$0100]
+        ld   a,$10
+        ldh  [$FF00],a
+        stop
+        ldh  a,[$FF04]
+        ld   [$C000],a
+        jp   $0150
+    SECTION done,ROM0[$0150]
+    .done
+        jr   .done
+    ",
+        );
+        run_for(&mut e, &mut ops, 100_000);
+
+        assert!(e.cpu.is_stopped());
+        assert_eq!(e.cpu.read_program_counter(), 0x0106);
+        assert_eq!(read_memory(&e, &ops, 0xFF04), 0x00);
+        assert_eq!(read_memory(&e, &ops, 0xFF44), 0x00);
+        assert_eq!(read_memory(&e, &ops, 0xFF41) & 0x03, 0x00);
+
+        e.joypad_key_events.push(KeyEvent::Down(Keycode::Return));
+        run_for(&mut e, &mut ops, 2_000);
+
+        assert!(!e.cpu.is_stopped());
+        assert_eq!(e.cpu.read_program_counter(), 0x0150);
+        assert_eq!(read_memory(&e, &ops, 0xC000), 0x00);
+        assert_ne!(read_memory(&e, &ops, 0xFF04), 0x00);
+        assert_ne!(read_memory(&e, &ops, 0xFF44), 0x00);
+    }
+}
@@ -5,6 +5,7 @@ use super::{
     KeyEvent, MemoryMappedHardware,
 };
 use alloc::vec::Vec;
+use core::mem;
 
 #[derive(Clone, Copy, Default, PartialEq)]
 enum KeyBank {
@@ -26,6 +27,7 @@ pub struct PlainJoyPad {
     left: ButtonState,
     right: ButtonState,
     bank: KeyBank,
+    interrupt_requested: bool,
 }
 
 impl MemoryMappedHardware for PlainJoyPad {
@@ -51,6 +53,7 @@ impl MemoryMappedHardware for PlainJoyPad {
     }
 
     fn set_value(&mut self, _: u16, value: u8) {
+        let lines = self.input_lines();
         self.bank = if value & JoypadFlag::SelectButtonKeys as u8 == 0
             && value & JoypadFlag::SelectDirectionKeys as u8 == 0
         {
@@ -62,6 +65,7 @@ impl MemoryMappedHardware for PlainJoyPad {
         } else {
             KeyBank::Neither
         };
+        self.update_interrupt(lines);
     }
 }
 
@@ -102,6 +106,19 @@ impl PlainJoyPad {
         })
     }
 
+    /// The four input lines of P1, a line is low when a button on a selected bank is pressed.
+    fn input_lines(&self) -> u8 {
+        self.read_value(0) & 0x0F
+    }
+
+    /// The joypad interrupt is requested whenever one of the input lines goes from high to low.
+    /// This happens both from pressing a button and from selecting a bank with a button held down.
+    fn update_interrupt(&mut self, previous_lines: u8) {
+        if previous_lines & !self.input_lines() != 0 {
+            self.interrupt_requested = true;
+        }
+    }
+
     fn get_state(&mut self, code: ButtonCode) -> &mut ButtonState {
         match code {
             ButtonCode::A => &mut self.a,
@@ -127,12 +144,14 @@ impl PlainJoyPad {
     }
 
     pub fn respond_to_events(&mut self, button_events: Vec<ButtonEvent>) {
+        let lines = self.input_lines();
         for event in button_events {
             match event {
                 ButtonEvent::Up(c) => *self.get_state(c) = ButtonState::NotPressed,
                 ButtonEvent::Down(c) => *self.get_state(c) = ButtonState::Pressed,
             }
         }
+        self.update_interrupt(lines);
     }
 }
 
@@ -142,4 +161,8 @@ impl JoyPad for PlainJoyPad {
         let button_events = self.filter_events(button_events);
         self.respond_to_events(button_events);
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        mem::take(&mut self.interrupt_requested)
+    }
 }
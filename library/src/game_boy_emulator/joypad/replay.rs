@@ -89,6 +89,10 @@ impl<Storage: PersistentStorage> JoyPad for RecordingJoyPad<Storage> {
             self.inner.respond_to_events(entry.button_events);
         }
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        self.inner.take_interrupt()
+    }
 }
 
 impl<Storage: PersistentStorage> MemoryMappedHardware for RecordingJoyPad<Storage> {
@@ -134,6 +138,10 @@ impl<Storage: PersistentStorage> JoyPad for PlaybackJoyPad<Storage> {
             self.current_entry = crate::codec::deserialize_from(&mut self.input_file).ok();
         }
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        self.inner.take_interrupt()
+    }
 }
 
 impl<Storage: PersistentStorage> MemoryMappedHardware for PlaybackJoyPad<Storage> {
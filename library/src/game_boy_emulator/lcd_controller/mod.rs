@@ -22,6 +22,13 @@
 //! before going back to Mode 2 repeating the cycle. Most programs will want to do their work
 //! updating the data the LCD Controller reads during Mode 1, which is the longest mode.
 //!
+//! On the DMG, putting an address in OAM on the bus while the LCD Controller is reading OAM in
+//! Mode 2 corrupts it. See the `oam_bug` module for the details.
+//!
+//! The STAT interrupt sources (the modes and LY matching LYC) are all ORed together into one line,
+//! and the interrupt is only requested when that line goes high. So while one enabled source holds
+//! the line high, another one becoming true doesn't cause another interrupt.
+//!
 //! Here is a list of various terms used throughout.
 //!
 //! - LCD: Liquid Crystal Display; This is the type of display in the Game Boy.
@@ -45,20 +52,23 @@
 //!
 
 use crate::game_boy_emulator::memory_controller::{
-    FlagMask, GameBoyFlags, GameBoyRegister, MemoryChunk, MemoryMappedHardware,
+    BusAccess, FlagMask, GameBoyFlags, GameBoyRegister, MemoryChunk, MemoryMappedHardware,
 };
-use crate::game_boy_emulator::{GameBoyEmulatorEvent, GameBoyScheduler, InterruptFlag};
+use crate::game_boy_emulator::{oam_dma, GameBoyEmulatorEvent, GameBoyScheduler, InterruptFlag};
 use crate::rendering::Renderer;
 use alloc::vec::Vec;
 use core::ops::Range;
-use core::{fmt, iter};
+use core::{fmt, iter, mem};
 use enum_iterator::IntoEnumIterator;
 use num_enum::IntoPrimitive;
 pub use palette::Palette;
+use pixel_fifo::PixelFifo;
 use serde_derive::{Deserialize, Serialize};
 use strum_macros::IntoStaticStr;
 
+mod oam_bug;
 mod palette;
+mod pixel_fifo;
 
 /// The width of the screen in pixels
 const SCREEN_WIDTH: i32 = 160;
@@ -66,6 +76,9 @@ const SCREEN_WIDTH: i32 = 160;
 /// The height of the screen in pixels
 const SCREEN_HEIGHT: i32 = 144;
 
+/// How many dots shorter the first line is after the LCD is turned on.
+const LCD_ON_FIRST_LINE_SHORTENING: u64 = 4;
+
 /// This is the size (width and height) in pixels of one piece of character data.
 const CHARACTER_SIZE: i32 = 8;
 
@@ -120,6 +133,18 @@ const UNUSABLE_MEMORY: Range<u16> = Range {
 /// The maximum number of sprites that can appear on one line
 const LINE_SPRITE_LIMIT: usize = 10;
 
+/// How the LCD Controller draws the screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PpuMode {
+    /// Draw each line all at once at the start of mode 3. This is faster, but mode 3 is always the
+    /// same length and changes made in the middle of a line are missed.
+    #[default]
+    Scanline,
+    /// Draw each line a dot at a time using a pixel FIFO like the real hardware. Changes made to
+    /// registers in the middle of a line show up, and mode 3 takes as long as it does on hardware.
+    PixelFifo,
+}
+
 /// This register is used to control DMA (direct memory access). It allows bulk transfers of
 /// memory.
 #[derive(Default, Serialize, Deserialize)]
@@ -160,7 +185,7 @@ impl<'a> MemoryMappedHardware for (&'a mut DmaRegister, &'a mut GameBoyScheduler
 
         let address = reg.get_request_addr();
         scheduler.schedule(
-            scheduler.now(),
+            scheduler.now() + oam_dma::STARTUP_DELAY,
             GameBoyEmulatorEvent::StartDmaTransfer { address },
         );
     }
@@ -587,6 +612,9 @@ pub enum LcdControllerEvent {
     Mode1,
     Mode2,
     Mode3,
+    ResetLy,
+    StatWritten,
+    TransferPixels,
     UpdateLyMatch,
 }
 
@@ -596,16 +624,20 @@ impl LcdControllerEvent {
         self,
         controller: &mut LcdController,
         renderer: &mut impl Renderer,
+        ppu_mode: PpuMode,
         interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
         scheduler: &mut GameBoyScheduler,
         time: u64,
     ) {
         match self {
-            Self::AdvanceLy => controller.advance_ly(scheduler, time),
+            Self::AdvanceLy => controller.advance_ly(interrupt_flag, scheduler, time),
             Self::Mode0 => controller.mode_0(interrupt_flag, scheduler, time),
             Self::Mode1 => controller.mode_1(renderer, interrupt_flag, scheduler, time),
             Self::Mode2 => controller.mode_2(interrupt_flag, scheduler, time),
-            Self::Mode3 => controller.mode_3(renderer, scheduler, time),
+            Self::Mode3 => controller.mode_3(renderer, ppu_mode, interrupt_flag, scheduler, time),
+            Self::ResetLy => controller.set_ly(0, interrupt_flag, scheduler, time),
+            Self::StatWritten => controller.stat_written(interrupt_flag, scheduler, time),
+            Self::TransferPixels => controller.transfer_pixels(renderer, scheduler, time),
             Self::UpdateLyMatch => controller.update_ly_match(interrupt_flag, scheduler, time),
         }
     }
@@ -653,6 +685,19 @@ pub struct LcdController {
     pub unusable_memory: MemoryChunk,
     pub registers: LcdControllerRegisters,
     enabled: bool,
+    /// When the current line started, the next one starts 456 cycles later.
+    line_start: u64,
+    /// The line of the window to draw next. It only advances on lines the window was drawn on.
+    window_line: u8,
+    /// Set once LY has matched WY this frame. The window can't show until then.
+    window_y_triggered: bool,
+    /// With WX at 166 the window carries on through the whole of the next line.
+    window_wraps: bool,
+    /// All the enabled STAT interrupt sources ORed together.
+    stat_line: bool,
+    /// Set when the LCD is turned on, the first frame after that isn't shown.
+    blank_frame: bool,
+    pixel_fifo: PixelFifo,
     #[serde(skip)]
     object_buffer: Vec<LcdObject>,
     #[serde(skip)]
@@ -674,6 +719,13 @@ impl LcdController {
             oam_data: MemoryChunk::from_range(OAM_DATA),
             unusable_memory: MemoryChunk::from_range(UNUSABLE_MEMORY),
             enabled: true,
+            line_start: 0,
+            window_line: 0,
+            window_y_triggered: false,
+            window_wraps: false,
+            stat_line: false,
+            blank_frame: false,
+            pixel_fifo: Default::default(),
             registers: Default::default(),
             object_buffer: Vec::new(),
             palette: Default::default(),
@@ -682,6 +734,9 @@ impl LcdController {
 
     /// Must be called after creation to schedule events needed for proper operation.
     pub(crate) fn schedule_initial_events(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        if !self.enabled {
+            return;
+        }
         scheduler.schedule(now + 56, LcdControllerEvent::Mode2);
         scheduler.schedule(now + 56 + 456, LcdControllerEvent::AdvanceLy);
     }
@@ -704,6 +759,12 @@ impl LcdController {
         }
     }
 
+    /// Resets the state back to what it is when the Game Boy is switched on. The LCD stays off
+    /// until the boot ROM turns it on.
+    pub fn set_state_power_on(&mut self) {
+        self.enabled = false;
+    }
+
     /// Resets the state back to what it is when the Game Boy boots
     pub fn set_state_post_bios(&mut self) {
         self.registers
@@ -758,12 +819,34 @@ impl LcdController {
         (x, y)
     }
 
+    /// Where the window starts on the current line, or `None` if it isn't on this line.
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn get_window_origin_relative_to_lcd(&self) -> (i32, i32) {
-        let x = self.registers.wx.read_value() as i32 - 7;
-        let y = self.registers.wy.read_value() as i32;
+    fn window_start_x(&self) -> Option<i32> {
+        if !self.registers.lcdc.read_flag(LcdControlFlag::WindowingOn) || !self.window_y_triggered {
+            return None;
+        }
+        if self.window_wraps {
+            return Some(0);
+        }
+        let wx = self.registers.wx.read_value() as i32;
+        (wx - 7 < SCREEN_WIDTH).then_some(wx - 7)
+    }
 
-        (x, y)
+    /// Called at the end of each line drawn, moves the window on to its next line.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn end_window_line(&mut self, window_drawn: bool) {
+        if window_drawn {
+            self.window_line = self.window_line.wrapping_add(1);
+        }
+        self.window_wraps =
+            window_drawn && self.registers.wx.read_value() as i32 - 7 == SCREEN_WIDTH - 1;
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn reset_window(&mut self) {
+        self.window_line = 0;
+        self.window_y_triggered = false;
+        self.window_wraps = false;
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -890,17 +973,11 @@ impl LcdController {
         scheduler: &mut GameBoyScheduler,
         time: u64,
     ) {
+        self.line_start = time;
         self.oam_data.borrow();
         self.unusable_memory.borrow();
         self.registers.stat.set_flag_value(LcdStatusFlag::Mode, 0x2);
-        if self
-            .registers
-            .stat
-            .read_flag(LcdStatusFlag::InterruptMode10)
-        {
-            interrupt_flag.set_flag(InterruptFlag::LCDSTAT, true);
-            scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
-        }
+        self.update_stat_line(interrupt_flag, scheduler, time);
 
         scheduler.schedule(time + 77, LcdControllerEvent::Mode3);
     }
@@ -927,16 +1004,18 @@ impl LcdController {
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn draw_window(&mut self, line: &mut ScanLine) {
-        if !self.registers.lcdc.read_flag(LcdControlFlag::WindowingOn) {
-            return;
-        }
+    fn draw_window(&mut self, line: &mut ScanLine) -> bool {
+        let Some(scroll_x) = self.window_start_x() else {
+            return false;
+        };
 
         let window_area_selection = self
             .registers
             .lcdc
             .read_flag(LcdControlFlag::WindowCodeAreaSelection);
-        let (scroll_x, scroll_y) = self.get_window_origin_relative_to_lcd();
+
+        // The window isn't drawn from LY, but from its own line counter.
+        let scroll_y = self.registers.ly.read_value() as i32 - self.window_line as i32;
         self.draw_tiles(
             line,
             scroll_x,
@@ -945,12 +1024,15 @@ impl LcdController {
             false,
             false,
         );
+        true
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn mode_3(
         &mut self,
         renderer: &mut impl Renderer,
+        ppu_mode: PpuMode,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
         scheduler: &mut GameBoyScheduler,
         time: u64,
     ) {
@@ -962,28 +1044,74 @@ impl LcdController {
         self.background_display_data_1.borrow();
         self.background_display_data_2.borrow();
 
-        let mut line = ScanLine::new();
-        self.draw_background(&mut line);
-        self.draw_window(&mut line);
-        self.draw_oam_data(&mut line);
-        line.draw(renderer, &self.palette, ly as i32);
+        if self.registers.stat.read_flag_value(LcdStatusFlag::Mode) == 0x0 {
+            // The first line after the LCD is turned on has no mode 2, so OAM is only locked now.
+            self.oam_data.borrow();
+            self.unusable_memory.borrow();
+        }
 
         self.registers.stat.set_flag_value(LcdStatusFlag::Mode, 0x3);
-        scheduler.schedule(time + 175, LcdControllerEvent::Mode0);
+        self.update_stat_line(interrupt_flag, scheduler, time);
+
+        // Once LY has matched WY the window is free to show for the rest of the frame.
+        if ly == self.registers.wy.read_value() {
+            self.window_y_triggered = true;
+        }
+
+        match ppu_mode {
+            PpuMode::Scanline => {
+                let mut line = ScanLine::new();
+                self.draw_background(&mut line);
+                let window_drawn = self.draw_window(&mut line);
+                self.draw_oam_data(&mut line);
+                line.draw(renderer, &self.palette, ly as i32);
+                self.end_window_line(window_drawn);
+
+                scheduler.schedule(time + 175, LcdControllerEvent::Mode0);
+            }
+            PpuMode::PixelFifo => {
+                let mut pixel_fifo = mem::take(&mut self.pixel_fifo);
+                pixel_fifo.start(self);
+                self.pixel_fifo = pixel_fifo;
+
+                self.transfer_pixels(renderer, scheduler, time);
+            }
+        }
     }
 
+    /// Draws the next four dots of the line. Mode 3 ends when the last pixel is drawn.
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn mode_0(
+    fn transfer_pixels(
         &mut self,
-        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        renderer: &mut impl Renderer,
         scheduler: &mut GameBoyScheduler,
         time: u64,
     ) {
-        if !self.enabled {
-            scheduler.schedule(time + 4, LcdControllerEvent::Mode0);
-            return;
+        let mut pixel_fifo = mem::take(&mut self.pixel_fifo);
+        let start = pixel_fifo.dots();
+
+        let mut done = false;
+        while !done && pixel_fifo.dots() - start < 4 {
+            done = pixel_fifo.dot(self, renderer);
+        }
+        let elapsed = (pixel_fifo.dots() - start) as u64;
+        self.pixel_fifo = pixel_fifo;
+
+        if done {
+            self.end_window_line(self.pixel_fifo.window_drawn());
+            scheduler.schedule(time + elapsed, LcdControllerEvent::Mode0);
+        } else {
+            scheduler.schedule(time + elapsed, LcdControllerEvent::TransferPixels);
         }
+    }
 
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn mode_0(
+        &mut self,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
         self.character_data.release();
         self.background_display_data_1.release();
         self.background_display_data_2.release();
@@ -991,37 +1119,51 @@ impl LcdController {
         self.unusable_memory.release();
 
         self.registers.stat.set_flag_value(LcdStatusFlag::Mode, 0x0);
+        self.update_stat_line(interrupt_flag, scheduler, time);
 
-        if self
-            .registers
-            .stat
-            .read_flag(LcdStatusFlag::InterruptMode00)
-        {
-            interrupt_flag.set_flag(InterruptFlag::LCDSTAT, true);
-            scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
-        }
-
+        let next_line = self.line_start + 456;
         if self.registers.ly.read_value() < 143 {
-            scheduler.schedule(time + 204, LcdControllerEvent::Mode2);
+            scheduler.schedule(next_line, LcdControllerEvent::Mode2);
         } else {
-            scheduler.schedule(time + 204, LcdControllerEvent::Mode1);
+            scheduler.schedule(next_line, LcdControllerEvent::Mode1);
         }
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn advance_ly(&mut self, scheduler: &mut GameBoyScheduler, time: u64) {
+    fn advance_ly(
+        &mut self,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
         // This advances the ly register, which represents the horizontal line that is currently
         // being drawn on the LCD.
-        self.registers.ly.add(1);
-
-        // There are only 154 lines, so wrap back to zero after that.
-        if self.registers.ly.read_value() > 153 {
-            self.registers.ly.set_value(0);
+        let ly = self.registers.ly.read_value() + 1;
+        self.set_ly(ly, interrupt_flag, scheduler, time);
+
+        if ly == 153 {
+            // LY only reads 153 at the very start of the last line, for the rest of it LY already
+            // reads 0. So there is nothing to advance until line 1.
+            scheduler.schedule(time + 4, LcdControllerEvent::ResetLy);
+            scheduler.schedule(time + 456 * 2, LcdControllerEvent::AdvanceLy);
+        } else {
+            scheduler.schedule(time + 456, LcdControllerEvent::AdvanceLy);
         }
+    }
 
-        scheduler.schedule(time + 456, LcdControllerEvent::AdvanceLy);
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_ly(
+        &mut self,
+        ly: u8,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
+        self.registers.ly.set_value(ly);
 
+        // LY and LYC don't compare as equal for a cycle while LY is changing.
         self.registers.stat.set_flag(LcdStatusFlag::LYMatch, false);
+        self.update_stat_line(interrupt_flag, scheduler, time);
         scheduler.schedule(time + 1, LcdControllerEvent::UpdateLyMatch);
     }
 
@@ -1032,17 +1174,78 @@ impl LcdController {
         scheduler: &mut GameBoyScheduler,
         time: u64,
     ) {
-        if self.registers.ly.read_value() == self.registers.lyc.read_value() {
-            self.registers.stat.set_flag(LcdStatusFlag::LYMatch, true);
-            if self
-                .registers
-                .stat
-                .read_flag(LcdStatusFlag::InterruptLYMatching)
-            {
-                interrupt_flag.set_flag(InterruptFlag::LCDSTAT, true);
-                scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
-            }
+        let ly_match = self.registers.ly.read_value() == self.registers.lyc.read_value();
+        self.registers
+            .stat
+            .set_flag(LcdStatusFlag::LYMatch, ly_match);
+        self.update_stat_line(interrupt_flag, scheduler, time);
+    }
+
+    /// Whether any of the given STAT interrupt sources are currently true. `enabled` is a mask of
+    /// STAT interrupt enable flags.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn stat_sources(&self, enabled: u8) -> bool {
+        let stat = &self.registers.stat;
+        let mode_source = match stat.read_flag_value(LcdStatusFlag::Mode) {
+            0x0 => LcdStatusFlag::InterruptMode00 as u8,
+            0x1 => LcdStatusFlag::InterruptMode01 as u8,
+            0x2 => LcdStatusFlag::InterruptMode10 as u8,
+            _ => 0,
+        };
+        let ly_match_source = if stat.read_flag(LcdStatusFlag::LYMatch) {
+            LcdStatusFlag::InterruptLYMatching as u8
+        } else {
+            0
+        };
+        (mode_source | ly_match_source) & enabled != 0
+    }
+
+    /// Raises the STAT line to the given level, requesting the interrupt if it was low.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_stat_line(
+        &mut self,
+        level: bool,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
+        if level && !self.stat_line {
+            interrupt_flag.set_flag(InterruptFlag::LCDSTAT, true);
+            scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
         }
+        self.stat_line = level;
+    }
+
+    /// Must be called whenever the mode, LY matching LYC, or which sources are enabled changes.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn update_stat_line(
+        &mut self,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
+        let level = self.stat_sources(self.registers.stat.read_value());
+        self.set_stat_line(level, interrupt_flag, scheduler, time);
+    }
+
+    /// On the DMG writing to STAT enables every source for a moment before the written value takes
+    /// effect. This causes a spurious interrupt when written during mode 0 or 1, or while LY
+    /// matches LYC.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn stat_written(
+        &mut self,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        time: u64,
+    ) {
+        let level = self.stat_sources(
+            LcdStatusFlag::InterruptMode00 as u8
+                | LcdStatusFlag::InterruptMode01 as u8
+                | LcdStatusFlag::InterruptLYMatching as u8,
+        );
+        self.set_stat_line(level, interrupt_flag, scheduler, time);
+
+        self.update_stat_line(interrupt_flag, scheduler, time);
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1054,19 +1257,18 @@ impl LcdController {
         time: u64,
     ) {
         self.registers.stat.set_flag_value(LcdStatusFlag::Mode, 0x1);
+        if mem::take(&mut self.blank_frame) {
+            for y in 0..SCREEN_HEIGHT {
+                ScanLine::new().draw(renderer, &self.palette, y);
+            }
+        }
         renderer.present();
+        self.reset_window();
 
         interrupt_flag.set_flag(InterruptFlag::VerticalBlanking, true);
-
-        if self
-            .registers
-            .stat
-            .read_flag(LcdStatusFlag::InterruptMode01)
-        {
-            interrupt_flag.set_flag(InterruptFlag::LCDSTAT, true);
-        }
-
         scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
+        self.update_stat_line(interrupt_flag, scheduler, time);
+
         scheduler.schedule(time + 4560, LcdControllerEvent::Mode2);
     }
 
@@ -1075,7 +1277,15 @@ impl LcdController {
         assert!(!self.enabled);
 
         self.enabled = true;
-        self.schedule_initial_events(scheduler, scheduler.now());
+        self.blank_frame = true;
+
+        // LY starts again from 0, but the first line is a little shorter than the rest. It also
+        // stays in mode 0 where it would normally be in mode 2.
+        let now = scheduler.now();
+        self.line_start = now.saturating_sub(LCD_ON_FIRST_LINE_SHORTENING);
+        scheduler.schedule(self.line_start + 77, LcdControllerEvent::Mode3);
+        scheduler.schedule(self.line_start + 456, LcdControllerEvent::AdvanceLy);
+        scheduler.schedule(now, LcdControllerEvent::UpdateLyMatch);
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1097,23 +1307,77 @@ impl LcdController {
 
         self.registers.stat.set_flag_value(LcdStatusFlag::Mode, 0x0);
         self.registers.ly.set_value(0);
+        self.stat_line = false;
+        self.reset_window();
         scheduler.drop_events(|e| matches!(e, GameBoyEmulatorEvent::Lcd(_)));
 
         self.enabled = false;
     }
 
+    /// The STOP instruction turns the LCD off without touching LCDC.
+    pub(crate) fn stop(&mut self, scheduler: &mut GameBoyScheduler) {
+        if self.enabled {
+            self.disable(scheduler);
+        }
+    }
+
+    /// Turns the LCD back on after STOP, if LCDC says it should be on.
+    pub(crate) fn resume(&mut self, scheduler: &mut GameBoyScheduler) {
+        if !self.enabled && self.registers.lcdc.read_flag(LcdControlFlag::DisplayOn) {
+            self.enable(scheduler);
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_register(&self, address: u16) -> u8 {
+        let registers = &self.registers;
+        match address {
+            0 => registers.lcdc.read_value(),
+            1 => registers.stat.read_value(),
+            2 => registers.scy.read_value(),
+            3 => registers.scx.read_value(),
+            4 => registers.ly.read_value(),
+            5 => registers.lyc.read_value(),
+            _ => panic!("address = {address}"),
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_lcdc(&mut self, scheduler: &mut GameBoyScheduler, value: u8) {
+        self.registers.lcdc.set_value(value);
+
+        let lcdc_enabled = self.registers.lcdc.read_flag(LcdControlFlag::DisplayOn);
+        if self.enabled != lcdc_enabled {
+            if lcdc_enabled {
+                self.enable(scheduler);
+            } else {
+                self.disable(scheduler);
+            }
+        }
+    }
+
+    /// Corrupts OAM if the given address is in it and the LCD Controller is currently reading it.
+    fn increment_decrement(&mut self, now: u64, address: u16, access: BusAccess) {
+        let mode = self.registers.stat.read_flag_value(LcdStatusFlag::Mode);
+        if !self.enabled || mode != 0x2 || !(0xFE00..=0xFEFF).contains(&address) {
+            return;
+        }
+
+        let row = (now - self.line_start) / 4;
+        oam_bug::corrupt(self.oam_data.as_mut_slice(), row as usize, access);
+    }
+
     pub fn palette_mut(&mut self) -> &mut Palette {
         &mut self.palette
     }
 }
 
-/// This implementation is where reads for LCDC go
+/// This implementation is where reads for LCDC through LYC go
 impl<'a> MemoryMappedHardware for (&'a LcdController, &'a GameBoyScheduler) {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn read_value(&self, address: u16) -> u8 {
-        assert_eq!(address, 0);
         let (controller, _scheduler) = self;
-        controller.registers.lcdc.read_value()
+        controller.read_register(address)
     }
 
     fn set_value(&mut self, _address: u16, _value: u8) {
@@ -1121,34 +1385,44 @@ impl<'a> MemoryMappedHardware for (&'a LcdController, &'a GameBoyScheduler) {
     }
 }
 
-/// This implementation is where the writes for LCDC go
+/// This implementation is where the writes for LCDC through LYC go
 impl<'a> MemoryMappedHardware for (&'a mut LcdController, &'a mut GameBoyScheduler) {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn read_value(&self, address: u16) -> u8 {
-        assert_eq!(address, 0);
         let (controller, _scheduler) = self;
-        controller.registers.lcdc.read_value()
+        controller.read_register(address)
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn set_value(&mut self, address: u16, value: u8) {
-        assert_eq!(address, 0);
         let (controller, scheduler) = self;
-
-        controller.registers.lcdc.set_value(value);
-
-        let lcdc_enabled = controller
-            .registers
-            .lcdc
-            .read_flag(LcdControlFlag::DisplayOn);
-        if controller.enabled != lcdc_enabled {
-            if lcdc_enabled {
-                controller.enable(scheduler);
-            } else {
-                controller.disable(scheduler);
+        let registers = &mut controller.registers;
+        match address {
+            0 => controller.set_lcdc(scheduler, value),
+            1 => {
+                registers.stat.set_value(value);
+                if controller.enabled {
+                    scheduler.schedule(scheduler.now(), LcdControllerEvent::StatWritten);
+                }
+            }
+            2 => registers.scy.set_value(value),
+            3 => registers.scx.set_value(value),
+            4 => registers.ly.set_value(value),
+            5 => {
+                registers.lyc.set_value(value);
+                if controller.enabled {
+                    scheduler.schedule(scheduler.now(), LcdControllerEvent::UpdateLyMatch);
+                }
             }
+            _ => panic!("address = {address}"),
         }
     }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn increment_decrement(&mut self, address: u16, access: BusAccess) {
+        let (controller, scheduler) = self;
+        controller.increment_decrement(scheduler.now(), address, access);
+    }
 }
 
 #[cfg(test)]
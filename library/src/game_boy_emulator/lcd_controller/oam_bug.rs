@@ -0,0 +1,142 @@
+// Copyright 2026 Remi Bernotavicius
+
+//! The DMG corrupts OAM when the CPU puts an address in OAM on the bus while the PPU is reading
+//! OAM in mode 2. The PPU reads one 8-byte row every M-cycle, and the row it is reading ends up
+//! as a mix of itself and the row before it. It happens for reads and writes, and also when the
+//! 16-bit increment / decrement unit puts its value on the bus without any access happening.
+//!
+//! The patterns here treat OAM as 16-bit little-endian words, four to a row.
+
+use crate::game_boy_emulator::memory_controller::BusAccess;
+
+/// How many bytes of OAM the PPU reads in one M-cycle.
+const ROW_SIZE: usize = 8;
+
+/// How many rows the PPU reads during mode 2.
+pub(super) const NUM_ROWS: usize = 20;
+
+fn word(oam: &[u8], row: usize, index: usize) -> u16 {
+    let offset = row * ROW_SIZE + index * 2;
+    u16::from_le_bytes([oam[offset], oam[offset + 1]])
+}
+
+fn set_word(oam: &mut [u8], row: usize, index: usize, value: u16) {
+    let offset = row * ROW_SIZE + index * 2;
+    oam[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+/// The first word of the row is mixed with the row before it, and the rest of the row is replaced
+/// with the row before it.
+fn corrupt_row(oam: &mut [u8], row: usize, first_word: impl Fn(u16, u16, u16) -> u16) {
+    let a = word(oam, row, 0);
+    let b = word(oam, row - 1, 0);
+    let c = word(oam, row - 1, 2);
+    set_word(oam, row, 0, first_word(a, b, c));
+    oam.copy_within((row - 1) * ROW_SIZE + 2..row * ROW_SIZE, row * ROW_SIZE + 2);
+}
+
+fn corrupt_write(oam: &mut [u8], row: usize) {
+    corrupt_row(oam, row, |a, b, c| ((a ^ c) & (b ^ c)) ^ c);
+}
+
+fn corrupt_read(oam: &mut [u8], row: usize) {
+    corrupt_row(oam, row, |a, b, c| b | (a & c));
+}
+
+/// A read at the same time as an increment or decrement first mixes the row before into the row
+/// two before, spreads that over both of them and the current row, and then corrupts the current
+/// row like a read.
+fn corrupt_read_increment_decrement(oam: &mut [u8], row: usize) {
+    if (4..NUM_ROWS - 1).contains(&row) {
+        let a = word(oam, row - 2, 0);
+        let b = word(oam, row - 1, 0);
+        let c = word(oam, row, 0);
+        let d = word(oam, row - 2, 2);
+        set_word(oam, row - 1, 0, (b & (a | c | d)) | (a & c & d));
+
+        let previous = (row - 1) * ROW_SIZE..row * ROW_SIZE;
+        oam.copy_within(previous.clone(), (row - 2) * ROW_SIZE);
+        oam.copy_within(previous, row * ROW_SIZE);
+    }
+    corrupt_read(oam, row);
+}
+
+/// Corrupts OAM for an access made while the PPU was reading the given row. The first row is never
+/// corrupted.
+pub(super) fn corrupt(oam: &mut [u8], row: usize, access: BusAccess) {
+    if row == 0 || row >= NUM_ROWS {
+        return;
+    }
+
+    match access {
+        BusAccess::Idle | BusAccess::Write => corrupt_write(oam, row),
+        BusAccess::Read => corrupt_read_increment_decrement(oam, row),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn oam() -> Vec<u8> {
+        (0..0xA0).collect()
+    }
+
+    fn row(oam: &[u8], row: usize) -> &[u8] {
+        &oam[row * ROW_SIZE..(row + 1) * ROW_SIZE]
+    }
+
+    #[test]
+    fn first_row_is_not_corrupted() {
+        let mut actual = oam();
+        corrupt(&mut actual, 0, BusAccess::Write);
+        corrupt(&mut actual, 0, BusAccess::Read);
+        assert_eq!(actual, oam());
+    }
+
+    #[test]
+    fn write_corruption() {
+        let mut actual = oam();
+        corrupt(&mut actual, 2, BusAccess::Idle);
+
+        let (a, b, c) = (0x1110, 0x0908, 0x0D0C);
+        let first = ((a ^ c) & (b ^ c)) ^ c;
+        let mut expected = oam();
+        expected[16..18].copy_from_slice(&u16::to_le_bytes(first));
+        expected[18..24].copy_from_slice(&oam()[10..16]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn read_increment_decrement_corruption() {
+        let mut actual = oam();
+        corrupt(&mut actual, 6, BusAccess::Read);
+
+        // The row before and two rows before end up the same, and the current row is that row
+        // with its first word corrupted again like a read.
+        assert_eq!(row(&actual, 4), row(&actual, 5));
+        assert_eq!(row(&actual, 5)[2..], row(&actual, 6)[2..]);
+        assert_eq!(row(&actual, 7), row(&oam(), 7));
+        assert_eq!(row(&actual, 3), row(&oam(), 3));
+
+        let (a, b, c, d) = (0x2120, 0x2928, 0x3130, 0x2524);
+        let previous = (b & (a | c | d)) | (a & c & d);
+        assert_eq!(row(&actual, 5)[..2], u16::to_le_bytes(previous));
+
+        let (a, b, c) = (previous, previous, word(&actual, 5, 2));
+        assert_eq!(word(&actual, 6, 0), b | (a & c));
+    }
+
+    #[test]
+    fn read_corruption_near_the_start() {
+        let mut actual = oam();
+        corrupt(&mut actual, 2, BusAccess::Read);
+
+        let (a, b, c) = (0x1110, 0x0908, 0x0D0C);
+        let mut expected = oam();
+        expected[16..18].copy_from_slice(&u16::to_le_bytes(b | (a & c)));
+        expected[18..24].copy_from_slice(&oam()[10..16]);
+        assert_eq!(actual, expected);
+    }
+}
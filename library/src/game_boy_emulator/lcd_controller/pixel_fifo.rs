@@ -0,0 +1,673 @@
+// Copyright 2026 Remi Bernotavicius
+
+//! This module contains a renderer which draws a line one dot at a time, the way the PPU does.
+//!
+//! During mode 3 a fetcher reads tiles from the background display data and character data and
+//! pushes them eight pixels at a time into a FIFO. Every dot one pixel is shifted out of the FIFO
+//! and onto the screen. When the screen position reaches an object, the fetcher is paused while the
+//! object's pixels are fetched and mixed into a second FIFO for objects.
+//!
+//! Because registers are read as the line is drawn, changes made in the middle of a line show up
+//! on the screen. The length of mode 3 also depends on what is being drawn: fine scrolling with
+//! SCX, starting the window and fetching objects all pause the pixel output.
+
+use super::{
+    LcdColor, LcdControlFlag, LcdController, LcdObjectAttributeFlag, LcdShade, CHARACTER_AREA_SIZE,
+    CHARACTER_SIZE, LINE_SPRITE_LIMIT, SCREEN_WIDTH,
+};
+use crate::game_boy_emulator::memory_controller::GameBoyFlags;
+use crate::rendering::Renderer;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use serde_derive::{Deserialize, Serialize};
+
+/// The first tile the fetcher gets on a line is thrown away.
+const WARM_UP_DOTS: u8 = 6;
+
+/// How long the fetcher is paused to fetch an object's pixels, once it is done with its tile.
+const OBJECT_FETCH_DOTS: u8 = 6;
+
+/// An object fetch has to wait for the fetcher to get this far into fetching its tile.
+const OBJECT_FETCH_WAIT_DOTS: u8 = 4;
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum FetcherStep {
+    #[default]
+    TileCode,
+    DataLow,
+    DataHigh,
+    Push,
+}
+
+/// Fetches a row of eight pixels of a tile. Getting the character code and the two bytes of dot
+/// data takes two dots each, then it waits until the FIFO is empty to push the pixels.
+#[derive(Default, Serialize, Deserialize)]
+struct Fetcher {
+    step: FetcherStep,
+    /// True when the first of the two dots of the current step has passed.
+    half_done: bool,
+    /// Dots spent since the last time pixels were pushed.
+    dots_since_push: u8,
+    /// The tile column relative to the start of the background or window.
+    tile_x: u8,
+    character_code: u8,
+    data_low: u8,
+    data_high: u8,
+}
+
+/// An object found on the current line while scanning OAM.
+#[derive(Serialize, Deserialize)]
+struct LineObject {
+    y: u8,
+    x: u8,
+    character_code: u8,
+    flags: u8,
+    fetched: bool,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct ObjectPixel {
+    color: u8,
+    palette: bool,
+    behind_bg: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ObjectFetch {
+    index: usize,
+    dots: u8,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub(super) struct PixelFifo {
+    ly: u8,
+    /// The number of dots since mode 3 started.
+    dots: u16,
+    /// The screen position of the next pixel to come out.
+    x: u8,
+    warm_up: u8,
+    /// Pixels still to be thrown away because of fine scrolling with SCX.
+    discard: u8,
+    fetching_window: bool,
+    /// Whether the window showed up at all on this line.
+    window_drawn: bool,
+    fetcher: Fetcher,
+    background: VecDeque<u8>,
+    objects: VecDeque<ObjectPixel>,
+    line_objects: Vec<LineObject>,
+    object_fetch: Option<ObjectFetch>,
+}
+
+fn shade_for_color(palette: &GameBoyFlags<LcdColor>, color: u8) -> LcdShade {
+    let color = match color {
+        0x0 => LcdColor::Color0,
+        0x1 => LcdColor::Color1,
+        0x2 => LcdColor::Color2,
+        0x3 => LcdColor::Color3,
+        _ => panic!(),
+    };
+    match palette.read_flag_value(color) {
+        0x0 => LcdShade::Shade0,
+        0x1 => LcdShade::Shade1,
+        0x2 => LcdShade::Shade2,
+        0x3 => LcdShade::Shade3,
+        _ => panic!(),
+    }
+}
+
+fn pixel_color(data_low: u8, data_high: u8, bit: u8) -> u8 {
+    (((data_high >> bit) & 0x1) << 1) | ((data_low >> bit) & 0x1)
+}
+
+impl PixelFifo {
+    /// Gets ready to draw the line LY, finding the objects on it.
+    pub(super) fn start(&mut self, lcd: &LcdController) {
+        let ly = lcd.registers.ly.read_value();
+        self.ly = ly;
+        self.dots = 0;
+        self.x = 0;
+        self.warm_up = WARM_UP_DOTS;
+        self.discard = lcd.registers.scx.read_value() % CHARACTER_SIZE as u8;
+        self.fetching_window = false;
+        self.window_drawn = false;
+        self.fetcher = Fetcher::default();
+        self.background.clear();
+        self.objects.clear();
+        self.object_fetch = None;
+
+        let height = if lcd
+            .registers
+            .lcdc
+            .read_flag(LcdControlFlag::ObjectBlockCompositionSelection)
+        {
+            CHARACTER_SIZE * 2
+        } else {
+            CHARACTER_SIZE
+        };
+        self.line_objects.clear();
+        for entry in lcd.oam_data.as_slice().chunks(4) {
+            let top = entry[0] as i32 - CHARACTER_SIZE * 2;
+            if (top..top + height).contains(&(ly as i32)) {
+                self.line_objects.push(LineObject {
+                    y: entry[0],
+                    x: entry[1],
+                    character_code: entry[2],
+                    flags: entry[3],
+                    fetched: false,
+                });
+                if self.line_objects.len() == LINE_SPRITE_LIMIT {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub(super) fn window_drawn(&self) -> bool {
+        self.window_drawn
+    }
+
+    /// The number of dots spent drawing the line so far.
+    pub(super) fn dots(&self) -> u16 {
+        self.dots
+    }
+
+    /// Runs for one dot, returns true when the line is finished.
+    pub(super) fn dot(&mut self, lcd: &LcdController, renderer: &mut impl Renderer) -> bool {
+        self.dots += 1;
+        if self.warm_up > 0 {
+            self.warm_up -= 1;
+            return false;
+        }
+
+        if self.object_fetch.is_none() && self.discard == 0 {
+            self.object_fetch = self
+                .next_object(lcd)
+                .map(|index| ObjectFetch { index, dots: 0 });
+        }
+
+        let fetcher_busy =
+            self.fetcher.dots_since_push < OBJECT_FETCH_WAIT_DOTS || self.background.is_empty();
+        if let Some(fetch) = &mut self.object_fetch {
+            // The fetcher gets to finish the tile it is working on first.
+            if fetcher_busy {
+                self.step_fetcher(lcd);
+                return false;
+            }
+
+            fetch.dots += 1;
+            if fetch.dots == OBJECT_FETCH_DOTS {
+                let index = fetch.index;
+                self.object_fetch = None;
+                self.fetch_object(lcd, index);
+            }
+            return false;
+        }
+
+        if self.discard == 0 {
+            if let Some(start_x) = self.window_starts(lcd) {
+                self.fetching_window = true;
+                self.window_drawn = true;
+                self.fetcher = Fetcher::default();
+                self.background.clear();
+                // With WX below 7 the start of the window is off the left edge of the screen.
+                if self.x == 0 {
+                    self.discard = (-start_x).max(0) as u8;
+                }
+            }
+        }
+
+        self.step_fetcher(lcd);
+
+        let Some(color) = self.background.pop_front() else {
+            return false;
+        };
+        if self.discard > 0 {
+            self.discard -= 1;
+            return false;
+        }
+
+        let object = self.objects.pop_front();
+        let shade = self.mix(lcd, color, object);
+        renderer.color_pixel(
+            self.x as i32,
+            self.ly as i32,
+            lcd.palette.color_for_shade(shade),
+        );
+        self.x += 1;
+
+        self.x as i32 == SCREEN_WIDTH
+    }
+
+    /// Returns where the window starts if it starts at the current position.
+    fn window_starts(&self, lcd: &LcdController) -> Option<i32> {
+        if self.fetching_window {
+            return None;
+        }
+        lcd.window_start_x()
+            .filter(|&start_x| self.x as i32 >= start_x)
+    }
+
+    /// Finds the next object which starts at the current position. When several do, the one
+    /// furthest to the left is drawn on top, then the one first in OAM.
+    fn next_object(&self, lcd: &LcdController) -> Option<usize> {
+        if !lcd.registers.lcdc.read_flag(LcdControlFlag::ObjectOn) {
+            return None;
+        }
+        self.line_objects
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| !o.fetched && o.x <= self.x + CHARACTER_SIZE as u8)
+            .min_by_key(|&(i, o)| (o.x, i))
+            .map(|(i, _)| i)
+    }
+
+    fn step_fetcher(&mut self, lcd: &LcdController) {
+        let fetcher = &mut self.fetcher;
+        if fetcher.step == FetcherStep::Push {
+            fetcher.dots_since_push = fetcher.dots_since_push.saturating_add(1);
+            if self.background.is_empty() {
+                self.background.extend(
+                    (0..CHARACTER_SIZE as u8)
+                        .rev()
+                        .map(|bit| pixel_color(fetcher.data_low, fetcher.data_high, bit)),
+                );
+                fetcher.tile_x = fetcher.tile_x.wrapping_add(1);
+                fetcher.step = FetcherStep::TileCode;
+                fetcher.dots_since_push = 0;
+            }
+            return;
+        }
+
+        fetcher.dots_since_push = fetcher.dots_since_push.saturating_add(1);
+
+        if !fetcher.half_done {
+            fetcher.half_done = true;
+            return;
+        }
+        fetcher.half_done = false;
+
+        match fetcher.step {
+            FetcherStep::TileCode => {
+                self.fetcher.character_code = self.read_character_code(lcd);
+                self.fetcher.step = FetcherStep::DataLow;
+            }
+            FetcherStep::DataLow => {
+                self.fetcher.data_low = self.read_tile_data(lcd, 0);
+                self.fetcher.step = FetcherStep::DataHigh;
+            }
+            FetcherStep::DataHigh => {
+                self.fetcher.data_high = self.read_tile_data(lcd, 1);
+                self.fetcher.step = FetcherStep::Push;
+            }
+            FetcherStep::Push => unreachable!(),
+        }
+    }
+
+    /// The line within the background or window the fetcher is reading from.
+    fn fetcher_y(&self, lcd: &LcdController) -> u8 {
+        if self.fetching_window {
+            lcd.window_line
+        } else {
+            self.ly.wrapping_add(lcd.registers.scy.read_value())
+        }
+    }
+
+    fn read_character_code(&self, lcd: &LcdController) -> u8 {
+        let lcdc = &lcd.registers.lcdc;
+        let (area_selection, tile_x) = if self.fetching_window {
+            (
+                lcdc.read_flag(LcdControlFlag::WindowCodeAreaSelection),
+                self.fetcher.tile_x,
+            )
+        } else {
+            let scroll_tile_x = lcd.registers.scx.read_value() / CHARACTER_SIZE as u8;
+            (
+                lcdc.read_flag(LcdControlFlag::BGCodeAreaSelection),
+                scroll_tile_x.wrapping_add(self.fetcher.tile_x),
+            )
+        };
+        let area = match area_selection {
+            false => &lcd.background_display_data_1,
+            true => &lcd.background_display_data_2,
+        };
+
+        let tile_x = (tile_x % CHARACTER_AREA_SIZE as u8) as usize;
+        let tile_y = (self.fetcher_y(lcd) / CHARACTER_SIZE as u8) as usize;
+        area.as_slice()[tile_y * CHARACTER_AREA_SIZE as usize + tile_x]
+    }
+
+    fn read_tile_data(&self, lcd: &LcdController, byte: usize) -> u8 {
+        let character_data_selection = lcd
+            .registers
+            .lcdc
+            .read_flag(LcdControlFlag::BGCharacterDataSelection);
+        let dot_data = LcdController::read_dot_data(
+            &lcd.character_data,
+            character_data_selection,
+            self.fetcher.character_code,
+        );
+        let row = (self.fetcher_y(lcd) % CHARACTER_SIZE as u8) as usize;
+        dot_data.data[row * 2 + byte]
+    }
+
+    /// Reads the object's row of pixels and mixes them in with the ones already waiting to be
+    /// drawn. Objects fetched earlier are on top, so only transparent pixels get replaced.
+    fn fetch_object(&mut self, lcd: &LcdController, index: usize) {
+        let object = &mut self.line_objects[index];
+        object.fetched = true;
+
+        let mut flags = GameBoyFlags::<LcdObjectAttributeFlag>::new();
+        flags.set_value(object.flags);
+
+        let tall = lcd
+            .registers
+            .lcdc
+            .read_flag(LcdControlFlag::ObjectBlockCompositionSelection);
+        let height = if tall {
+            CHARACTER_SIZE * 2
+        } else {
+            CHARACTER_SIZE
+        };
+        let mut row = self.ly as i32 - (object.y as i32 - CHARACTER_SIZE * 2);
+        if flags.read_flag(LcdObjectAttributeFlag::VerticalFlip) {
+            row = height - 1 - row;
+        }
+        let character_code = if tall {
+            (object.character_code & !1) | (row >= CHARACTER_SIZE) as u8
+        } else {
+            object.character_code
+        };
+        let row = (row % CHARACTER_SIZE) as usize;
+
+        let dot_data = LcdController::read_dot_data(&lcd.character_data, true, character_code);
+        let data_low = dot_data.data[row * 2];
+        let data_high = dot_data.data[row * 2 + 1];
+
+        // Objects hanging off the left side of the screen are cut off.
+        let hidden = (self.x + CHARACTER_SIZE as u8 - object.x) as usize;
+        let horizontal_flip = flags.read_flag(LcdObjectAttributeFlag::HorizontalFlip);
+        for i in hidden..CHARACTER_SIZE as usize {
+            let bit = (if horizontal_flip { i } else { 7 - i }) as u8;
+            let pixel = ObjectPixel {
+                color: pixel_color(data_low, data_high, bit),
+                palette: flags.read_flag(LcdObjectAttributeFlag::Palette),
+                behind_bg: flags.read_flag(LcdObjectAttributeFlag::DisplayPriority),
+            };
+            match self.objects.get_mut(i - hidden) {
+                Some(existing) if existing.color == 0 => *existing = pixel,
+                Some(_) => {}
+                None => self.objects.push_back(pixel),
+            }
+        }
+    }
+
+    fn mix(&self, lcd: &LcdController, color: u8, object: Option<ObjectPixel>) -> LcdShade {
+        let registers = &lcd.registers;
+        let bg_on = registers.lcdc.read_flag(LcdControlFlag::BGDisplayOn);
+        let color = if bg_on { color } else { 0 };
+
+        match object {
+            Some(o)
+                if o.color != 0
+                    && registers.lcdc.read_flag(LcdControlFlag::ObjectOn)
+                    && (!o.behind_bg || color == 0) =>
+            {
+                let palette = match o.palette {
+                    false => &registers.obp0,
+                    true => &registers.obp1,
+                };
+                shade_for_color(palette, o.color)
+            }
+            // With the background off it is just blank.
+            _ if !bg_on => LcdShade::Shade0,
+            _ => shade_for_color(&registers.bgp, color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::lcd_controller::{LcdControllerEvent, LcdStatusFlag, PpuMode};
+    use crate::game_boy_emulator::{GameBoyEmulatorEvent, GameBoyScheduler, InterruptFlag};
+    use crate::io;
+    use crate::rendering::{Color, Event};
+    use alloc::{vec, vec::Vec};
+
+    /// Keeps the colors drawn to the first line of the screen.
+    struct LineRenderer {
+        line: Vec<Color>,
+    }
+
+    impl Renderer for LineRenderer {
+        fn poll_events(&mut self) -> Vec<Event> {
+            vec![]
+        }
+
+        fn save_buffer(&self, _: impl io::Write) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn color_pixel(&mut self, x: i32, y: i32, color: Color) {
+            if y == 0 {
+                self.line[x as usize] = color;
+            }
+        }
+
+        fn present(&mut self) {}
+    }
+
+    struct TestLcd {
+        lcd: LcdController,
+        scheduler: GameBoyScheduler,
+        interrupt_flag: GameBoyFlags<InterruptFlag>,
+        renderer: LineRenderer,
+        mode_3_start: Option<u64>,
+    }
+
+    impl TestLcd {
+        /// The LCD is on showing the background, with the first 32 tiles of character data at
+        /// 0x8000 filled with the given row of dot data.
+        fn new(row: [u8; 2]) -> Self {
+            let mut lcd = LcdController::new();
+            lcd.registers.lcdc.set_value(0x93);
+            lcd.registers.bgp.set_value(0xE4);
+            lcd.registers.obp0.set_value(0xE4);
+            let mut character_data = vec![0; 0x1800];
+            for tile in character_data.chunks_mut(16).take(32) {
+                for pair in tile.chunks_mut(2) {
+                    pair.copy_from_slice(&row);
+                }
+            }
+            lcd.character_data.clone_from_slice(&character_data);
+
+            let mut scheduler = GameBoyScheduler::new();
+            lcd.schedule_initial_events(&mut scheduler, 0);
+            Self {
+                lcd,
+                scheduler,
+                interrupt_flag: GameBoyFlags::new(),
+                renderer: LineRenderer {
+                    line: vec![Color::new(0, 0, 0); SCREEN_WIDTH as usize],
+                },
+                mode_3_start: None,
+            }
+        }
+
+        fn place_object(&mut self, index: usize, x: u8, character_code: u8, flags: u8) {
+            let entry = [16, x, character_code, flags];
+            let mut oam = self.lcd.oam_data.as_slice().to_vec();
+            oam[index * 4..index * 4 + 4].copy_from_slice(&entry);
+            self.lcd.oam_data.clone_from_slice(&oam);
+        }
+
+        /// Delivers events up to the given time, returns the time mode 0 started if it did.
+        fn run_until(&mut self, now: u64) -> Option<u64> {
+            while let Some((time, event)) = self.scheduler.poll(now) {
+                let GameBoyEmulatorEvent::Lcd(event) = event else {
+                    continue;
+                };
+                let mode_0 = matches!(event, LcdControllerEvent::Mode0);
+                event.deliver(
+                    &mut self.lcd,
+                    &mut self.renderer,
+                    PpuMode::PixelFifo,
+                    &mut self.interrupt_flag,
+                    &mut self.scheduler,
+                    time,
+                );
+                if self.mode_3_start.is_none()
+                    && self.lcd.registers.stat.read_flag_value(LcdStatusFlag::Mode) == 0x3
+                {
+                    self.mode_3_start = Some(time);
+                }
+                if mode_0 {
+                    return Some(time);
+                }
+            }
+            None
+        }
+
+        /// Draws the first line, returning how long mode 3 took.
+        fn draw_line(&mut self) -> u64 {
+            let mut now = self.scheduler.now();
+            loop {
+                if let Some(mode_0) = self.run_until(now) {
+                    return mode_0 - self.mode_3_start.unwrap();
+                }
+                now += 1;
+            }
+        }
+
+        fn shade(&self, x: usize) -> LcdShade {
+            let palette = &self.lcd.palette;
+            [
+                LcdShade::Shade0,
+                LcdShade::Shade1,
+                LcdShade::Shade2,
+                LcdShade::Shade3,
+            ]
+            .into_iter()
+            .find(|&s| palette.color_for_shade(s) == self.renderer.line[x])
+            .unwrap()
+        }
+    }
+
+    #[test]
+    fn mode_3_length_plain() {
+        let mut lcd = TestLcd::new([0xFF, 0x00]);
+        assert_eq!(lcd.draw_line(), 172);
+        assert!((0..160).all(|x| lcd.shade(x) == LcdShade::Shade1));
+    }
+
+    #[test]
+    fn mode_3_length_fine_scroll() {
+        for scx in 0..8 {
+            let mut lcd = TestLcd::new([0xFF, 0x00]);
+            lcd.lcd.registers.scx.set_value(scx);
+            assert_eq!(lcd.draw_line(), 172 + scx as u64);
+        }
+    }
+
+    #[test]
+    fn mode_3_length_window() {
+        let mut lcd = TestLcd::new([0x00, 0x00]);
+        lcd.lcd.registers.lcdc.set_value(0xF3);
+        lcd.lcd.registers.wx.set_value(7 + 80);
+        lcd.lcd.registers.wy.set_value(0);
+        // The window uses the second background display data, point it at tile 1.
+        lcd.lcd
+            .background_display_data_2
+            .clone_from_slice(&[1; 0x400]);
+        let mut character_data = lcd.lcd.character_data.as_slice().to_vec();
+        character_data[16..32].copy_from_slice(&[0xFF; 16]);
+        lcd.lcd.character_data.clone_from_slice(&character_data);
+
+        assert_eq!(lcd.draw_line(), 172 + 6);
+        assert_eq!(lcd.shade(79), LcdShade::Shade0);
+        assert_eq!(lcd.shade(80), LcdShade::Shade3);
+        assert_eq!(lcd.shade(159), LcdShade::Shade3);
+    }
+
+    #[test]
+    fn mode_3_length_objects() {
+        // How long an object pauses the fetcher depends on where it lands in the background tile.
+        for (x, penalty) in [(8, 11), (9, 10), (12, 7), (13, 6), (15, 6), (88, 11)] {
+            let mut lcd = TestLcd::new([0x00, 0x00]);
+            lcd.place_object(0, x, 0, 0);
+            assert_eq!(lcd.draw_line(), 172 + penalty, "object at x = {x}");
+        }
+
+        // Objects which are off the screen still cost time.
+        let mut lcd = TestLcd::new([0x00, 0x00]);
+        lcd.place_object(0, 0, 0, 0);
+        assert_eq!(lcd.draw_line(), 172 + 11);
+
+        let mut lcd = TestLcd::new([0x00, 0x00]);
+        lcd.place_object(0, 50, 0, 0);
+        lcd.place_object(1, 50, 0, 0);
+        assert_eq!(lcd.draw_line(), 172 + 9 + 6);
+    }
+
+    #[test]
+    fn object_priority() {
+        let mut lcd = TestLcd::new([0x00, 0x00]);
+        let mut character_data = lcd.lcd.character_data.as_slice().to_vec();
+        // Tile 1 is color 1 on the left half, tile 2 is color 2 all the way across.
+        character_data[16..32].copy_from_slice(&[0xF0, 0x00].repeat(8));
+        character_data[32..48].copy_from_slice(&[0x00, 0xFF].repeat(8));
+        lcd.lcd.character_data.clone_from_slice(&character_data);
+        lcd.place_object(0, 24, 2, 0);
+        lcd.place_object(1, 20, 1, 0);
+
+        lcd.draw_line();
+        // The object further left is on top, except where it is transparent.
+        assert_eq!(lcd.shade(11), LcdShade::Shade0);
+        assert_eq!(lcd.shade(12), LcdShade::Shade1);
+        assert_eq!(lcd.shade(15), LcdShade::Shade1);
+        assert_eq!(lcd.shade(16), LcdShade::Shade2);
+        assert_eq!(lcd.shade(23), LcdShade::Shade2);
+        assert_eq!(lcd.shade(24), LcdShade::Shade0);
+    }
+
+    #[test]
+    fn mid_line_palette_change() {
+        let mut lcd = TestLcd::new([0xFF, 0x00]);
+        // Wait until part of the line has been drawn, then change the background palette.
+        lcd.run_until(56 + 77 + 100);
+        lcd.lcd.registers.bgp.set_value(0xE4 ^ 0x0C);
+        lcd.draw_line();
+
+        // The dots up to the time of the change are drawn with the old palette. The first 12 of
+        // them are spent getting the first tile.
+        let changed = (0..160)
+            .find(|&x| lcd.shade(x) != LcdShade::Shade1)
+            .unwrap();
+        assert_eq!(changed, 104 - 12);
+        assert!((changed..160).all(|x| lcd.shade(x) == LcdShade::Shade2));
+    }
+
+    #[test]
+    fn mid_line_scroll_change() {
+        let mut lcd = TestLcd::new([0x00, 0x00]);
+        // Tile 1 is color 3, and it is only in the second column of the background.
+        let mut character_data = lcd.lcd.character_data.as_slice().to_vec();
+        character_data[16..32].copy_from_slice(&[0xFF; 16]);
+        lcd.lcd.character_data.clone_from_slice(&character_data);
+        let mut map = vec![0; 0x400];
+        map[1] = 1;
+        lcd.lcd.background_display_data_1.clone_from_slice(&map);
+
+        lcd.run_until(56 + 77 + 100);
+        lcd.lcd.registers.scx.set_value(0xA0);
+        lcd.draw_line();
+
+        assert_eq!(lcd.shade(8), LcdShade::Shade3);
+        assert_eq!(lcd.shade(16), LcdShade::Shade0);
+        // Tiles fetched after the change come from 20 columns further along, so the second
+        // column comes around again for the 13th tile.
+        assert_eq!(lcd.shade(103), LcdShade::Shade0);
+        assert!((104..112).all(|x| lcd.shade(x) == LcdShade::Shade3));
+        assert_eq!(lcd.shade(112), LcdShade::Shade0);
+    }
+}
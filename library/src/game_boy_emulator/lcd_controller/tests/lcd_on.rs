@@ -0,0 +1,126 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::ScreenshotRenderer;
+use crate::game_boy_emulator::lcd_controller::{
+    LcdController, LcdShade, LcdStatusFlag, Palette, PpuMode,
+};
+use crate::game_boy_emulator::memory_controller::GameBoyFlags;
+use crate::game_boy_emulator::{
+    assemble, GameBoyEmulator, GameBoyEmulatorEvent, GameBoyOps, GameBoyScheduler, GamePak,
+};
+use crate::rendering::NullRenderer;
+use crate::sound::NullSoundStream;
+use crate::storage::PanicStorage;
+use alloc::vec::Vec;
+
+/// Delivers the LCD controller's events up to the given time, returning when each one happened
+/// along with what LY and the mode were after it.
+fn run_lcd_until(
+    lcd: &mut LcdController,
+    scheduler: &mut GameBoyScheduler,
+    now: u64,
+) -> Vec<(u64, u8, u8)> {
+    let mut interrupt_flag = GameBoyFlags::new();
+    let mut changes = Vec::new();
+    while let Some((time, event)) = scheduler.poll(now) {
+        let GameBoyEmulatorEvent::Lcd(event) = event else {
+            continue;
+        };
+        event.deliver(
+            lcd,
+            &mut NullRenderer,
+            PpuMode::PixelFifo,
+            &mut interrupt_flag,
+            scheduler,
+            time,
+        );
+        let ly = lcd.registers.ly.read_value();
+        let mode = lcd.registers.stat.read_flag_value(LcdStatusFlag::Mode);
+        changes.push((time, ly, mode));
+    }
+    changes
+}
+
+#[test]
+fn lcd_off_and_on() {
+    let mut lcd = LcdController::new();
+    lcd.registers.lcdc.set_value(0x91);
+    let mut scheduler = GameBoyScheduler::new();
+    lcd.schedule_initial_events(&mut scheduler, 0);
+    run_lcd_until(&mut lcd, &mut scheduler, 10_000);
+    assert_ne!(lcd.registers.ly.read_value(), 0);
+
+    // Turning the LCD off puts LY back to 0 and STAT into mode 0, and nothing happens after that.
+    lcd.set_lcdc(&mut scheduler, 0x11);
+    assert_eq!(lcd.registers.ly.read_value(), 0);
+    assert_eq!(lcd.registers.stat.read_flag_value(LcdStatusFlag::Mode), 0x0);
+    assert_eq!(run_lcd_until(&mut lcd, &mut scheduler, 20_000), []);
+
+    // The first line skips mode 2 and is 4 dots shorter than usual.
+    lcd.set_lcdc(&mut scheduler, 0x91);
+    let changes = run_lcd_until(&mut lcd, &mut scheduler, 20_456);
+    let first = |f: fn(u8, u8) -> bool| {
+        changes
+            .iter()
+            .find(|&&(_, ly, mode)| f(ly, mode))
+            .map(|&(time, _, _)| time)
+    };
+    assert_eq!(first(|_, mode| mode == 0x3), Some(20_000 + 73));
+    assert_eq!(first(|_, mode| mode == 0x2), Some(20_000 + 452));
+    assert_eq!(first(|ly, _| ly == 1), Some(20_000 + 452));
+}
+
+/// The LCD doesn't show anything for the first frame after it is turned on.
+#[test]
+fn first_frame_after_lcd_on_is_blank() {
+    let rom = assemble(
+        "
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+        xor  a
+        ldh  [$FF40],a
+        ld   hl,$8000
+        ld   a,$FF
+        ld   b,$10
+    .tile
+        ldi  [hl],a
+        dec  b
+        jr   nz,.tile
+        ld   hl,$9800
+        ld   bc,$0400
+    .background
+        xor  a
+        ldi  [hl],a
+        dec  bc
+        ld   a,b
+        or   c
+        jr   nz,.background
+        ld   a,$E4
+        ldh  [$FF47],a
+        ld   a,$91
+        ldh  [$FF40],a
+    .done
+        jr   .done
+    ",
+    )
+    .unwrap()
+    .bin;
+    let mut ops = GameBoyOps::new(ScreenshotRenderer::new(), NullSoundStream, PanicStorage);
+    let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+    ops.load_game_pak(game_pak);
+
+    let mut e = GameBoyEmulator::new();
+    while ops.renderer.frames.len() < 2 {
+        e.tick(&mut ops);
+    }
+
+    // The LCD is turned off before the first frame is finished, so the first frame presented is
+    // the one after turning it back on.
+    let palette = Palette::default();
+    let frames = &ops.renderer.frames;
+    let blank = palette.color_for_shade(LcdShade::Shade0);
+    assert!(frames[0].iter().all(|&c| c == blank));
+    let dark = palette.color_for_shade(LcdShade::Shade3);
+    assert!(frames[1].iter().all(|&c| c == dark));
+}
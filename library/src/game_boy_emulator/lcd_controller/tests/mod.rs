@@ -1,4 +1,46 @@
 // Copyright 2021 Remi Bernotavicius
 
+use super::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::io;
+use crate::rendering::{Color, Event, Renderer};
+use alloc::{vec, vec::Vec};
+
 mod blargg;
+mod lcd_on;
 mod mooneye;
+mod oam_bug;
+mod stat;
+mod window;
+
+/// Keeps a screenshot of every frame presented.
+struct ScreenshotRenderer {
+    screen: Vec<Color>,
+    frames: Vec<Vec<Color>>,
+}
+
+impl ScreenshotRenderer {
+    fn new() -> Self {
+        Self {
+            screen: vec![Color::new(0, 0, 0); (SCREEN_WIDTH * SCREEN_HEIGHT) as usize],
+            frames: vec![],
+        }
+    }
+}
+
+impl Renderer for ScreenshotRenderer {
+    fn poll_events(&mut self) -> Vec<Event> {
+        vec![]
+    }
+
+    fn save_buffer(&self, _: impl io::Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn color_pixel(&mut self, x: i32, y: i32, color: Color) {
+        self.screen[(y * SCREEN_WIDTH + x) as usize] = color;
+    }
+
+    fn present(&mut self) {
+        self.frames.push(self.screen.clone());
+    }
+}
@@ -28,3 +28,25 @@ fn mooneye_test_rom_acceptance_oam_dma_reg_read() {
 fn mooneye_test_rom_acceptance_oam_dma_sources_gs() {
     run_mooneye_test_rom("acceptance/oam_dma/sources-GS.gb");
 }
+
+/// Checks that a STAT interrupt source becoming true while another enabled source already holds
+/// the STAT line high doesn't request another interrupt
+#[test]
+#[ignore]
+fn mooneye_test_rom_acceptance_ppu_stat_irq_blocking() {
+    run_mooneye_test_rom("acceptance/ppu/stat_irq_blocking.gb");
+}
+
+/// Checks when the registers and memory change after the LCD is turned on
+#[test]
+#[ignore]
+fn mooneye_test_rom_acceptance_ppu_lcdon_timing_gs() {
+    run_mooneye_test_rom("acceptance/ppu/lcdon_timing-GS.gb");
+}
+
+/// Checks when OAM and VRAM become locked after the LCD is turned on
+#[test]
+#[ignore]
+fn mooneye_test_rom_acceptance_ppu_lcdon_write_timing_gs() {
+    run_mooneye_test_rom("acceptance/ppu/lcdon_write_timing-GS.gb");
+}
@@ -0,0 +1,89 @@
+// Copyright 2026 Remi Bernotavicius
+
+use crate::game_boy_emulator::{assemble, GameBoyEmulator, GamePak, NullGameBoyOps};
+use alloc::{format, vec::Vec};
+
+/// Fills OAM with a pattern, then runs the given instruction on the first line after the STAT
+/// mode matches the given one, and returns what OAM looks like afterwards.
+fn oam_after(setup: &str, instruction: &str, mode: u8) -> Vec<u8> {
+    let program = format!(
+        "
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+        xor  a
+        ldh  [$FF40],a
+        ld   hl,$FE00
+        ld   b,$A0
+        ld   a,$01
+    .fill
+        ldi  [hl],a
+        inc  a
+        dec  b
+        jr   nz,.fill
+        ld   a,$81
+        ldh  [$FF40],a
+    .line_1
+        ldh  a,[$FF44]
+        cp   a,$01
+        jr   nz,.line_1
+    .hblank
+        ldh  a,[$FF41]
+        and  a,$03
+        jr   nz,.hblank
+        {setup}
+    .mode
+        ldh  a,[$FF41]
+        and  a,$03
+        cp   a,${mode:02X}
+        jr   nz,.mode
+        {instruction}
+    .done
+        jr   .done
+    "
+    );
+    let rom = assemble(&program).unwrap().bin;
+    let mut ops = NullGameBoyOps::null();
+    let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+    ops.load_game_pak(game_pak);
+
+    let mut e = GameBoyEmulator::new();
+    let end = e.elapsed_cycles() + 100_000;
+    while e.elapsed_cycles() < end {
+        e.tick(&mut ops);
+    }
+    e.bridge.lcd_controller.oam_data.as_slice().to_vec()
+}
+
+fn pattern() -> Vec<u8> {
+    (0x01..=0xA0).collect()
+}
+
+#[test]
+fn inc_in_oam_during_mode_2_corrupts() {
+    assert_ne!(oam_after("ld hl,$FE80", "inc hl", 0x2), pattern());
+    assert_ne!(oam_after("ld de,$FE80", "dec de", 0x2), pattern());
+}
+
+#[test]
+fn push_and_pop_in_oam_during_mode_2_corrupt() {
+    assert_ne!(oam_after("ld sp,$FE80", "push bc", 0x2), pattern());
+    assert_ne!(oam_after("ld sp,$FE80", "pop bc", 0x2), pattern());
+}
+
+#[test]
+fn ldi_in_oam_during_mode_2_corrupts() {
+    assert_ne!(oam_after("ld hl,$FE80", "ldi a,[hl]", 0x2), pattern());
+    assert_ne!(oam_after("ld hl,$FE80", "ldd [hl],a", 0x2), pattern());
+}
+
+#[test]
+fn inc_outside_oam_does_not_corrupt() {
+    assert_eq!(oam_after("ld hl,$C000", "inc hl", 0x2), pattern());
+}
+
+#[test]
+fn inc_in_oam_outside_mode_2_does_not_corrupt() {
+    assert_eq!(oam_after("ld hl,$FE80", "inc hl", 0x0), pattern());
+    assert_eq!(oam_after("ld hl,$FE80", "inc hl", 0x3), pattern());
+}
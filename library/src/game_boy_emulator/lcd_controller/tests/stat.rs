@@ -0,0 +1,153 @@
+// Copyright 2026 Remi Bernotavicius
+
+use crate::game_boy_emulator::{
+    assemble, GameBoyEmulator, GamePak, MemoryAccessor as _, NullGameBoyOps,
+};
+
+fn game_boy(program: &str) -> (GameBoyEmulator, NullGameBoyOps) {
+    let rom = assemble(program).unwrap().bin;
+    let mut ops = NullGameBoyOps::null();
+    let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+    ops.load_game_pak(game_pak);
+    (GameBoyEmulator::new(), ops)
+}
+
+fn run_for(e: &mut GameBoyEmulator, ops: &mut NullGameBoyOps, cycles: u64) {
+    let end = e.elapsed_cycles() + cycles;
+    while e.elapsed_cycles() < end {
+        e.tick(ops);
+    }
+}
+
+fn read_memory(e: &GameBoyEmulator, ops: &NullGameBoyOps, address: u16) -> u8 {
+    ops.memory_map(&e.bridge).read_memory(address)
+}
+
+/// With both the mode 0 and mode 2 interrupts enabled, the line stays high going from mode 0 to
+/// mode 2, so only the first mode 2 of the frame interrupts.
+#[test]
+fn stat_irq_blocking() {
+    let (mut e, mut ops) = game_boy(
+        "
+    SECTION stat,ROM0[$0048]
+        push af
+        ld   a,[$C000]
+        add  a,$01
+        ld   [$C000],a
+        ld   a,[$C001]
+        adc  a,$00
+        ld   [$C001],a
+        pop  af
+        reti
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+    .first_vblank
+        ldh  a,[$FF44]
+        cp   a,$90
+        jr   nz,.first_vblank
+        ld   a,$28
+        ldh  [$FF41],a
+        xor  a
+        ld   [$C000],a
+        ld   [$C001],a
+        ldh  [$FF0F],a
+        ld   a,$02
+        ldh  [$FFFF],a
+        ei
+    .leave_vblank
+        ldh  a,[$FF44]
+        cp   a,$90
+        jr   z,.leave_vblank
+    .next_vblank
+        ldh  a,[$FF44]
+        cp   a,$90
+        jr   nz,.next_vblank
+        di
+    .done
+        jr   .done
+    ",
+    );
+    run_for(&mut e, &mut ops, 200_000);
+
+    let count = u16::from_le_bytes([read_memory(&e, &ops, 0xC000), read_memory(&e, &ops, 0xC001)]);
+    assert_eq!(count, 1 + 144);
+}
+
+/// On the DMG writing to STAT during vertical blanking requests the interrupt, even with every
+/// source disabled.
+#[test]
+fn stat_write_bug() {
+    let (mut e, mut ops) = game_boy(
+        "
+    SECTION stat,ROM0[$0048]
+        ld   a,$42
+        ld   [$C000],a
+        reti
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+        xor  a
+        ld   [$C000],a
+    .vblank
+        ldh  a,[$FF44]
+        cp   a,$90
+        jr   nz,.vblank
+        xor  a
+        ldh  [$FF0F],a
+        ld   a,$02
+        ldh  [$FFFF],a
+        ei
+        xor  a
+        ldh  [$FF41],a
+        nop
+        nop
+        di
+    .done
+        jr   .done
+    ",
+    );
+    run_for(&mut e, &mut ops, 100_000);
+
+    assert_eq!(read_memory(&e, &ops, 0xC000), 0x42);
+}
+
+/// LY already reads 0 for most of line 153, so LYC = 0 matches while still in vertical blanking.
+#[test]
+fn lyc_0_matches_during_line_153() {
+    let (mut e, mut ops) = game_boy(
+        "
+    SECTION stat,ROM0[$0048]
+        ldh  a,[$FF41]
+        ld   [$C000],a
+        ldh  a,[$FF44]
+        ld   [$C001],a
+        reti
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+    .vblank
+        ldh  a,[$FF44]
+        cp   a,$90
+        jr   nz,.vblank
+        xor  a
+        ldh  [$FF45],a
+        ld   a,$40
+        ldh  [$FF41],a
+        xor  a
+        ldh  [$FF0F],a
+        ld   [$C000],a
+        ld   a,$FF
+        ld   [$C001],a
+        ld   a,$02
+        ldh  [$FFFF],a
+        ei
+    .done
+        jr   .done
+    ",
+    );
+    run_for(&mut e, &mut ops, 100_000);
+
+    assert_eq!(read_memory(&e, &ops, 0xC000) & 0x03, 0x01);
+    assert_eq!(read_memory(&e, &ops, 0xC001), 0x00);
+}
@@ -0,0 +1,212 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::ScreenshotRenderer;
+use crate::game_boy_emulator::lcd_controller::{
+    LcdShade, Palette, PpuMode, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use crate::game_boy_emulator::{assemble, GameBoyEmulator, GameBoyOps, GamePak};
+use crate::rendering::Color;
+use crate::sound::NullSoundStream;
+use crate::storage::PanicStorage;
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::Write as _;
+
+/// Builds a ROM which draws the window over a blank background. Every line of the window looks
+/// different, row `i` of window tile row `r` has `i` in its low bit plane and `r + 1` in its high
+/// one. Each frame the given registers are written once LY reaches the given lines.
+fn window_rom(wy: u8, wx: u8, writes: &[(u8, u8, u8)]) -> Vec<u8> {
+    let mut frame = String::new();
+    for (i, &(ly, register, value)) in writes.iter().enumerate() {
+        write!(
+            frame,
+            "
+    .wait_{i}
+        ldh  a,[$FF44]
+        cp   a,${ly:02X}
+        jr   nz,.wait_{i}
+        ld   a,${value:02X}
+        ldh  [$FF{register:02X}],a"
+        )
+        .unwrap();
+    }
+
+    let program = format!(
+        "
+    SECTION entry,ROM0[$0100]
+        jp   $0150
+    SECTION test,ROM0[$0150]
+        xor  a
+        ldh  [$FF40],a
+        ld   hl,$9000
+        ld   b,$10
+    .blank_tile
+        ldi  [hl],a
+        dec  b
+        jr   nz,.blank_tile
+        ld   d,$01
+    .tile
+        ld   e,$00
+    .tile_row
+        ld   a,e
+        ldi  [hl],a
+        ld   a,d
+        ldi  [hl],a
+        inc  e
+        ld   a,e
+        cp   a,$08
+        jr   nz,.tile_row
+        inc  d
+        ld   a,d
+        cp   a,$13
+        jr   nz,.tile
+        ld   hl,$9800
+        ld   bc,$0400
+    .background
+        xor  a
+        ldi  [hl],a
+        dec  bc
+        ld   a,b
+        or   c
+        jr   nz,.background
+        ld   d,$01
+    .window_row
+        ld   b,$20
+    .window_column
+        ld   a,d
+        ldi  [hl],a
+        dec  b
+        jr   nz,.window_column
+        inc  d
+        ld   a,d
+        cp   a,$13
+        jr   nz,.window_row
+        ld   a,$E4
+        ldh  [$FF47],a
+        ld   a,${wy:02X}
+        ldh  [$FF4A],a
+        ld   a,${wx:02X}
+        ldh  [$FF4B],a
+        ld   a,$E1
+        ldh  [$FF40],a
+    .frame
+        nop{frame}
+        jp   .frame
+    "
+    );
+    assemble(&program).unwrap().bin
+}
+
+/// Runs the ROM for a few frames and returns what the last one looked like.
+fn screenshot(rom: &[u8], ppu_mode: PpuMode) -> Vec<Color> {
+    let mut ops = GameBoyOps::new(ScreenshotRenderer::new(), NullSoundStream, PanicStorage);
+    ops.ppu_mode = ppu_mode;
+    let game_pak = GamePak::new(rom, &mut ops.storage, None).unwrap();
+    ops.load_game_pak(game_pak);
+
+    let mut e = GameBoyEmulator::new();
+    while ops.renderer.frames.len() < 4 {
+        e.tick(&mut ops);
+    }
+    ops.renderer.frames.pop().unwrap()
+}
+
+/// What the screen should look like given which line of the window shows on each line of the
+/// screen, and where it starts.
+fn expected_screenshot(window: impl Fn(u8) -> Option<(u8, i32)>) -> Vec<Color> {
+    let palette = Palette::default();
+    let shades = [
+        LcdShade::Shade0,
+        LcdShade::Shade1,
+        LcdShade::Shade2,
+        LcdShade::Shade3,
+    ];
+
+    let mut screen = vec![];
+    for y in 0..SCREEN_HEIGHT as u8 {
+        for x in 0..SCREEN_WIDTH {
+            let color = match window(y) {
+                Some((line, start_x)) if x >= start_x => {
+                    let (low, high) = (line % 8, line / 8 + 1);
+                    let bit = 7 - (x - start_x) % 8;
+                    (((high >> bit) & 0x1) << 1) | ((low >> bit) & 0x1)
+                }
+                _ => 0,
+            };
+            screen.push(palette.color_for_shade(shades[color as usize]));
+        }
+    }
+    screen
+}
+
+#[track_caller]
+fn assert_screenshot(rom: &[u8], window: impl Fn(u8) -> Option<(u8, i32)>) {
+    let expected = expected_screenshot(window);
+    for ppu_mode in [PpuMode::PixelFifo, PpuMode::Scanline] {
+        let actual = screenshot(rom, ppu_mode);
+        let lines = actual
+            .chunks(SCREEN_WIDTH as usize)
+            .zip(expected.chunks(SCREEN_WIDTH as usize));
+        for (y, (actual, expected)) in lines.enumerate() {
+            assert!(actual == expected, "line {y} is wrong with {ppu_mode:?}");
+        }
+    }
+}
+
+#[test]
+fn window_covers_screen() {
+    let rom = window_rom(0x00, 0x07, &[]);
+    assert_screenshot(&rom, |y| Some((y, 0)));
+}
+
+/// The window carries on from the line it got to when it is turned off for a while, or moved off
+/// the screen.
+#[test]
+fn window_line_counter() {
+    let rom = window_rom(
+        0x00,
+        0x07,
+        &[
+            (0x28, 0x40, 0xC1),
+            (0x31, 0x4B, 0xA7),
+            (0x32, 0x40, 0xE1),
+            (0x3C, 0x4B, 0x07),
+        ],
+    );
+    assert_screenshot(&rom, |y| match y {
+        0..40 => Some((y, 0)),
+        40..60 => None,
+        _ => Some((y - 20, 0)),
+    });
+}
+
+/// The window only shows up once LY has matched WY, after that moving WY doesn't matter.
+#[test]
+fn window_y_latch() {
+    let rom = window_rom(0x14, 0x07, &[(0x3C, 0x4A, 0x64), (0x90, 0x4A, 0x14)]);
+    assert_screenshot(&rom, |y| (y >= 20).then(|| (y - 20, 0)));
+
+    // LY is never equal to WY here, so the window never shows.
+    let rom = window_rom(0x32, 0x07, &[(0x1E, 0x4A, 0x0A), (0x90, 0x4A, 0x32)]);
+    assert_screenshot(&rom, |_| None);
+}
+
+/// With WX below 7 the window is partly off the left of the screen.
+#[test]
+fn window_x_below_7() {
+    for wx in 0..7 {
+        let rom = window_rom(0x00, wx, &[]);
+        assert_screenshot(&rom, |y| Some((y, wx as i32 - 7)));
+    }
+}
+
+/// With WX at 166 the window is only the last pixel of the line, but then covers the whole of
+/// the next one.
+#[test]
+fn window_x_166() {
+    let rom = window_rom(0x00, 0xA7, &[(0x28, 0x4B, 0xA6), (0x29, 0x4B, 0xA7)]);
+    assert_screenshot(&rom, |y| match y {
+        40 => Some((0, 159)),
+        41 => Some((1, 0)),
+        _ => None,
+    });
+}
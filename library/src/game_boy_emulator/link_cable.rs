@@ -0,0 +1,226 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{GameBoyEmulator, GameBoyOps, SerialDevice};
+use crate::rendering::Renderer;
+use crate::sound::SoundStream;
+use crate::storage::PersistentStorage;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+
+#[derive(Default)]
+struct LinkCableState {
+    /// The byte each side has ready while it waits for the other side to drive the clock.
+    waiting: [Option<u8>; 2],
+    /// Bytes clocked in to a waiting side, it picks them up the next time it polls.
+    delivered: [Option<u8>; 2],
+}
+
+/// One end of a link cable between two emulators in the same process.
+struct LinkCableEnd {
+    state: Rc<RefCell<LinkCableState>>,
+    side: usize,
+}
+
+impl SerialDevice for LinkCableEnd {
+    fn transfer_as_master(&mut self, _now: u64, value: u8) -> u8 {
+        let mut state = self.state.borrow_mut();
+        let other = 1 - self.side;
+
+        // If the other side isn't waiting for our clock, nothing gets shifted in.
+        match state.waiting[other].take() {
+            Some(incoming) => {
+                state.delivered[other] = Some(value);
+                incoming
+            }
+            None => 0xFF,
+        }
+    }
+
+    fn poll_as_slave(&mut self, _now: u64, value: u8) -> Option<u8> {
+        let mut state = self.state.borrow_mut();
+        if let Some(incoming) = state.delivered[self.side].take() {
+            state.waiting[self.side] = None;
+            Some(incoming)
+        } else {
+            state.waiting[self.side] = Some(value);
+            None
+        }
+    }
+
+    fn stop_waiting(&mut self, _now: u64) {
+        let mut state = self.state.borrow_mut();
+        state.waiting[self.side] = None;
+        state.delivered[self.side] = None;
+    }
+}
+
+/// Connects the link ports of two Game Boys with a cable. They must then be run with
+/// `run_linked`.
+pub fn connect_link_cable(
+    ops_a: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+    ops_b: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+) {
+    let state = Rc::new(RefCell::new(LinkCableState::default()));
+    ops_a.plug_in_serial_device(LinkCableEnd {
+        state: state.clone(),
+        side: 0,
+    });
+    ops_b.plug_in_serial_device(LinkCableEnd { state, side: 1 });
+}
+
+/// Runs two Game Boys for the given number of cycles. Whichever one is behind is the one that
+/// runs next, so they never get more than an instruction apart.
+pub fn run_linked(
+    a: &mut GameBoyEmulator,
+    ops_a: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+    b: &mut GameBoyEmulator,
+    ops_b: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+    cycles: u64,
+) {
+    let start_a = a.elapsed_cycles();
+    let start_b = b.elapsed_cycles();
+    loop {
+        let elapsed_a = a.elapsed_cycles() - start_a;
+        let elapsed_b = b.elapsed_cycles() - start_b;
+        if elapsed_a >= cycles && elapsed_b >= cycles {
+            break;
+        }
+
+        if elapsed_a <= elapsed_b {
+            step(a, ops_a);
+        } else {
+            step(b, ops_b);
+        }
+    }
+}
+
+fn step(
+    e: &mut GameBoyEmulator,
+    ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+) {
+    if let Some(c) = e.crashed() {
+        panic!("Emulator crashed: {}", c);
+    }
+    e.tick(ops);
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{assemble, GamePak, MemoryAccessor as _, NullGameBoyOps};
+    use alloc::{format, vec::Vec};
+
+    /// Exchanges three bytes over the link port, storing the received ones at 0xC000.
+    pub(crate) fn exchange_rom(serial_control: u8, base: u8) -> Vec<u8> {
+        assemble(&format!(
+            "
+    SECTION test,ROM0[$0100]
+        ld   hl,$C000
+        ld   b,$03
+    .loop
+        ld   a,b
+        xor  a,${base:02X}
+        ldh  [$FF01],a
+        ld   a,${serial_control:02X}
+        ldh  [$FF02],a
+    .wait
+        ldh  a,[$FF02]
+        and  a,$80
+        jr   nz,.wait
+        ldh  a,[$FF01]
+        ldi  [hl],a
+        dec  b
+        jr   nz,.loop
+        jp   $0150
+    SECTION done,ROM0[$0150]
+    .done
+        jr   .done
+    "
+        ))
+        .unwrap()
+        .bin
+    }
+
+    fn idle_rom() -> Vec<u8> {
+        assemble(
+            "
+    SECTION test,ROM0[$0100]
+    .loop
+        jr   .loop
+    ",
+        )
+        .unwrap()
+        .bin
+    }
+
+    pub(crate) fn game_boy(rom: &[u8]) -> (GameBoyEmulator, NullGameBoyOps) {
+        let mut ops = NullGameBoyOps::null();
+        let game_pak = GamePak::new(rom, &mut ops.storage, None).unwrap();
+        ops.load_game_pak(game_pak);
+        (GameBoyEmulator::new(), ops)
+    }
+
+    pub(crate) fn received(e: &GameBoyEmulator, ops: &NullGameBoyOps) -> [u8; 3] {
+        let memory_map = ops.memory_map(&e.bridge);
+        [0xC000, 0xC001, 0xC002].map(|a| memory_map.read_memory(a))
+    }
+
+    #[test]
+    fn link_cable_exchanges_bytes() {
+        let (mut master, mut master_ops) = game_boy(&exchange_rom(0x81, 0x10));
+        let (mut slave, mut slave_ops) = game_boy(&exchange_rom(0x80, 0x20));
+        connect_link_cable(&mut master_ops, &mut slave_ops);
+
+        run_linked(
+            &mut master,
+            &mut master_ops,
+            &mut slave,
+            &mut slave_ops,
+            100_000,
+        );
+
+        assert_eq!(master.cpu.read_program_counter(), 0x0150);
+        assert_eq!(slave.cpu.read_program_counter(), 0x0150);
+        assert_eq!(received(&master, &master_ops), [0x23, 0x22, 0x21]);
+        assert_eq!(received(&slave, &slave_ops), [0x13, 0x12, 0x11]);
+    }
+
+    #[test]
+    fn link_cable_without_slave() {
+        let (mut master, mut master_ops) = game_boy(&exchange_rom(0x81, 0x10));
+        let (mut other, mut other_ops) = game_boy(&idle_rom());
+        connect_link_cable(&mut master_ops, &mut other_ops);
+
+        run_linked(
+            &mut master,
+            &mut master_ops,
+            &mut other,
+            &mut other_ops,
+            100_000,
+        );
+
+        assert_eq!(master.cpu.read_program_counter(), 0x0150);
+        assert_eq!(received(&master, &master_ops), [0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn link_cable_slave_waits_for_master() {
+        let (mut slave, mut slave_ops) = game_boy(&exchange_rom(0x80, 0x20));
+        let (mut other, mut other_ops) = game_boy(&idle_rom());
+        connect_link_cable(&mut slave_ops, &mut other_ops);
+
+        run_linked(
+            &mut slave,
+            &mut slave_ops,
+            &mut other,
+            &mut other_ops,
+            100_000,
+        );
+
+        assert_ne!(slave.cpu.read_program_counter(), 0x0150);
+        assert_eq!(
+            slave_ops.memory_map(&slave.bridge).read_memory(0xFF02),
+            0xFE
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::joypad::KeyEvent;
+use super::memory_controller::{BusAccess, MemoryAccessor, MemoryDescription};
+use super::perf::PerfObserver;
+use super::{Bridge, GameBoyOps};
+use crate::rendering::Renderer;
+use crate::sound::SoundStream;
+use crate::storage::PersistentStorage;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// How the CPU's memory accesses line up with the rest of the hardware.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CpuMode {
+    /// Run each instruction all at once. Every memory access lands right after the instruction
+    /// has been fetched. This is faster, but accesses made later in an instruction happen too
+    /// early.
+    #[default]
+    Instruction,
+    /// Deliver scheduler events between the memory accesses of an instruction, so each access
+    /// lands in the M-cycle it does on hardware.
+    MCycle,
+}
+
+struct MCycleBus<'a, R, S, St: PersistentStorage, O> {
+    bridge: &'a mut Bridge,
+    joypad_key_events: &'a mut Vec<KeyEvent>,
+    ops: &'a mut GameBoyOps<R, S, St>,
+    observer: &'a mut O,
+    start: u64,
+    access_cycles: &'static [u8],
+    next_access: usize,
+    /// The M-cycle an increment / decrement happens in when no access follows it.
+    last_cycle: u8,
+}
+
+impl<'a, R: Renderer, S: SoundStream, St: PersistentStorage, O: PerfObserver>
+    MCycleBus<'a, R, S, St, O>
+{
+    fn catch_up(&mut self, cycle: u8) {
+        let now = self.start + cycle as u64 * 4;
+        self.bridge
+            .deliver_events(self.ops, self.observer, self.joypad_key_events, now);
+    }
+
+    /// Catches the rest of the hardware up to the M-cycle of the next access.
+    fn advance(&mut self) {
+        // If the instruction makes more accesses than its schedule lists, the extra ones land
+        // along with the last one.
+        if let Some(&cycle) = self.access_cycles.get(self.next_access) {
+            self.next_access += 1;
+            self.catch_up(cycle);
+        }
+    }
+
+    /// Catches the rest of the hardware up to the M-cycle the increment / decrement unit puts its
+    /// value on the bus in. A read or write shares the M-cycle of the access that follows it,
+    /// while an idle one gets the M-cycle before the next access.
+    fn advance_increment_decrement(&mut self, access: BusAccess) {
+        let cycle = match (self.access_cycles.get(self.next_access), access) {
+            (Some(&cycle), BusAccess::Idle) => cycle - 1,
+            (Some(&cycle), _) => cycle,
+            (None, _) => self.last_cycle,
+        };
+        self.catch_up(cycle);
+    }
+}
+
+/// A `MemoryAccessor` for running one instruction in `CpuMode::MCycle`. Before each access it
+/// delivers the scheduler events which happen up to the M-cycle the instruction's access schedule
+/// gives for it.
+///
+/// The accesses come through `&self`, so the bus is kept in a `RefCell`. It only borrows the parts
+/// of the emulator other than the CPU, since the events delivered in the middle of an instruction
+/// don't need it.
+pub(super) struct MCycleMemoryAccessor<'a, R, S, St: PersistentStorage, O> {
+    bus: RefCell<MCycleBus<'a, R, S, St, O>>,
+}
+
+impl<'a, R: Renderer, S: SoundStream, St: PersistentStorage, O: PerfObserver>
+    MCycleMemoryAccessor<'a, R, S, St, O>
+{
+    pub(super) fn new(
+        bridge: &'a mut Bridge,
+        joypad_key_events: &'a mut Vec<KeyEvent>,
+        ops: &'a mut GameBoyOps<R, S, St>,
+        observer: &'a mut O,
+        start: u64,
+        access_cycles: &'static [u8],
+        duration: u8,
+    ) -> Self {
+        Self {
+            bus: RefCell::new(MCycleBus {
+                bridge,
+                joypad_key_events,
+                ops,
+                observer,
+                start,
+                access_cycles,
+                next_access: 0,
+                last_cycle: (duration / 4).saturating_sub(1),
+            }),
+        }
+    }
+}
+
+impl<'a, R: Renderer, S: SoundStream, St: PersistentStorage, O: PerfObserver> MemoryAccessor
+    for MCycleMemoryAccessor<'a, R, S, St, O>
+{
+    fn read_memory(&self, address: u16) -> u8 {
+        let bus = &mut *self.bus.borrow_mut();
+        bus.advance();
+        bus.ops.memory_map(bus.bridge).read_memory(address)
+    }
+
+    fn set_memory(&mut self, address: u16, value: u8) {
+        let bus = self.bus.get_mut();
+        bus.advance();
+        bus.ops
+            .memory_map_mut(bus.bridge)
+            .set_memory(address, value);
+    }
+
+    fn set_interrupts_enabled(&mut self, enabled: bool) {
+        let bus = self.bus.get_mut();
+        bus.ops
+            .memory_map_mut(bus.bridge)
+            .set_interrupts_enabled(enabled);
+    }
+
+    fn describe_address(&self, address: u16) -> MemoryDescription {
+        let bus = self.bus.borrow();
+        bus.ops.memory_map(bus.bridge).describe_address(address)
+    }
+
+    fn increment_decrement(&mut self, address: u16, access: BusAccess) {
+        let bus = self.bus.get_mut();
+        bus.advance_increment_decrement(access);
+        bus.ops
+            .memory_map_mut(bus.bridge)
+            .increment_decrement(address, access);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{
+        assemble, GameBoyEmulator, GamePak, NullGameBoyOps, NullPerfObserver,
+    };
+    use crate::lr35902_emulator::Intel8080Register;
+
+    #[test]
+    fn accesses_land_in_their_m_cycles() {
+        let mut e = GameBoyEmulator::new();
+        let mut ops = NullGameBoyOps::null();
+        let mut observer = NullPerfObserver;
+        let start = e.elapsed_cycles();
+
+        let mut memory_accessor = MCycleMemoryAccessor::new(
+            &mut e.bridge,
+            &mut e.joypad_key_events,
+            &mut ops,
+            &mut observer,
+            start,
+            &[2, 3],
+            16,
+        );
+        memory_accessor.set_memory(0xFF80, 0x12);
+        assert_eq!(
+            memory_accessor.bus.get_mut().bridge.scheduler.now(),
+            start + 8
+        );
+        assert_eq!(memory_accessor.read_memory(0xFF80), 0x12);
+        assert_eq!(
+            memory_accessor.bus.get_mut().bridge.scheduler.now(),
+            start + 12
+        );
+    }
+
+    #[test]
+    fn increment_decrement_lands_in_its_m_cycle() {
+        let mut e = GameBoyEmulator::new();
+        let mut ops = NullGameBoyOps::null();
+        let mut observer = NullPerfObserver;
+        let start = e.elapsed_cycles();
+
+        // Like PUSH, which puts SP on the bus before each of its two writes.
+        let mut memory_accessor = MCycleMemoryAccessor::new(
+            &mut e.bridge,
+            &mut e.joypad_key_events,
+            &mut ops,
+            &mut observer,
+            start,
+            &[2, 3],
+            16,
+        );
+        let now = |memory_accessor: &mut MCycleMemoryAccessor<'_, _, _, _, _>| {
+            memory_accessor.bus.get_mut().bridge.scheduler.now()
+        };
+        memory_accessor.increment_decrement(0xC002, BusAccess::Idle);
+        assert_eq!(now(&mut memory_accessor), start + 4);
+        memory_accessor.increment_decrement(0xC001, BusAccess::Write);
+        assert_eq!(now(&mut memory_accessor), start + 8);
+        memory_accessor.set_memory(0xC001, 0x12);
+        memory_accessor.increment_decrement(0xC000, BusAccess::Write);
+        assert_eq!(now(&mut memory_accessor), start + 12);
+        memory_accessor.set_memory(0xC000, 0x34);
+
+        // Like INC rr, which doesn't access memory at all.
+        let start = start + 16;
+        let mut memory_accessor = MCycleMemoryAccessor::new(
+            &mut e.bridge,
+            &mut e.joypad_key_events,
+            &mut ops,
+            &mut observer,
+            start,
+            &[],
+            8,
+        );
+        memory_accessor.increment_decrement(0xC000, BusAccess::Idle);
+        assert_eq!(now(&mut memory_accessor), start + 4);
+    }
+
+    fn run_program(cpu_mode: CpuMode) -> (GameBoyEmulator, NullGameBoyOps) {
+        let program = "
+        SECTION entry,ROM0[$0100]
+            jp   $0150
+        SECTION test,ROM0[$0150]
+            ld   sp,$FFFE
+            ld   bc,$1234
+            push bc
+            call $0200
+            pop  de
+            ld   hl,$FF80
+            ld   [hl],$56
+            inc  [hl]
+            set  7,[hl]
+            ld   a,[hl]
+            ld   [$FF81],a
+            rst  $08
+        .done
+            jr   .done
+        SECTION sub,ROM0[$0200]
+            ld   [$FF82],sp
+            ret
+        SECTION rst,ROM0[$0008]
+            ld   a,$9A
+            ldh  [$FF84],a
+            ret
+        ";
+        let rom = assemble(program).unwrap().bin;
+        let mut ops = NullGameBoyOps::null();
+        ops.cpu_mode = cpu_mode;
+        let game_pak = GamePak::new(&rom, &mut ops.storage, None).unwrap();
+        ops.load_game_pak(game_pak);
+
+        let mut e = GameBoyEmulator::new();
+        let end = e.elapsed_cycles() + 10_000;
+        while e.elapsed_cycles() < end {
+            e.tick(&mut ops);
+        }
+        (e, ops)
+    }
+
+    #[test]
+    fn cpu_modes_agree() {
+        let (e, ops) = run_program(CpuMode::MCycle);
+        let memory_map = ops.memory_map(&e.bridge);
+        assert_eq!(e.cpu.read_register_pair(Intel8080Register::D), 0x1234);
+        assert_eq!(memory_map.read_memory(0xFF81), 0xD7);
+        assert_eq!(memory_map.read_memory(0xFF82), 0xFA);
+        assert_eq!(memory_map.read_memory(0xFF83), 0xFF);
+        assert_eq!(memory_map.read_memory(0xFF84), 0x9A);
+
+        let (expected_e, _) = run_program(CpuMode::Instruction);
+        assert_eq!(
+            e.cpu.read_program_counter(),
+            expected_e.cpu.read_program_counter()
+        );
+        assert_eq!(
+            e.bridge.high_ram.as_slice(),
+            expected_e.bridge.high_ram.as_slice()
+        );
+    }
+}
@@ -8,29 +8,18 @@ where
     #[allow(clippy::identity_op, clippy::if_same_then_else)]
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn read_memory(&self, address: u16) -> u8 {
+        if let Some(value) = MemoryMappedHardware::bus_conflict(&self.bridge.oam_dma, address) {
+            return value;
+        }
         if address == 65280u16 {
             MemoryMappedHardware::read_value(&self.joypad, address - 65280u16)
         } else if address == 65281u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.registers.serial_transfer_data,
-                address - 65281u16,
-            )
+            MemoryMappedHardware::read_value(&self.bridge.serial.data, address - 65281u16)
         } else if address == 65282u16 {
             MemoryMappedHardware::read_value(
-                &self.bridge.registers.serial_transfer_control,
+                &(&self.bridge.serial, &self.bridge.scheduler),
                 address - 65282u16,
             )
-        } else if address == 65284u16 {
-            MemoryMappedHardware::read_value(&self.bridge.registers.divider, address - 65284u16)
-        } else if address == 65285u16 {
-            MemoryMappedHardware::read_value(&self.bridge.timer.counter, address - 65285u16)
-        } else if address == 65286u16 {
-            MemoryMappedHardware::read_value(&self.bridge.timer.modulo, address - 65286u16)
-        } else if address == 65287u16 {
-            MemoryMappedHardware::read_value(
-                &(&self.bridge.timer, &self.bridge.scheduler),
-                address - 65287u16,
-            )
         } else if address == 65295u16 {
             MemoryMappedHardware::read_value(
                 &(
@@ -39,36 +28,6 @@ where
                 ),
                 address - 65295u16,
             )
-        } else if address == 65344u16 {
-            MemoryMappedHardware::read_value(
-                &(&self.bridge.lcd_controller, &self.bridge.scheduler),
-                address - 65344u16,
-            )
-        } else if address == 65345u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.lcd_controller.registers.stat,
-                address - 65345u16,
-            )
-        } else if address == 65346u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.lcd_controller.registers.scy,
-                address - 65346u16,
-            )
-        } else if address == 65347u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.lcd_controller.registers.scx,
-                address - 65347u16,
-            )
-        } else if address == 65348u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.lcd_controller.registers.ly,
-                address - 65348u16,
-            )
-        } else if address == 65349u16 {
-            MemoryMappedHardware::read_value(
-                &self.bridge.lcd_controller.registers.lyc,
-                address - 65349u16,
-            )
         } else if address == 65350u16 {
             MemoryMappedHardware::read_value(
                 &(
@@ -102,6 +61,8 @@ where
                 &self.bridge.lcd_controller.registers.wx,
                 address - 65355u16,
             )
+        } else if address == 65360u16 {
+            MemoryMappedHardware::read_value(&self.bridge.boot_rom, address - 65360u16)
         } else if address == 65535u16 {
             MemoryMappedHardware::read_value(
                 &(
@@ -110,6 +71,11 @@ where
                 ),
                 address - 65535u16,
             )
+        } else if address < 256u16 {
+            MemoryMappedHardware::read_value(
+                &(&self.bridge.boot_rom, &self.game_pak),
+                address - 0u16,
+            )
         } else if address < 32768u16 {
             MemoryMappedHardware::read_value(&self.game_pak, address - 0u16)
         } else if (32768u16..38912u16).contains(&address) {
@@ -145,8 +111,18 @@ where
                 &self.bridge.lcd_controller.unusable_memory,
                 address - 65184u16,
             )
+        } else if (65284u16..65288u16).contains(&address) {
+            MemoryMappedHardware::read_value(
+                &(&self.bridge.timer, &self.bridge.scheduler),
+                address - 65284u16,
+            )
         } else if (65296u16..65344u16).contains(&address) {
             MemoryMappedHardware::read_value(&self.bridge.sound_controller, address - 0u16)
+        } else if (65344u16..65350u16).contains(&address) {
+            MemoryMappedHardware::read_value(
+                &(&self.bridge.lcd_controller, &self.bridge.scheduler),
+                address - 65344u16,
+            )
         } else if (65408u16..65535u16).contains(&address) {
             MemoryMappedHardware::read_value(&self.bridge.high_ram, address - 65408u16)
         } else {
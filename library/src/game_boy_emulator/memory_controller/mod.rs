@@ -3,7 +3,7 @@
 use super::game_pak::GamePak;
 use super::joypad::JoyPad;
 use super::Bridge;
-pub use crate::emulator_common::{MemoryAccessor, MemoryDescription};
+pub use crate::emulator_common::{BusAccess, MemoryAccessor, MemoryDescription};
 use crate::storage::PersistentStorage;
 use alloc::{boxed::Box, format, vec, vec::Vec};
 use core::fmt;
@@ -338,6 +338,12 @@ pub trait MemoryMappedHardware {
     fn set_interrupts_enabled(&mut self, _enabled: bool) {
         panic!("unexpected set_interrupts_enabled call");
     }
+    fn increment_decrement(&mut self, _address: u16, _access: BusAccess) {}
+    /// When something else has hold of the bus the given address is on, this is what the CPU
+    /// reads there instead. Writes to it go nowhere.
+    fn bus_conflict(&self, _address: u16) -> Option<u8> {
+        None
+    }
 }
 
 impl<T: MemoryMappedHardware> MemoryMappedHardware for Option<T> {
@@ -355,6 +361,11 @@ impl<T: MemoryMappedHardware> MemoryMappedHardware for Option<T> {
             v.set_value(address, value);
         };
     }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn bus_conflict(&self, address: u16) -> Option<u8> {
+        self.as_ref().and_then(|v| v.bus_conflict(address))
+    }
 }
 
 impl<T: MemoryMappedHardware + ?Sized> MemoryMappedHardware for Box<T> {
@@ -1,27 +1,50 @@
 // Copyright 2017 Remi Bernotavicius
 
-pub use self::game_pak::{rom_hash, GamePak};
+use self::boot_rom::BootRomOverlay;
+pub use self::boot_rom::{Boot, BootRom, GameBoyModel, ParseGameBoyModelError, BOOT_ROM_SIZE};
+#[cfg(feature = "bitmap-renderer")]
+pub use self::game_pak::BmpImageSource;
+pub use self::game_pak::{
+    rom_hash, CartridgeHeader, CgbSupport, Checksum, Destination, FrozenWallClock, GamePak,
+    GamePakError, ImageSource, InfraredPeer, LoopbackInfraredPeer, Mbc1Wiring, Rumble,
+    TestPatternImageSource, TiltSensor, ToneGenerator, WallClock, CAMERA_SENSOR_HEIGHT,
+    CAMERA_SENSOR_WIDTH,
+};
+#[cfg(feature = "std")]
+pub use self::game_pak::{SharedTilt, SystemWallClock};
 pub use self::joypad::ControllerJoyPad;
 use self::joypad::{JoyPad, KeyEvent};
-use self::lcd_controller::{LcdController, OAM_DATA};
+use self::lcd_controller::LcdController;
+pub use self::link_cable::{connect_link_cable, run_linked};
+use self::m_cycle::MCycleMemoryAccessor;
 pub use self::memory_controller::MemoryMappedHardware;
 use self::memory_controller::{
-    FlagMask, GameBoyFlags, GameBoyMemoryMap, GameBoyMemoryMapMut, GameBoyRegister, MemoryAccessor,
-    MemoryChunk,
+    FlagMask, GameBoyFlags, GameBoyMemoryMap, GameBoyMemoryMapMut, MemoryAccessor, MemoryChunk,
 };
+#[cfg(feature = "std")]
+pub use self::network_link::{LinkError, NetworkLinkCable, DEFAULT_LINK_SYNC_CYCLES};
+use self::oam_dma::OamDmaTransfer;
+#[cfg(feature = "bitmap-renderer")]
+pub use self::printer::{GameBoyPrinter, PRINTER_WIDTH};
+use self::serial::SerialPort;
+pub use self::serial::{CapturingSerialDevice, SerialDevice};
 use self::sound_controller::SoundController;
+use self::timer::GameBoyTimer;
 use crate::io;
-use crate::lr35902_emulator::{Intel8080Register, LR35902Emulator, LR35902Flag};
+use crate::lr35902_emulator::{
+    Intel8080Register, LR35902Emulator, LR35902Flag, LR35902Instruction,
+};
 use crate::rendering::{Keycode, NullRenderer, Renderer};
 use crate::sound::{NullSoundStream, SoundStream};
 use crate::storage::{OpenMode, PanicStorage, PersistentStorage};
 use crate::util::super_fast_hash;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use core::fmt::Debug;
-use core::ops::{Range, RangeFrom};
-use core::{fmt, mem};
+use core::mem;
+use core::ops::Range;
 use enum_iterator::IntoEnumIterator;
-pub use lcd_controller::Palette;
+pub use lcd_controller::{Palette, PpuMode};
+pub use m_cycle::CpuMode;
 use num_enum::IntoPrimitive;
 use perf::PerfObserver;
 use serde_derive::{Deserialize, Serialize};
@@ -35,18 +58,28 @@ pub use perf::NullPerfObserver;
 
 #[cfg(feature = "std")]
 mod assembler;
+mod boot_rom;
 mod coverage;
 mod debugger;
 mod disassembler;
 mod game_pak;
 pub mod joypad;
 mod lcd_controller;
+mod link_cable;
+mod m_cycle;
 mod memory_controller;
+#[cfg(feature = "std")]
+mod network_link;
+mod oam_dma;
 #[macro_use]
 pub mod perf;
+#[cfg(feature = "bitmap-renderer")]
+mod printer;
 mod runner;
+mod serial;
 mod sound_controller;
 mod tandem;
+mod timer;
 mod underclocker;
 
 pub mod trampolines;
@@ -80,9 +113,16 @@ impl ModuloCounter {
 #[derive(Debug)]
 pub enum Error {
     Coverage(coverage::Error),
+    GamePak(game_pak::GamePakError),
     Io(io::Error),
+    #[cfg(feature = "std")]
+    Link(network_link::LinkError),
     Replay(joypad::replay::Error),
     Serde(crate::codec::Error),
+    WrongBootRomLength {
+        expected: usize,
+        actual: usize,
+    },
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -93,6 +133,19 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<game_pak::GamePakError> for Error {
+    fn from(e: game_pak::GamePakError) -> Self {
+        Self::GamePak(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<network_link::LinkError> for Error {
+    fn from(e: network_link::LinkError) -> Self {
+        Self::Link(e)
+    }
+}
+
 impl From<joypad::replay::Error> for Error {
     fn from(e: joypad::replay::Error) -> Self {
         Self::Replay(e)
@@ -216,40 +269,6 @@ const INTERNAL_RAM_B: Range<u16> = Range {
     end: 0xE000,
 };
 
-#[derive(Serialize, Deserialize, Default)]
-struct Divider(GameBoyRegister);
-
-impl fmt::Debug for Divider {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.0)
-    }
-}
-
-impl Divider {
-    fn set_state_post_bios(&mut self) {
-        self.0.set_value(0xab);
-    }
-
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn increment(&mut self) {
-        self.0.add(1)
-    }
-}
-
-impl MemoryMappedHardware for Divider {
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn read_value(&self, address: u16) -> u8 {
-        assert_eq!(address, 0);
-        self.0.read_value()
-    }
-
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn set_value(&mut self, address: u16, _value: u8) {
-        assert_eq!(address, 0);
-        self.0.set_value(0);
-    }
-}
-
 #[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
 struct InterruptsEnabled(bool);
 
@@ -305,10 +324,6 @@ struct GameBoyRegisters {
     interrupt_flag: GameBoyFlags<InterruptFlag>,
     interrupt_enable_mask: GameBoyFlags<InterruptEnableFlag>,
     interrupts_enabled: InterruptsEnabled,
-
-    serial_transfer_data: GameBoyRegister,
-    serial_transfer_control: GameBoyRegister,
-    divider: Divider,
 }
 
 /// This implementation is where reads for interrupt_flag go
@@ -395,134 +410,17 @@ impl<'a> MemoryMappedHardware
     }
 }
 
-#[derive(Default, Serialize, Deserialize)]
-struct GameBoyTimer {
-    counter: GameBoyRegister,
-    modulo: GameBoyRegister,
-    control: GameBoyFlags<TimerFlags>,
-    running: bool,
-}
-
-impl fmt::Debug for GameBoyTimer {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("GameBoyTimer")
-            .field("counter", &self.counter)
-            .field("modulo", &self.modulo)
-            .field("control", &self.control)
-            .finish()
-    }
-}
-
-#[derive(Debug, Clone, Copy, IntoPrimitive, IntoEnumIterator)]
-#[repr(u8)]
-enum TimerFlags {
-    Enabled = 0b00000100,
-    Speed = 0b00000011,
-}
-
-impl FlagMask for TimerFlags {
-    fn read_mask() -> u8 {
-        TimerFlags::Enabled as u8 | TimerFlags::Speed as u8
-    }
-
-    fn write_mask() -> u8 {
-        TimerFlags::Enabled as u8 | TimerFlags::Speed as u8
-    }
-}
-
-impl GameBoyTimer {
-    fn enabled(&self) -> bool {
-        self.control.read_flag(TimerFlags::Enabled)
-    }
-
-    fn timer_speed(&self) -> u64 {
-        match self.control.read_flag_value(TimerFlags::Speed) {
-            0b00 => 1024,
-            0b01 => 16,
-            0b10 => 64,
-            0b11 => 256,
-            _ => panic!(),
-        }
-    }
-
-    fn set_state_post_bios(&mut self) {
-        self.counter.set_value(0x0);
-        self.modulo.set_value(0x0);
-        self.control.set_value(0xf8);
-    }
-
-    fn schedule_initial_events(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
-        let speed = self.timer_speed();
-        scheduler.schedule(now + speed, GameBoyEmulatorEvent::TimerTick);
-    }
-
-    fn fire(
-        &mut self,
-        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
-        scheduler: &mut GameBoyScheduler,
-        now: u64,
-    ) {
-        let counter = self.counter.read_value().wrapping_add(1);
-        if counter == 0 {
-            interrupt_flag.set_flag(InterruptFlag::Timer, true);
-            scheduler.schedule(now, GameBoyEmulatorEvent::HandleInterrupts);
-            let modulo_value = self.modulo.read_value();
-            self.counter.set_value(modulo_value);
-        } else {
-            self.counter.set_value(counter);
-        }
-        let speed = self.timer_speed();
-        scheduler.schedule(now + speed, GameBoyEmulatorEvent::TimerTick);
-    }
-}
-
-/// This implementation is where reads for timer control go
-impl<'a> MemoryMappedHardware for (&'a GameBoyTimer, &'a GameBoyScheduler) {
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn read_value(&self, address: u16) -> u8 {
-        assert_eq!(address, 0);
-        let (timer, _scheduler) = self;
-        timer.control.read_value()
-    }
-
-    fn set_value(&mut self, _address: u16, _value: u8) {
-        unreachable!()
-    }
-}
-
-/// This implementation is where the writes for timer control go
-impl<'a> MemoryMappedHardware for (&'a mut GameBoyTimer, &'a mut GameBoyScheduler) {
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn read_value(&self, address: u16) -> u8 {
-        assert_eq!(address, 0);
-        let (timer, _scheduler) = self;
-        timer.control.read_value()
-    }
-
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn set_value(&mut self, address: u16, value: u8) {
-        assert_eq!(address, 0);
-        let (timer, scheduler) = self;
-
-        timer.control.set_value(value);
-
-        scheduler.drop_events(|e| matches!(e, GameBoyEmulatorEvent::TimerTick));
-        if timer.enabled() {
-            let speed = timer.timer_speed();
-            let now = scheduler.now();
-            scheduler.schedule(now + speed, GameBoyEmulatorEvent::TimerTick);
-        }
-    }
-}
-
 #[derive(Serialize, Deserialize)]
 pub(crate) enum GameBoyEmulatorEvent {
-    DividerTick,
     DriveJoypad,
     TimerTick,
+    TimerReload,
     StartDmaTransfer { address: u16 },
     DriveDmaTransfer,
     HandleInterrupts,
+    SerialTransferComplete,
+    PollSerialDevice,
+    DriveSerialDevice,
     Lcd(lcd_controller::LcdControllerEvent),
     Sound(sound_controller::SoundControllerEvent),
 }
@@ -530,12 +428,15 @@ pub(crate) enum GameBoyEmulatorEvent {
 impl<'a> From<&'a GameBoyEmulatorEvent> for &'static str {
     fn from(e: &'a GameBoyEmulatorEvent) -> Self {
         match e {
-            GameBoyEmulatorEvent::DividerTick => "DividerTick",
             GameBoyEmulatorEvent::DriveJoypad => "DriveJoypad",
             GameBoyEmulatorEvent::TimerTick => "TimerTick",
+            GameBoyEmulatorEvent::TimerReload => "TimerReload",
             GameBoyEmulatorEvent::StartDmaTransfer { .. } => "StartDmaTransfer",
             GameBoyEmulatorEvent::DriveDmaTransfer => "DriveDmaTransfer",
             GameBoyEmulatorEvent::HandleInterrupts => "HandleInterrupts",
+            GameBoyEmulatorEvent::SerialTransferComplete => "SerialTransferComplete",
+            GameBoyEmulatorEvent::PollSerialDevice => "PollSerialDevice",
+            GameBoyEmulatorEvent::DriveSerialDevice => "DriveSerialDevice",
             GameBoyEmulatorEvent::Lcd(e) => e.into(),
             GameBoyEmulatorEvent::Sound(e) => e.into(),
         }
@@ -564,24 +465,63 @@ impl GameBoyEmulatorEvent {
         ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
         time: u64,
     ) {
-        let scheduler = &mut emulator.bridge.scheduler;
-        let interrupt_flag = &mut emulator.bridge.registers.interrupt_flag;
         match self {
-            Self::DividerTick => emulator.divider_tick(time),
             Self::DriveJoypad => emulator.drive_joypad(ops, time),
-            Self::TimerTick => emulator.bridge.timer.fire(interrupt_flag, scheduler, time),
-            Self::StartDmaTransfer { address } => emulator.start_dma_transfer(ops, address, time),
-            Self::DriveDmaTransfer => emulator.drive_dma_transfer(ops, time),
             Self::HandleInterrupts => emulator.handle_interrupts(ops),
+            _ => self.deliver_to_bridge(
+                &mut emulator.bridge,
+                &mut emulator.joypad_key_events,
+                ops,
+                time,
+            ),
+        }
+    }
+
+    /// Delivers any event other than `HandleInterrupts` without needing the CPU, which lets
+    /// events be delivered in the middle of an instruction.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn deliver_to_bridge(
+        self,
+        bridge: &mut Bridge,
+        joypad_key_events: &mut Vec<KeyEvent>,
+        ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        time: u64,
+    ) {
+        let scheduler = &mut bridge.scheduler;
+        let interrupt_flag = &mut bridge.registers.interrupt_flag;
+        match self {
+            Self::DriveJoypad => bridge.drive_joypad(ops, joypad_key_events, time),
+            Self::TimerTick => bridge.timer.fire(scheduler, time),
+            Self::TimerReload => bridge.timer.reload(interrupt_flag, scheduler, time),
+            Self::StartDmaTransfer { address } => bridge.start_dma_transfer(ops, address, time),
+            Self::DriveDmaTransfer => bridge.drive_dma_transfer(ops, time),
+            Self::HandleInterrupts => unreachable!(),
+            Self::SerialTransferComplete => bridge.serial.complete_transfer(
+                ops.serial_device_mut(),
+                interrupt_flag,
+                scheduler,
+                time,
+            ),
+            Self::PollSerialDevice => {
+                bridge
+                    .serial
+                    .poll_device(ops.serial_device_mut(), interrupt_flag, scheduler, time)
+            }
+            Self::DriveSerialDevice => {
+                bridge
+                    .serial
+                    .drive_device(ops.serial_device_mut(), scheduler, time)
+            }
             Self::Lcd(e) => e.deliver(
-                &mut emulator.bridge.lcd_controller,
+                &mut bridge.lcd_controller,
                 &mut ops.renderer,
+                ops.ppu_mode,
                 interrupt_flag,
                 scheduler,
                 time,
             ),
             Self::Sound(e) => e.deliver(
-                &mut emulator.bridge.sound_controller,
+                &mut bridge.sound_controller,
                 &mut ops.sound_stream,
                 scheduler,
                 time,
@@ -590,45 +530,6 @@ impl GameBoyEmulatorEvent {
     }
 }
 
-#[derive(Serialize, Deserialize)]
-struct OamDmaTransfer {
-    src_current: u16,
-    dst_current: u16,
-    value: u8,
-    now: u64,
-}
-
-impl OamDmaTransfer {
-    fn new(src: RangeFrom<u16>, now: u64) -> Self {
-        Self {
-            src_current: src.start,
-            dst_current: 0,
-            value: 0,
-            now,
-        }
-    }
-
-    fn read(&mut self, memory_map: &GameBoyMemoryMap<'_, impl PersistentStorage>) {
-        self.value = memory_map.read_memory(self.src_current);
-        self.src_current = self.src_current.wrapping_add(1);
-    }
-
-    fn write(&mut self, oam: &mut MemoryChunk) {
-        oam.as_mut_slice()[self.dst_current as usize] = self.value;
-        self.dst_current += 1;
-    }
-
-    fn bytes_to_transfer(&mut self, now: u64) -> u64 {
-        let result = now - self.now;
-        self.now = now;
-        result
-    }
-
-    fn is_done(&self) -> bool {
-        self.dst_current == (OAM_DATA.end - OAM_DATA.start)
-    }
-}
-
 const fn default_clock_speed_hz() -> u32 {
     // GameBoy clock speed is about 4.19Mhz
     4_194_304
@@ -639,8 +540,11 @@ pub struct GameBoyOps<Renderer, SoundStream, Storage: PersistentStorage> {
     pub sound_stream: SoundStream,
     pub storage: Storage,
     joypad: Option<Box<dyn JoyPad + 'static>>,
+    serial_device: Option<Box<dyn SerialDevice + 'static>>,
     game_pak: Option<GamePak<Storage>>,
     pub clock_speed_hz: u32,
+    pub ppu_mode: PpuMode,
+    pub cpu_mode: CpuMode,
 }
 
 pub type NullGameBoyOps = GameBoyOps<NullRenderer, NullSoundStream, PanicStorage>;
@@ -658,8 +562,11 @@ impl<Renderer, SoundStream, Storage: PersistentStorage> GameBoyOps<Renderer, Sou
             sound_stream,
             storage,
             joypad: None,
+            serial_device: None,
             game_pak: None,
             clock_speed_hz: default_clock_speed_hz(),
+            ppu_mode: PpuMode::default(),
+            cpu_mode: CpuMode::default(),
         }
     }
 
@@ -688,8 +595,20 @@ impl<Renderer, SoundStream, Storage: PersistentStorage> GameBoyOps<Renderer, Sou
         self.joypad = Some(Box::new(joypad));
     }
 
-    pub fn load_game_pak(&mut self, game_pak: GamePak<Storage>) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn serial_device_mut(&mut self) -> Option<&mut dyn SerialDevice> {
+        self.serial_device
+            .as_mut()
+            .map(|d| &mut **d as &mut dyn SerialDevice)
+    }
+
+    pub fn plug_in_serial_device(&mut self, device: impl SerialDevice + 'static) {
+        self.serial_device = Some(Box::new(device));
+    }
+
+    pub fn load_game_pak(&mut self, mut game_pak: GamePak<Storage>) {
         log::info!("Loading {:?}", &game_pak);
+        game_pak.start_clock();
         self.game_pak = Some(game_pak);
     }
 
@@ -706,8 +625,11 @@ pub(crate) struct Bridge {
     internal_ram_a: MemoryChunk,
     internal_ram_b: MemoryChunk,
     registers: GameBoyRegisters,
+    serial: SerialPort,
     timer: GameBoyTimer,
     scheduler: GameBoyScheduler,
+    boot_rom: BootRomOverlay,
+    oam_dma: Option<OamDmaTransfer>,
 }
 
 impl Bridge {
@@ -719,17 +641,104 @@ impl Bridge {
             internal_ram_a: MemoryChunk::from_range(INTERNAL_RAM_A),
             internal_ram_b: MemoryChunk::from_range(INTERNAL_RAM_B),
             registers: Default::default(),
+            serial: Default::default(),
             timer: Default::default(),
             scheduler: GameBoyScheduler::new(),
+            boot_rom: Default::default(),
+            oam_dma: None,
         }
     }
+
+    /// Delivers the events up to the given time, except for handling interrupts. This is what
+    /// happens in the middle of an instruction.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn deliver_events(
+        &mut self,
+        ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        observer: &mut impl PerfObserver,
+        joypad_key_events: &mut Vec<KeyEvent>,
+        now: u64,
+    ) {
+        let m = |e: &_| !matches!(e, GameBoyEmulatorEvent::HandleInterrupts);
+        while let Some((time, event)) = self.scheduler.poll_match(now, m) {
+            observe!(
+                observer,
+                (&event).into(),
+                event.deliver_to_bridge(self, joypad_key_events, ops, time)
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn drive_joypad(
+        &mut self,
+        ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        key_events: &mut Vec<KeyEvent>,
+        time: u64,
+    ) {
+        if let Some(joypad) = &mut ops.joypad {
+            joypad.tick(time, mem::take(key_events));
+
+            if joypad.take_interrupt() {
+                self.registers
+                    .interrupt_flag
+                    .set_flag(InterruptFlag::Joypad, true);
+                self.scheduler
+                    .schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
+            }
+        }
+
+        self.scheduler
+            .schedule(time + 456, GameBoyEmulatorEvent::DriveJoypad);
+    }
+
+    /// Starts an OAM DMA transfer. If one is already running, this one takes its place.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn start_dma_transfer(
+        &mut self,
+        ops: &GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        mut address: u16,
+        now: u64,
+    ) {
+        // 0xE000 to 0xFFFF is mapped differently for DMA. It ends up just being the internal
+        // ram repeated again. To account for this we just adjust the source address.
+        if address >= INTERNAL_RAM_B.end {
+            address -= 0x2000;
+        }
+        self.scheduler
+            .drop_events(|e| matches!(e, GameBoyEmulatorEvent::DriveDmaTransfer));
+        self.oam_dma = Some(OamDmaTransfer::new(address));
+
+        self.drive_dma_transfer(ops, now);
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn drive_dma_transfer(
+        &mut self,
+        ops: &GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        now: u64,
+    ) {
+        // The transfer is taken out while it reads so it doesn't get in its own way on the bus.
+        let mut transfer = self.oam_dma.take().unwrap();
+
+        // The bus is let go of the M-cycle after the last byte is copied.
+        if transfer.is_done() {
+            return;
+        }
+
+        transfer.read(&ops.memory_map(self));
+        transfer.write(&mut self.lcd_controller.oam_data);
+        self.oam_dma = Some(transfer);
+
+        self.scheduler
+            .schedule(now + 4, GameBoyEmulatorEvent::DriveDmaTransfer);
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct GameBoyEmulator {
     pub cpu: LR35902Emulator,
     bridge: Bridge,
-    dma_transfer: Option<OamDmaTransfer>,
     joypad_key_events: Vec<KeyEvent>,
 }
 
@@ -741,18 +750,28 @@ impl Default for GameBoyEmulator {
 
 impl GameBoyEmulator {
     pub fn new() -> Self {
+        Self::new_with_boot(Boot::default())
+    }
+
+    pub fn new_with_boot(boot: Boot) -> Self {
         let mut e = GameBoyEmulator {
             cpu: LR35902Emulator::new(),
             bridge: Bridge::new(),
-
-            dma_transfer: None,
             joypad_key_events: vec![],
         };
-        e.set_state_post_bios();
+        match boot {
+            Boot::BootRom(boot_rom) => e.set_state_power_on(boot_rom),
+            Boot::PostBoot(model) => e.set_state_post_bios(model),
+        }
         e.schedule_initial_events();
         e
     }
 
+    /// Returns true while the boot ROM is still mapped over the cartridge.
+    pub fn running_boot_rom(&self) -> bool {
+        self.bridge.boot_rom.is_mapped()
+    }
+
     fn crashed(&self) -> Option<&String> {
         if self.cpu.crashed() {
             self.cpu.crash_message.as_ref()
@@ -761,14 +780,6 @@ impl GameBoyEmulator {
         }
     }
 
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn divider_tick(&mut self, time: u64) {
-        self.bridge.registers.divider.increment();
-        self.bridge
-            .scheduler
-            .schedule(time + 256, GameBoyEmulatorEvent::DividerTick);
-    }
-
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn deliver_events(
         &mut self,
@@ -809,29 +820,88 @@ impl GameBoyEmulator {
         ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
         observer: &mut impl PerfObserver,
     ) {
-        let instr = observe!(observer, "load_instruction", {
-            self.cpu.load_instruction(&ops.memory_map(&self.bridge))
-        });
+        let start = self.cpu.elapsed_cycles;
+        let instr = match ops.cpu_mode {
+            CpuMode::Instruction => observe!(observer, "load_instruction", {
+                self.cpu.load_instruction(&ops.memory_map(&self.bridge))
+            }),
+            CpuMode::MCycle => self.load_instruction_by_m_cycle(ops, observer, start),
+        };
 
         if let Some(instr) = instr {
             let interrupts_ok = false;
             self.deliver_events(ops, observer, interrupts_ok, self.cpu.elapsed_cycles);
 
-            observe!(observer, "execute_instruction", {
-                self.cpu
-                    .execute_instruction(&mut ops.memory_map_mut(&mut self.bridge), instr)
-            });
+            // An EI before this instruction can set IME while it runs, but HALT looks at IME as
+            // it was beforehand.
+            let interrupts_enabled = self.bridge.registers.interrupts_enabled.get();
+
+            match ops.cpu_mode {
+                CpuMode::Instruction => observe!(observer, "execute_instruction", {
+                    self.cpu
+                        .execute_instruction(&mut ops.memory_map_mut(&mut self.bridge), instr)
+                }),
+                CpuMode::MCycle => self.execute_instruction_by_m_cycle(ops, observer, instr, start),
+            }
+
+            if self.cpu.is_halted() {
+                self.enter_halt_mode(interrupts_enabled);
+            }
+
+            if self.cpu.is_stopped() {
+                self.enter_stop_mode();
+            }
         } else {
             self.cpu.crash_from_unkown_opcode();
         }
     }
 
+    /// Fetches the instruction one byte per M-cycle.
+    fn load_instruction_by_m_cycle(
+        &mut self,
+        ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        observer: &mut impl PerfObserver,
+        start: u64,
+    ) -> Option<LR35902Instruction> {
+        let memory_accessor = MCycleMemoryAccessor::new(
+            &mut self.bridge,
+            &mut self.joypad_key_events,
+            ops,
+            observer,
+            start,
+            &[0, 1, 2],
+            12,
+        );
+        self.cpu.load_instruction(&memory_accessor)
+    }
+
+    /// Runs the instruction with each of its memory accesses landing in the M-cycle given by
+    /// its access schedule.
+    fn execute_instruction_by_m_cycle(
+        &mut self,
+        ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
+        observer: &mut impl PerfObserver,
+        instr: LR35902Instruction,
+        start: u64,
+    ) {
+        let mut memory_accessor = MCycleMemoryAccessor::new(
+            &mut self.bridge,
+            &mut self.joypad_key_events,
+            ops,
+            observer,
+            start,
+            instr.access_cycles(),
+            instr.duration(),
+        );
+        self.cpu.execute_instruction(&mut memory_accessor, instr);
+    }
+
     pub fn tick_with_observer(
         &mut self,
         ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
         observer: &mut impl PerfObserver,
     ) {
-        if self.cpu.is_halted() {
+        if self.cpu.is_halted() || self.cpu.is_stopped() {
             self.halted_cpu_tick(ops, observer);
         } else {
             self.cpu_tick(ops, observer);
@@ -885,58 +955,52 @@ impl GameBoyEmulator {
         ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
         time: u64,
     ) {
-        if let Some(joypad) = &mut ops.joypad {
-            joypad.tick(time, mem::take(&mut self.joypad_key_events));
-        }
-
         self.bridge
-            .scheduler
-            .schedule(time + 456, GameBoyEmulatorEvent::DriveJoypad);
+            .drive_joypad(ops, &mut self.joypad_key_events, time);
+
+        // Any selected button being held down wakes the Game Boy from STOP.
+        if let Some(joypad) = &ops.joypad {
+            if self.cpu.is_stopped() && joypad.read_value(0) & 0x0F != 0x0F {
+                self.leave_stop_mode(time);
+            }
+        }
     }
 
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn start_dma_transfer(
-        &mut self,
-        ops: &GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
-        mut address: u16,
-        now: u64,
-    ) {
-        if self.dma_transfer.is_some() {
-            // XXX: I'm not sure what is suppose to happen in this case.
+    /// HALT doesn't wait if an interrupt is already pending. With IME set the interrupt is
+    /// dispatched right away, otherwise the CPU carries on but runs into the HALT bug.
+    #[cold]
+    fn enter_halt_mode(&mut self, interrupts_enabled: bool) {
+        let interrupts = self.bridge.registers.interrupt_flag.read_value();
+        let interrupts_mask = self.bridge.registers.interrupt_enable_mask.read_value();
+        if interrupts & interrupts_mask == 0 {
             return;
         }
-        // 0xE000 to 0xFFFF is mapped differently for DMA. It ends up just being the internal
-        // ram repeated again. To account for this we just adjust the source address.
-        if address >= INTERNAL_RAM_B.end {
-            address -= 0x2000;
-        }
-        self.dma_transfer = Some(OamDmaTransfer::new(address.., now));
-        self.bridge.lcd_controller.oam_data.borrow();
 
-        self.drive_dma_transfer(ops, now);
+        if interrupts_enabled {
+            let scheduler = &mut self.bridge.scheduler;
+            scheduler.schedule(scheduler.now(), GameBoyEmulatorEvent::HandleInterrupts);
+        } else {
+            self.cpu.trigger_halt_bug();
+        }
     }
 
-    #[cfg_attr(feature = "aggressive-inline", inline(always))]
-    fn drive_dma_transfer(
-        &mut self,
-        ops: &GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
-        now: u64,
-    ) {
-        let transfer = self.dma_transfer.as_mut().unwrap();
+    /// STOP puts the Game Boy in a low-power mode. The LCD is switched off and the divider is
+    /// reset and stops counting, along with the timer which is driven by it.
+    #[cold]
+    fn enter_stop_mode(&mut self) {
+        let scheduler = &mut self.bridge.scheduler;
+        self.bridge.timer.stop(scheduler);
+        self.bridge.lcd_controller.stop(scheduler);
+    }
 
-        for _ in 0..transfer.bytes_to_transfer(now) {
-            transfer.read(&ops.memory_map(&self.bridge));
-            transfer.write(&mut self.bridge.lcd_controller.oam_data);
+    #[cold]
+    fn leave_stop_mode(&mut self, time: u64) {
+        self.cpu.wake();
 
-            if transfer.is_done() {
-                self.dma_transfer = None;
-                self.bridge.lcd_controller.oam_data.release();
-                return;
-            }
-        }
-        self.bridge
-            .scheduler
-            .schedule(now + 4, GameBoyEmulatorEvent::DriveDmaTransfer);
+        let scheduler = &mut self.bridge.scheduler;
+        self.bridge.timer.resume(scheduler, time);
+        self.bridge.lcd_controller.resume(scheduler);
+        scheduler.schedule(time, GameBoyEmulatorEvent::HandleInterrupts);
     }
 
     /// If the stack happens to overflow into the IO registers, it can cause weird behavior when
@@ -975,7 +1039,7 @@ impl GameBoyEmulator {
         flag: InterruptFlag,
         address: u16,
     ) {
-        let pc = self.cpu.read_program_counter();
+        let pc = self.cpu.interrupt_return_address();
 
         self.cpu
             .push_u16_onto_stack(&mut ops.memory_map_mut(&mut self.bridge), pc);
@@ -1023,6 +1087,11 @@ impl GameBoyEmulator {
         &mut self,
         ops: &mut GameBoyOps<impl Renderer, impl SoundStream, impl PersistentStorage>,
     ) {
+        // Only a button press wakes the CPU from STOP, it checks for interrupts again when it does.
+        if self.cpu.is_stopped() {
+            return;
+        }
+
         let interrupts = self.bridge.registers.interrupt_flag.read_value();
         let interrupts_mask = self.bridge.registers.interrupt_enable_mask.read_value();
 
@@ -1042,35 +1111,41 @@ impl GameBoyEmulator {
         }
     }
 
-    fn set_state_post_bios(&mut self) {
+    /// Puts everything in the state it is in when the Game Boy is switched on, with the given
+    /// boot ROM mapped in.
+    fn set_state_power_on(&mut self, boot_rom: BootRom) {
+        self.cpu.elapsed_cycles = 0;
+        self.cpu.set_register_pair(Intel8080Register::SP, 0x0);
+        self.cpu.set_program_counter(0x0);
+
+        self.bridge.lcd_controller.set_state_power_on();
+
+        self.bridge.registers.interrupt_flag.set_value(0xe1);
+
+        self.bridge.boot_rom.map(boot_rom);
+    }
+
+    fn set_state_post_bios(&mut self, model: GameBoyModel) {
         self.bridge.sound_controller.set_state_post_bios();
         self.bridge.lcd_controller.set_state_post_bios();
 
         /*
          * After running the BIOS (the part of the gameboy that shows the logo) the cpu is left in
          * a very certain state. Since this is always the case, certain games may rely on this fact
-         * (and indeed often times do.)
+         * (and indeed often times do.) The exact values differ between the models.
          */
-        self.cpu.set_register(Intel8080Register::A, 0x1);
-        self.cpu.set_register(Intel8080Register::B, 0x0);
-        self.cpu.set_register(Intel8080Register::C, 0x13);
-        self.cpu.set_register(Intel8080Register::D, 0x0);
-        self.cpu.set_register(Intel8080Register::E, 0xD8);
-        self.cpu.set_register(Intel8080Register::H, 0x01);
-        self.cpu.set_register(Intel8080Register::L, 0x4D);
-        self.cpu.set_flag(LR35902Flag::Carry, true);
-        self.cpu.set_flag(LR35902Flag::HalfCarry, true);
-        self.cpu.set_flag(LR35902Flag::Subtract, false);
-        self.cpu.set_flag(LR35902Flag::Zero, true);
-
-        self.bridge.registers.serial_transfer_data.set_value(0x0);
-        self.bridge
-            .registers
-            .serial_transfer_control
-            .set_value(0x7e);
+        let [af, bc, de, hl] = model.post_boot_registers();
+        // The A register pair is A and FLAGS
+        self.cpu.set_register_pair(Intel8080Register::A, af);
+        self.cpu.set_register_pair(Intel8080Register::B, bc);
+        self.cpu.set_register_pair(Intel8080Register::D, de);
+        self.cpu.set_register_pair(Intel8080Register::H, hl);
+
+        self.bridge.serial.set_state_post_bios();
 
-        self.bridge.registers.divider.set_state_post_bios();
-        self.bridge.timer.set_state_post_bios();
+        self.bridge
+            .timer
+            .set_state_post_bios(self.cpu.elapsed_cycles);
 
         self.bridge.registers.interrupt_flag.set_value(0xe1);
 
@@ -1096,9 +1171,7 @@ impl GameBoyEmulator {
 
     fn schedule_initial_events(&mut self) {
         let now = self.cpu.elapsed_cycles;
-        self.bridge
-            .scheduler
-            .schedule(now + 52, GameBoyEmulatorEvent::DividerTick);
+        self.bridge.scheduler.advance(now);
         self.bridge
             .scheduler
             .schedule(now + 456, GameBoyEmulatorEvent::DriveJoypad);
@@ -1112,6 +1185,9 @@ impl GameBoyEmulator {
         self.bridge
             .sound_controller
             .schedule_initial_events(&mut self.bridge.scheduler, now);
+        self.bridge
+            .serial
+            .schedule_initial_events(&mut self.bridge.scheduler, now);
     }
 
     pub fn elapsed_cycles(&self) -> u64 {
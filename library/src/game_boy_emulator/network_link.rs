@@ -0,0 +1,422 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::{Result, SerialDevice};
+use alloc::collections::VecDeque;
+use alloc::vec;
+use serde_derive::{Deserialize, Serialize};
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Changes whenever the messages change in an incompatible way.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Every message is tiny, anything bigger than this means the other end isn't talking our
+/// protocol.
+const MAX_FRAME_LENGTH: u32 = 64;
+
+/// If the other side doesn't say anything for this long while we are waiting for it, the link is
+/// given up on.
+const PEER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// By default the two Game Boys are allowed to drift apart by a frame.
+pub const DEFAULT_LINK_SYNC_CYCLES: u64 = 70224;
+
+#[derive(Debug)]
+pub enum LinkError {
+    VersionMismatch { ours: u32, theirs: u32 },
+    RomMismatch { ours: u32, theirs: u32 },
+    FrameTooLong(u32),
+    UnexpectedMessage,
+}
+
+/// Each message is sent as a little-endian u32 length followed by that many bytes of payload.
+#[derive(Debug, Serialize, Deserialize)]
+enum Message {
+    Hello {
+        version: u32,
+        rom_hash: u32,
+    },
+    /// The sender's clock.
+    Sync {
+        now: u64,
+    },
+    /// At the given time, the sender started waiting for the clock with the given byte ready, or
+    /// stopped waiting.
+    Waiting {
+        now: u64,
+        value: Option<u8>,
+    },
+    /// The sender clocked a transfer, shifting out the given byte.
+    Transfer {
+        value: u8,
+    },
+}
+
+fn write_message(w: &mut impl io::Write, message: &Message) -> Result<()> {
+    let payload = crate::codec::serialize(message)?;
+    w.write_all(&(payload.len() as u32).to_le_bytes())?;
+    w.write_all(&payload)?;
+    Ok(())
+}
+
+fn read_message(r: &mut impl io::Read) -> Result<Message> {
+    let mut length = [0; 4];
+    r.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length);
+    if length > MAX_FRAME_LENGTH {
+        return Err(LinkError::FrameTooLong(length).into());
+    }
+
+    let mut payload = vec![0; length as usize];
+    r.read_exact(&mut payload)?;
+    Ok(crate::codec::deserialize(&payload)?)
+}
+
+/// A link cable to another emulator over TCP.
+///
+/// Both sides report their clock to each other as they run, and stop to let the other side catch
+/// up when they get more than `sync_cycles` cycles ahead. A larger window hides more network
+/// latency, a smaller one keeps the two Game Boys closer together.
+///
+/// Transfers are exact. Before clocking a transfer, the side driving the clock waits until the
+/// other side has run at least as far, so it knows whether that side was waiting for the clock at
+/// that moment. Likewise the side waiting for the clock doesn't run ahead of the other side. This
+/// means the two run in lockstep while the link is in use, so it works best with low latency. Each
+/// side counts its clock from when it first uses the cable, so the two emulators don't need to
+/// have started at the same time.
+pub struct NetworkLinkCable {
+    stream: TcpStream,
+    incoming: mpsc::Receiver<Message>,
+    connected: bool,
+    sync_cycles: u64,
+    /// Our clock when we first used the cable. The clocks sent over the link count from here.
+    start: Option<u64>,
+    /// The last clock we told the other side about.
+    sent_now: u64,
+    peer_now: u64,
+    /// The byte the other side is waiting with, if it is waiting for our clock.
+    peer_waiting: Option<u8>,
+    /// Changes to `peer_waiting` we know about, but that happened after our current time.
+    peer_waiting_changes: VecDeque<(u64, Option<u8>)>,
+    /// The byte we last told the other side we are waiting with.
+    waiting: Option<u8>,
+    /// A byte clocked in by the other side, it is picked up the next time we poll.
+    delivered: Option<u8>,
+}
+
+impl NetworkLinkCable {
+    /// Listens on the given address and waits for the other side to connect.
+    pub fn listen(address: impl ToSocketAddrs, rom_hash: u32, sync_cycles: u64) -> Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        Self::accept(&listener, rom_hash, sync_cycles)
+    }
+
+    pub fn accept(listener: &TcpListener, rom_hash: u32, sync_cycles: u64) -> Result<Self> {
+        log::info!("Waiting for link cable on {}", listener.local_addr()?);
+        let (stream, peer) = listener.accept()?;
+        log::info!("Link cable connected to {peer}");
+        Self::new(stream, rom_hash, sync_cycles)
+    }
+
+    pub fn connect(address: impl ToSocketAddrs, rom_hash: u32, sync_cycles: u64) -> Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        log::info!("Link cable connected to {}", stream.peer_addr()?);
+        Self::new(stream, rom_hash, sync_cycles)
+    }
+
+    /// Both sides have to be playing the same game, so the handshake fails if the ROM hashes don't
+    /// match.
+    fn new(mut stream: TcpStream, rom_hash: u32, sync_cycles: u64) -> Result<Self> {
+        stream.set_nodelay(true)?;
+
+        let hello = Message::Hello {
+            version: PROTOCOL_VERSION,
+            rom_hash,
+        };
+        write_message(&mut stream, &hello)?;
+        match read_message(&mut stream)? {
+            Message::Hello { version, .. } if version != PROTOCOL_VERSION => {
+                return Err(LinkError::VersionMismatch {
+                    ours: PROTOCOL_VERSION,
+                    theirs: version,
+                }
+                .into());
+            }
+            Message::Hello {
+                rom_hash: theirs, ..
+            } if theirs != rom_hash => {
+                return Err(LinkError::RomMismatch {
+                    ours: rom_hash,
+                    theirs,
+                }
+                .into());
+            }
+            Message::Hello { .. } => {}
+            _ => return Err(LinkError::UnexpectedMessage.into()),
+        }
+
+        let (sender, incoming) = mpsc::channel();
+        let mut reader = stream.try_clone()?;
+        thread::spawn(move || loop {
+            match read_message(&mut reader) {
+                Ok(message) => {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    log::debug!("Link cable stopped reading: {e:?}");
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            stream,
+            incoming,
+            connected: true,
+            sync_cycles,
+            start: None,
+            sent_now: 0,
+            peer_now: 0,
+            peer_waiting: None,
+            peer_waiting_changes: VecDeque::new(),
+            waiting: None,
+            delivered: None,
+        })
+    }
+
+    /// Converts our clock into the one we share with the other side.
+    fn link_time(&mut self, now: u64) -> u64 {
+        now - *self.start.get_or_insert(now)
+    }
+
+    fn disconnect(&mut self) {
+        if self.connected {
+            log::info!("Link cable disconnected");
+        }
+        self.connected = false;
+        self.peer_waiting = None;
+        self.peer_waiting_changes.clear();
+    }
+
+    fn send(&mut self, message: Message) {
+        if !self.connected {
+            return;
+        }
+        if let Message::Sync { now } | Message::Waiting { now, .. } = message {
+            self.sent_now = now;
+        }
+        if let Err(e) = write_message(&mut self.stream, &message) {
+            log::debug!("Failed to send over link cable: {e:?}");
+            self.disconnect();
+        }
+    }
+
+    fn handle(&mut self, message: Message) {
+        match message {
+            Message::Sync { now } => self.peer_now = now,
+            Message::Waiting { now, value } => {
+                self.peer_now = now;
+                self.peer_waiting_changes.push_back((now, value));
+            }
+            // If we stopped waiting before it arrived, the transfer is lost.
+            Message::Transfer { value } => {
+                if self.waiting.is_some() {
+                    self.delivered = Some(value);
+                }
+            }
+            Message::Hello { .. } => log::warn!("Unexpected link cable message {message:?}"),
+        }
+    }
+
+    fn receive(&mut self) {
+        loop {
+            match self.incoming.try_recv() {
+                Ok(message) => self.handle(message),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.disconnect();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Tells the other side where we are, then waits until it is no more than `behind` cycles
+    /// behind us. Afterwards `peer_waiting` is up to date with everything we heard about up to
+    /// now.
+    fn wait_for_peer(&mut self, now: u64, behind: u64) {
+        if now > self.sent_now {
+            self.send(Message::Sync { now });
+        }
+        self.receive();
+
+        // Since we just told the other side where we are, it can't also be waiting on us.
+        while self.connected && now > self.peer_now + behind {
+            match self.incoming.recv_timeout(PEER_TIMEOUT) {
+                Ok(message) => self.handle(message),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    log::error!("Link cable timed out waiting for the other side");
+                    self.disconnect();
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => self.disconnect(),
+            }
+        }
+
+        while let Some(&(when, waiting)) = self.peer_waiting_changes.front() {
+            if when > now {
+                break;
+            }
+            self.peer_waiting = waiting;
+            self.peer_waiting_changes.pop_front();
+        }
+    }
+}
+
+impl Drop for NetworkLinkCable {
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+impl SerialDevice for NetworkLinkCable {
+    fn transfer_as_master(&mut self, now: u64, value: u8) -> u8 {
+        let now = self.link_time(now);
+        self.wait_for_peer(now, 0);
+        match self.peer_waiting.take() {
+            Some(incoming) => {
+                self.send(Message::Transfer { value });
+                incoming
+            }
+            None => 0xFF,
+        }
+    }
+
+    fn poll_as_slave(&mut self, now: u64, value: u8) -> Option<u8> {
+        let now = self.link_time(now);
+        if self.waiting != Some(value) {
+            self.waiting = Some(value);
+            self.send(Message::Waiting {
+                now,
+                value: Some(value),
+            });
+        }
+
+        // Any transfer the other side clocked before now has to have arrived.
+        self.wait_for_peer(now, 0);
+        let incoming = self.delivered.take();
+        if incoming.is_some() {
+            self.waiting = None;
+        }
+        incoming
+    }
+
+    fn stop_waiting(&mut self, now: u64) {
+        let now = self.link_time(now);
+        self.delivered = None;
+        if self.waiting.take().is_some() {
+            self.send(Message::Waiting { now, value: None });
+        }
+    }
+
+    fn tick(&mut self, now: u64) {
+        let now = self.link_time(now);
+        self.wait_for_peer(now, self.sync_cycles);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::link_cable::tests::{exchange_rom, game_boy, received};
+    use crate::game_boy_emulator::Error;
+
+    const SYNC_CYCLES: u64 = 4096;
+
+    fn connected_pair(
+        hash_a: u32,
+        hash_b: u32,
+    ) -> (Result<NetworkLinkCable>, Result<NetworkLinkCable>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || NetworkLinkCable::connect(address, hash_b, SYNC_CYCLES));
+        let server = NetworkLinkCable::accept(&listener, hash_a, SYNC_CYCLES);
+        (server, client.join().unwrap())
+    }
+
+    fn run_exchange(link: NetworkLinkCable, serial_control: u8, base: u8) -> [u8; 3] {
+        let (mut e, mut ops) = game_boy(&exchange_rom(serial_control, base));
+        ops.plug_in_serial_device(link);
+
+        let end = e.elapsed_cycles() + 100_000;
+        while e.elapsed_cycles() < end {
+            e.tick(&mut ops);
+        }
+        assert_eq!(e.cpu.read_program_counter(), 0x0150);
+        received(&e, &ops)
+    }
+
+    #[test]
+    fn network_link_exchanges_bytes() {
+        let (server, client) = connected_pair(0x1234, 0x1234);
+        let (server, client) = (server.unwrap(), client.unwrap());
+
+        let slave = thread::spawn(move || run_exchange(client, 0x80, 0x20));
+        let master = run_exchange(server, 0x81, 0x10);
+
+        assert_eq!(master, [0x23, 0x22, 0x21]);
+        assert_eq!(slave.join().unwrap(), [0x13, 0x12, 0x11]);
+    }
+
+    #[test]
+    fn network_link_clocks_start_apart() {
+        let (server, client) = connected_pair(0x1234, 0x1234);
+        let (mut server, mut client) = (server.unwrap(), client.unwrap());
+
+        let slave = thread::spawn(move || {
+            (0..100).find_map(|step| client.poll_as_slave(500 + step * 4, 0x42))
+        });
+
+        server.tick(1_000_000);
+        let master = server.transfer_as_master(1_000_008, 0x99);
+        for step in 0..100 {
+            server.tick(1_000_008 + step * 4);
+        }
+
+        assert_eq!(master, 0x42);
+        assert_eq!(slave.join().unwrap(), Some(0x99));
+    }
+
+    #[test]
+    fn network_link_rom_mismatch() {
+        let (server, client) = connected_pair(0x1234, 0x5678);
+        assert!(matches!(
+            server,
+            Err(Error::Link(LinkError::RomMismatch {
+                ours: 0x1234,
+                theirs: 0x5678
+            }))
+        ));
+        assert!(matches!(
+            client,
+            Err(Error::Link(LinkError::RomMismatch {
+                ours: 0x5678,
+                theirs: 0x1234
+            }))
+        ));
+    }
+
+    #[test]
+    fn network_link_peer_disconnects() {
+        let (server, client) = connected_pair(0x1234, 0x1234);
+        drop(client);
+
+        // With nobody on the other end, nothing is shifted in and the clock doesn't wait.
+        let master = run_exchange(server.unwrap(), 0x81, 0x10);
+        assert_eq!(master, [0xFF, 0xFF, 0xFF]);
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright 2026 Remi Bernotavicius
+
+//! OAM DMA copies 160 bytes into OAM, one byte every M-cycle. The transfer starts two M-cycles
+//! after the DMA register is written. Writing the register again while a transfer is running
+//! restarts it, but the old transfer carries on until the new one starts.
+//!
+//! While a transfer is running it has the bus it reads from to itself, and OAM along with it. The
+//! CPU reading from that bus sees the byte being transferred instead, and its writes go nowhere.
+//! OAM reads as 0xFF. Only the other bus, the IO registers and high RAM work as usual, which is
+//! why games run the transfer from high RAM.
+
+use super::lcd_controller::OAM_DATA;
+use super::memory_controller::{
+    GameBoyMemoryMap, MemoryAccessor, MemoryChunk, MemoryMappedHardware,
+};
+use crate::storage::PersistentStorage;
+use core::ops::Range;
+use serde_derive::{Deserialize, Serialize};
+
+/// How long after the DMA register is written the transfer starts.
+pub(super) const STARTUP_DELAY: u64 = 8;
+
+const VIDEO_RAM: Range<u16> = 0x8000..0xA000;
+
+/// OAM and the unusable memory after it.
+const OAM_BUS: Range<u16> = 0xFE00..0xFF00;
+
+/// The memory buses a transfer can read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Bus {
+    /// The cartridge and internal RAM.
+    External,
+    Video,
+}
+
+impl Bus {
+    fn for_address(address: u16) -> Option<Self> {
+        if VIDEO_RAM.contains(&address) {
+            Some(Self::Video)
+        } else if address < OAM_BUS.start {
+            Some(Self::External)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct OamDmaTransfer {
+    src_current: u16,
+    dst_current: u16,
+    value: u8,
+    bus: Bus,
+}
+
+impl OamDmaTransfer {
+    /// The source address is expected to already have 0xE000 and up mapped to internal RAM.
+    pub(super) fn new(src: u16) -> Self {
+        Self {
+            src_current: src,
+            dst_current: 0,
+            value: 0xFF,
+            bus: Bus::for_address(src).unwrap(),
+        }
+    }
+
+    pub(super) fn read(&mut self, memory_map: &GameBoyMemoryMap<'_, impl PersistentStorage>) {
+        self.value = memory_map.read_memory(self.src_current);
+        self.src_current = self.src_current.wrapping_add(1);
+    }
+
+    pub(super) fn write(&mut self, oam: &mut MemoryChunk) {
+        oam.as_mut_slice()[self.dst_current as usize] = self.value;
+        self.dst_current += 1;
+    }
+
+    pub(super) fn is_done(&self) -> bool {
+        self.dst_current == (OAM_DATA.end - OAM_DATA.start)
+    }
+}
+
+impl MemoryMappedHardware for OamDmaTransfer {
+    fn read_value(&self, _address: u16) -> u8 {
+        unreachable!()
+    }
+
+    fn set_value(&mut self, _address: u16, _value: u8) {
+        unreachable!()
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn bus_conflict(&self, address: u16) -> Option<u8> {
+        if OAM_BUS.contains(&address) {
+            Some(0xFF)
+        } else if Bus::for_address(address) == Some(self.bus) {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{GameBoyEmulator, NullGameBoyOps, NullPerfObserver};
+
+    #[test]
+    fn transfer_from_internal_ram_conflicts_with_external_bus() {
+        let mut transfer = OamDmaTransfer::new(0xC000);
+        transfer.value = 0x12;
+        assert_eq!(transfer.bus_conflict(0x0150), Some(0x12));
+        assert_eq!(transfer.bus_conflict(0xC000), Some(0x12));
+        assert_eq!(transfer.bus_conflict(0xFE00), Some(0xFF));
+        assert_eq!(transfer.bus_conflict(0x8000), None);
+        assert_eq!(transfer.bus_conflict(0xFF44), None);
+        assert_eq!(transfer.bus_conflict(0xFF80), None);
+    }
+
+    #[test]
+    fn transfer_from_video_ram_conflicts_with_video_bus() {
+        let mut transfer = OamDmaTransfer::new(0x8000);
+        transfer.value = 0x12;
+        assert_eq!(transfer.bus_conflict(0x9800), Some(0x12));
+        assert_eq!(transfer.bus_conflict(0xFE9F), Some(0xFF));
+        assert_eq!(transfer.bus_conflict(0x0150), None);
+        assert_eq!(transfer.bus_conflict(0xC000), None);
+    }
+
+    fn source_value(source: u16, index: u16) -> u8 {
+        ((source >> 8) as u8) ^ index as u8
+    }
+
+    fn set_up() -> (GameBoyEmulator, NullGameBoyOps) {
+        let mut e = GameBoyEmulator::new();
+        let mut ops = NullGameBoyOps::null();
+        let mut memory_map = ops.memory_map_mut(&mut e.bridge);
+        for source in [0xC000, 0xC100] {
+            for index in 0..0xA0 {
+                memory_map.set_memory(source + index, source_value(source, index));
+            }
+        }
+        (e, ops)
+    }
+
+    fn start(e: &mut GameBoyEmulator, ops: &mut NullGameBoyOps, source: u16, now: u64) {
+        run_until(e, ops, now);
+        ops.memory_map_mut(&mut e.bridge)
+            .set_memory(0xFF46, (source >> 8) as u8);
+    }
+
+    fn run_until(e: &mut GameBoyEmulator, ops: &mut NullGameBoyOps, now: u64) {
+        let interrupts_ok = false;
+        e.deliver_events(ops, &mut NullPerfObserver, interrupts_ok, now);
+    }
+
+    fn read(e: &GameBoyEmulator, ops: &NullGameBoyOps, address: u16) -> u8 {
+        ops.memory_map(&e.bridge).read_memory(address)
+    }
+
+    #[test]
+    fn transfer_timing() {
+        let (mut e, mut ops) = set_up();
+        let now = e.elapsed_cycles();
+        start(&mut e, &mut ops, 0xC000, now);
+
+        run_until(&mut e, &mut ops, now + 4);
+        assert_eq!(read(&e, &ops, 0xC010), source_value(0xC000, 0x10));
+
+        for index in [0, 1, 0x9F] {
+            run_until(&mut e, &mut ops, now + STARTUP_DELAY + index * 4);
+            assert_eq!(read(&e, &ops, 0x0000), source_value(0xC000, index as u16));
+            assert_eq!(read(&e, &ops, 0xFE00), 0xFF);
+        }
+
+        run_until(&mut e, &mut ops, now + STARTUP_DELAY + 0xA0 * 4);
+        assert_eq!(read(&e, &ops, 0xC010), source_value(0xC000, 0x10));
+        let oam = e.bridge.lcd_controller.oam_data.as_slice();
+        for index in 0..0xA0 {
+            assert_eq!(oam[index as usize], source_value(0xC000, index));
+        }
+    }
+
+    #[test]
+    fn restart_takes_over_after_delay() {
+        let (mut e, mut ops) = set_up();
+        let now = e.elapsed_cycles();
+        start(&mut e, &mut ops, 0xC000, now);
+
+        let restart = now + STARTUP_DELAY + 0x20 * 4;
+        start(&mut e, &mut ops, 0xC100, restart);
+
+        run_until(&mut e, &mut ops, restart + 4);
+        assert_eq!(read(&e, &ops, 0x0000), source_value(0xC000, 0x21));
+
+        run_until(&mut e, &mut ops, restart + STARTUP_DELAY);
+        assert_eq!(read(&e, &ops, 0x0000), source_value(0xC100, 0));
+
+        run_until(&mut e, &mut ops, restart + STARTUP_DELAY + 0x9F * 4);
+        assert_eq!(read(&e, &ops, 0xFE00), 0xFF);
+
+        run_until(&mut e, &mut ops, restart + STARTUP_DELAY + 0xA0 * 4);
+        let oam = e.bridge.lcd_controller.oam_data.as_slice();
+        for index in 0..0xA0 {
+            assert_eq!(oam[index as usize], source_value(0xC100, index));
+        }
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::SerialDevice;
+use crate::storage::{OpenMode, PersistentStorage};
+use alloc::{format, string::String, vec, vec::Vec};
+use bmp::{Image, Pixel};
+use num_enum::IntoPrimitive;
+
+/// The paper is as wide as the screen.
+pub const PRINTER_WIDTH: usize = 160;
+
+const TILES_PER_ROW: usize = PRINTER_WIDTH / 8;
+
+const TILE_BYTES: usize = 16;
+
+/// The most image data the printer can hold on to before it has to print, 9 rows of tiles.
+const BUFFER_SIZE: usize = TILES_PER_ROW * TILE_BYTES * 2 * 9;
+
+/// How many pixels of paper are fed for each unit of margin.
+const MARGIN_LINE_PIXELS: usize = 8;
+
+/// How many status requests the printer reports being busy for after it starts printing.
+const BUSY_STATUS_REQUESTS: u8 = 3;
+
+const MAGIC: [u8; 2] = [0x88, 0x33];
+
+/// Sent back while the Game Boy clocks out the first byte after a packet.
+const ALIVE: u8 = 0x81;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Initialize = 0x01,
+    Print = 0x02,
+    Data = 0x04,
+    Status = 0x0F,
+}
+
+impl Command {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x01 => Some(Self::Initialize),
+            0x02 => Some(Self::Print),
+            0x04 => Some(Self::Data),
+            0x0F => Some(Self::Status),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, IntoPrimitive)]
+#[repr(u8)]
+enum PrinterStatus {
+    ChecksumError = 0b00000001,
+    Busy = 0b00000010,
+    ImageDataFull = 0b00000100,
+    UnprocessedData = 0b00001000,
+    PacketError = 0b00010000,
+}
+
+/// Where we are in receiving a packet. Each packet looks like
+///
+/// | magic (2) | command | compression | length (2) | data (length) | checksum (2) | alive | status |
+///
+/// The length and checksum are little-endian, and the checksum is the sum of everything from the
+/// command to the end of the data. The Game Boy sends zero for the last two bytes, and gets back
+/// `ALIVE` and the status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PacketState {
+    Magic(usize),
+    Command,
+    Compression,
+    LengthLow,
+    LengthHigh,
+    Data,
+    ChecksumLow,
+    ChecksumHigh,
+    Alive,
+    Status,
+}
+
+#[derive(Default)]
+struct Packet {
+    command: u8,
+    compressed: bool,
+    length: u16,
+    data: Vec<u8>,
+    checksum: u16,
+    sum: u16,
+}
+
+impl Packet {
+    fn add_to_sum(&mut self, value: u8) {
+        self.sum = self.sum.wrapping_add(value.into());
+    }
+}
+
+/// Undoes the printer's run-length encoding. Each run starts with a byte, if the top bit is set
+/// the next byte is repeated (low bits + 2) times, otherwise the next (low bits + 1) bytes are
+/// copied as they are.
+fn decompress(data: &[u8]) -> Vec<u8> {
+    let mut output = vec![];
+    let mut iter = data.iter().copied();
+    while let Some(control) = iter.next() {
+        if control & 0x80 != 0 {
+            let count = (control & 0x7F) as usize + 2;
+            if let Some(value) = iter.next() {
+                output.extend(core::iter::repeat_n(value, count));
+            }
+        } else {
+            let count = control as usize + 1;
+            output.extend(iter.by_ref().take(count));
+        }
+    }
+    output
+}
+
+/// Turns tile data into rows of shades, where 0 is white and 3 is black.
+fn render_tiles(data: &[u8], palette: u8) -> Vec<u8> {
+    // Some games send a palette of zero, which the printer treats as the normal palette.
+    let palette = if palette == 0 { 0xE4 } else { palette };
+
+    let tile_rows = data.len() / (TILES_PER_ROW * TILE_BYTES);
+    let mut shades = vec![0; tile_rows * 8 * PRINTER_WIDTH];
+    for (tile_index, tile) in data.chunks_exact(TILE_BYTES).enumerate() {
+        let tile_x = tile_index % TILES_PER_ROW;
+        let tile_y = tile_index / TILES_PER_ROW;
+        if tile_y >= tile_rows {
+            break;
+        }
+        for (line, bytes) in tile.chunks_exact(2).enumerate() {
+            for bit in 0..8 {
+                let low = (bytes[0] >> (7 - bit)) & 1;
+                let high = (bytes[1] >> (7 - bit)) & 1;
+                let color = high << 1 | low;
+                let y = tile_y * 8 + line;
+                let x = tile_x * 8 + bit;
+                shades[y * PRINTER_WIDTH + x] = (palette >> (color * 2)) & 0x3;
+            }
+        }
+    }
+    shades
+}
+
+/// The Game Boy Printer, plugged in to the link port. Each time paper is fed out after printing,
+/// what was printed is saved as a BMP image to the storage key `<output_prefix>_<n>.bmp`. Paper
+/// still in the printer is saved when the printer is dropped.
+///
+/// The exposure setting of the print command is ignored.
+pub struct GameBoyPrinter<Storage: PersistentStorage> {
+    storage: Storage,
+    output_prefix: String,
+    prints_saved: usize,
+    state: PacketState,
+    packet: Packet,
+    /// Image data received but not printed yet.
+    buffer: Vec<u8>,
+    /// Paper that has been printed on but not fed out yet, as rows of shades.
+    paper: Vec<u8>,
+    checksum_error: bool,
+    packet_error: bool,
+    busy: u8,
+}
+
+impl<Storage: PersistentStorage> GameBoyPrinter<Storage> {
+    pub fn new(storage: Storage, output_prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            output_prefix: output_prefix.into(),
+            prints_saved: 0,
+            state: PacketState::Magic(0),
+            packet: Packet::default(),
+            buffer: vec![],
+            paper: vec![],
+            checksum_error: false,
+            packet_error: false,
+            busy: 0,
+        }
+    }
+
+    /// How many prints have been saved so far.
+    pub fn prints_saved(&self) -> usize {
+        self.prints_saved
+    }
+
+    fn status(&self) -> u8 {
+        let mut status = 0;
+        let mut set = |flag: PrinterStatus, value: bool| {
+            if value {
+                status |= u8::from(flag);
+            }
+        };
+        set(PrinterStatus::ChecksumError, self.checksum_error);
+        set(PrinterStatus::Busy, self.busy > 0);
+        set(PrinterStatus::ImageDataFull, self.busy > 0);
+        set(PrinterStatus::UnprocessedData, !self.buffer.is_empty());
+        set(PrinterStatus::PacketError, self.packet_error);
+        status
+    }
+
+    fn feed(&mut self, lines: u8) {
+        let rows = lines as usize * MARGIN_LINE_PIXELS;
+        self.paper
+            .extend(core::iter::repeat_n(0, rows * PRINTER_WIDTH));
+    }
+
+    fn print(&mut self, data: &[u8]) {
+        let &[sheets, margins, palette, _exposure] = data else {
+            self.packet_error = true;
+            return;
+        };
+
+        let image = render_tiles(&self.buffer, palette);
+        self.buffer.clear();
+
+        self.feed(margins >> 4);
+        for _ in 0..sheets {
+            self.paper.extend_from_slice(&image);
+        }
+        self.feed(margins & 0xF);
+
+        // Without a margin after, the next print continues on the same piece of paper.
+        if margins & 0xF != 0 {
+            self.save_paper();
+        }
+        self.busy = BUSY_STATUS_REQUESTS;
+    }
+
+    fn save_paper(&mut self) {
+        let rows = self.paper.len() / PRINTER_WIDTH;
+        if rows == 0 {
+            return;
+        }
+
+        let mut image = Image::new(PRINTER_WIDTH as u32, rows as u32);
+        for (y, row) in self.paper.chunks_exact(PRINTER_WIDTH).enumerate() {
+            for (x, &shade) in row.iter().enumerate() {
+                let value = 0xFF - shade * 0x55;
+                image.set_pixel(x as u32, y as u32, Pixel::new(value, value, value));
+            }
+        }
+        self.paper.clear();
+
+        let key = format!("{}_{}.bmp", self.output_prefix, self.prints_saved);
+        let result = self
+            .storage
+            .open(OpenMode::Write, &key)
+            .and_then(|mut file| image.to_writer(&mut file));
+        match result {
+            Ok(()) => log::info!("Saved print to {key:?}"),
+            Err(e) => log::warn!("Failed to save print to {key:?}: {e:?}"),
+        }
+        self.prints_saved += 1;
+    }
+
+    fn process_packet(&mut self) {
+        let packet = core::mem::take(&mut self.packet);
+        self.checksum_error = packet.checksum != packet.sum;
+        if self.checksum_error {
+            return;
+        }
+
+        let data = if packet.compressed {
+            decompress(&packet.data)
+        } else {
+            packet.data
+        };
+
+        self.packet_error = false;
+        match Command::from_u8(packet.command) {
+            Some(Command::Initialize) => {
+                self.buffer.clear();
+                self.busy = 0;
+            }
+            Some(Command::Print) => self.print(&data),
+            Some(Command::Data) => {
+                let space = BUFFER_SIZE - self.buffer.len();
+                self.buffer
+                    .extend_from_slice(&data[..data.len().min(space)]);
+            }
+            Some(Command::Status) => self.busy = self.busy.saturating_sub(1),
+            None => self.packet_error = true,
+        }
+    }
+
+    fn receive(&mut self, value: u8) -> u8 {
+        let packet = &mut self.packet;
+        let (next, response) = match self.state {
+            PacketState::Magic(i) if value == MAGIC[i] => {
+                if i + 1 == MAGIC.len() {
+                    (PacketState::Command, 0)
+                } else {
+                    (PacketState::Magic(i + 1), 0)
+                }
+            }
+            // Anything else starts looking for the beginning of a packet again.
+            PacketState::Magic(_) => (PacketState::Magic(usize::from(value == MAGIC[0])), 0),
+            PacketState::Command => {
+                packet.command = value;
+                packet.add_to_sum(value);
+                (PacketState::Compression, 0)
+            }
+            PacketState::Compression => {
+                packet.compressed = value != 0;
+                packet.add_to_sum(value);
+                (PacketState::LengthLow, 0)
+            }
+            PacketState::LengthLow => {
+                packet.length = value.into();
+                packet.add_to_sum(value);
+                (PacketState::LengthHigh, 0)
+            }
+            PacketState::LengthHigh => {
+                packet.length |= u16::from(value) << 8;
+                packet.add_to_sum(value);
+                if packet.length == 0 {
+                    (PacketState::ChecksumLow, 0)
+                } else {
+                    (PacketState::Data, 0)
+                }
+            }
+            PacketState::Data => {
+                packet.data.push(value);
+                packet.add_to_sum(value);
+                if packet.data.len() == packet.length as usize {
+                    (PacketState::ChecksumLow, 0)
+                } else {
+                    (PacketState::Data, 0)
+                }
+            }
+            PacketState::ChecksumLow => {
+                packet.checksum = value.into();
+                (PacketState::ChecksumHigh, 0)
+            }
+            PacketState::ChecksumHigh => {
+                packet.checksum |= u16::from(value) << 8;
+                self.process_packet();
+                (PacketState::Alive, 0)
+            }
+            PacketState::Alive => (PacketState::Status, ALIVE),
+            PacketState::Status => (PacketState::Magic(0), self.status()),
+        };
+        self.state = next;
+        response
+    }
+}
+
+impl<Storage: PersistentStorage> Drop for GameBoyPrinter<Storage> {
+    fn drop(&mut self) {
+        self.save_paper();
+    }
+}
+
+impl<Storage: PersistentStorage> SerialDevice for GameBoyPrinter<Storage> {
+    fn transfer_as_master(&mut self, _now: u64, value: u8) -> u8 {
+        self.receive(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::fs::Fs;
+
+    fn packet(command: u8, compressed: bool, data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![command, compressed.into()];
+        bytes.extend((data.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(data);
+        let checksum = bytes.iter().map(|&b| b as u16).fold(0, u16::wrapping_add);
+
+        let mut packet = MAGIC.to_vec();
+        packet.extend(bytes);
+        packet.extend(checksum.to_le_bytes());
+        packet.extend([0, 0]);
+        packet
+    }
+
+    /// Sends the packet, returning the alive byte and the status.
+    fn send(printer: &mut GameBoyPrinter<impl PersistentStorage>, packet: &[u8]) -> (u8, u8) {
+        let responses: Vec<_> = packet
+            .iter()
+            .map(|&b| printer.transfer_as_master(0, b))
+            .collect();
+        assert!(responses[..responses.len() - 2].iter().all(|&r| r == 0));
+        (
+            responses[responses.len() - 2],
+            responses[responses.len() - 1],
+        )
+    }
+
+    /// Two rows of tiles, where each pixel of the top row uses color 1, and each pixel of the
+    /// bottom row uses color 3.
+    fn test_image() -> Vec<u8> {
+        let mut data = vec![];
+        for _ in 0..TILES_PER_ROW {
+            data.extend([0xFF, 0x00].repeat(8));
+        }
+        for _ in 0..TILES_PER_ROW {
+            data.extend([0xFF, 0xFF].repeat(8));
+        }
+        data
+    }
+
+    fn read_print(dir: &tempfile::TempDir, name: &str) -> Image {
+        bmp::open(dir.path().join(name)).unwrap()
+    }
+
+    fn shade(image: &Image, x: u32, y: u32) -> u8 {
+        image.get_pixel(x, y).r
+    }
+
+    #[test]
+    fn decompress_runs() {
+        assert_eq!(
+            decompress(&[0x81, 0xAA, 0x02, 1, 2, 3, 0x80, 0x55]),
+            [0xAA, 0xAA, 0xAA, 1, 2, 3, 0x55, 0x55]
+        );
+    }
+
+    #[test]
+    fn printer_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut printer = GameBoyPrinter::new(Fs::new(Some(dir.path())), "print");
+
+        assert_eq!(send(&mut printer, &packet(0x01, false, &[])), (ALIVE, 0x00));
+        assert_eq!(
+            send(&mut printer, &packet(0x04, false, &test_image())),
+            (ALIVE, 0x08)
+        );
+        assert_eq!(send(&mut printer, &packet(0x0F, false, &[])), (ALIVE, 0x08));
+
+        let mut bad_checksum = packet(0x0F, false, &[]);
+        bad_checksum[6] ^= 1;
+        assert_eq!(send(&mut printer, &bad_checksum), (ALIVE, 0x09));
+
+        assert_eq!(send(&mut printer, &packet(0x07, false, &[])), (ALIVE, 0x18));
+    }
+
+    #[test]
+    fn printer_prints() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut printer = GameBoyPrinter::new(Fs::new(Some(dir.path())), "print");
+
+        send(&mut printer, &packet(0x01, false, &[]));
+        send(&mut printer, &packet(0x04, false, &test_image()));
+        send(&mut printer, &packet(0x04, false, &[]));
+
+        // One sheet, a margin of one line before and after, with colors 1 and 3 swapped.
+        let (_, status) = send(&mut printer, &packet(0x02, false, &[1, 0x11, 0x6C, 0x40]));
+        assert_eq!(status, 0x06);
+        for _ in 0..BUSY_STATUS_REQUESTS - 1 {
+            assert_eq!(send(&mut printer, &packet(0x0F, false, &[])), (ALIVE, 0x06));
+        }
+        assert_eq!(send(&mut printer, &packet(0x0F, false, &[])), (ALIVE, 0x00));
+        assert_eq!(printer.prints_saved(), 1);
+
+        let image = read_print(&dir, "print_0.bmp");
+        assert_eq!(image.get_width(), PRINTER_WIDTH as u32);
+        assert_eq!(image.get_height(), 32);
+        assert_eq!(shade(&image, 0, 0), 0xFF);
+        assert_eq!(shade(&image, 5, 8), 0x00);
+        assert_eq!(shade(&image, 159, 16), 0xAA);
+        assert_eq!(shade(&image, 0, 31), 0xFF);
+    }
+
+    #[test]
+    fn printer_compressed_data_continues_paper() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut printer = GameBoyPrinter::new(Fs::new(Some(dir.path())), "print");
+
+        // A row of tiles with color 0, using runs, then a row with color 2 using literals.
+        let mut compressed = vec![0xFF, 0x00, 0xFF, 0x00, 0x80 | 60, 0x00];
+        for length in [128, 128, 64] {
+            compressed.push(length - 1);
+            compressed.extend([0x00, 0xFF].repeat(length as usize / 2));
+        }
+
+        send(&mut printer, &packet(0x01, false, &[]));
+        send(&mut printer, &packet(0x04, true, &compressed));
+        send(&mut printer, &packet(0x02, false, &[1, 0x00, 0xE4, 0x40]));
+        assert_eq!(printer.prints_saved(), 0);
+
+        send(&mut printer, &packet(0x04, false, &test_image()));
+        send(&mut printer, &packet(0x02, false, &[1, 0x01, 0xE4, 0x40]));
+        assert_eq!(printer.prints_saved(), 1);
+
+        let image = read_print(&dir, "print_0.bmp");
+        assert_eq!(image.get_height(), 16 + 16 + MARGIN_LINE_PIXELS as u32);
+        assert_eq!(shade(&image, 0, 0), 0xFF);
+        assert_eq!(shade(&image, 0, 8), 0x55);
+        assert_eq!(shade(&image, 0, 16), 0xAA);
+        assert_eq!(shade(&image, 0, 24), 0x00);
+        assert_eq!(shade(&image, 0, 39), 0xFF);
+    }
+
+    #[test]
+    fn printer_saves_paper_when_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut printer = GameBoyPrinter::new(Fs::new(Some(dir.path())), "print");
+
+        send(&mut printer, &packet(0x01, false, &[]));
+        send(&mut printer, &packet(0x04, false, &test_image()));
+        send(&mut printer, &packet(0x02, false, &[1, 0x00, 0xE4, 0x40]));
+        assert_eq!(printer.prints_saved(), 0);
+        drop(printer);
+
+        let image = read_print(&dir, "print_0.bmp");
+        assert_eq!(image.get_height(), 16);
+        assert_eq!(shade(&image, 0, 0), 0xAA);
+        assert_eq!(shade(&image, 0, 8), 0x00);
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright 2026 Remi Bernotavicius
+
+use super::memory_controller::{FlagMask, GameBoyFlags, GameBoyRegister, MemoryMappedHardware};
+use super::{GameBoyEmulatorEvent, GameBoyScheduler, InterruptFlag};
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::RefCell;
+use enum_iterator::IntoEnumIterator;
+use num_enum::IntoPrimitive;
+use serde_derive::{Deserialize, Serialize};
+
+/// With the internal clock, bits are shifted at 8192Hz.
+const CYCLES_PER_BIT: u64 = 512;
+
+const TRANSFER_CYCLES: u64 = CYCLES_PER_BIT * 8;
+
+/// How often an attached device is asked whether it clocked a transfer while the Game Boy is
+/// waiting on the external clock.
+const SLAVE_POLL_CYCLES: u64 = CYCLES_PER_BIT;
+
+/// How often an attached device is ticked, whether or not a transfer is happening.
+const DRIVE_DEVICE_CYCLES: u64 = TRANSFER_CYCLES;
+
+/// Something plugged in to the link port.
+pub trait SerialDevice {
+    /// The Game Boy clocked out `value` using its internal clock. Returns the byte that the device
+    /// shifted back in at the same time.
+    fn transfer_as_master(&mut self, now: u64, value: u8) -> u8;
+
+    /// The Game Boy is waiting for the device to provide the clock, with `value` ready to be
+    /// shifted out. Returns the byte shifted in if the device clocked a transfer.
+    fn poll_as_slave(&mut self, _now: u64, _value: u8) -> Option<u8> {
+        None
+    }
+
+    /// The Game Boy is no longer waiting for the device to provide the clock.
+    fn stop_waiting(&mut self, _now: u64) {}
+
+    /// Called periodically with the current time, regardless of what the link port is doing.
+    fn tick(&mut self, _now: u64) {}
+}
+
+/// `SerialDevice` that records every byte the Game Boy sends. Nothing is on the other end, so
+/// the Game Boy always reads back 0xFF. Clones share the same recording.
+#[derive(Clone, Default)]
+pub struct CapturingSerialDevice(Rc<RefCell<Vec<u8>>>);
+
+impl CapturingSerialDevice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns everything sent so far.
+    pub fn captured(&self) -> Vec<u8> {
+        self.0.borrow().clone()
+    }
+}
+
+impl SerialDevice for CapturingSerialDevice {
+    fn transfer_as_master(&mut self, _now: u64, value: u8) -> u8 {
+        self.0.borrow_mut().push(value);
+        0xFF
+    }
+}
+
+#[derive(Debug, Clone, Copy, IntoPrimitive, IntoEnumIterator)]
+#[repr(u8)]
+enum SerialControlFlag {
+    TransferStart = 0b10000000,
+    InternalClock = 0b00000001,
+}
+
+impl FlagMask for SerialControlFlag {
+    fn read_mask() -> u8 {
+        SerialControlFlag::TransferStart as u8 | SerialControlFlag::InternalClock as u8
+    }
+
+    fn write_mask() -> u8 {
+        SerialControlFlag::TransferStart as u8 | SerialControlFlag::InternalClock as u8
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct SerialPort {
+    /// The SB register, which holds the byte being shifted out and in.
+    pub(super) data: GameBoyRegister,
+    /// The SC register
+    control: GameBoyFlags<SerialControlFlag>,
+}
+
+impl SerialPort {
+    pub(super) fn set_state_post_bios(&mut self) {
+        self.data.set_value(0x0);
+        self.control.set_value(0x7e);
+    }
+
+    pub(super) fn schedule_initial_events(&self, scheduler: &mut GameBoyScheduler, now: u64) {
+        scheduler.schedule(
+            now + DRIVE_DEVICE_CYCLES,
+            GameBoyEmulatorEvent::DriveSerialDevice,
+        );
+    }
+
+    pub(super) fn drive_device(
+        &self,
+        device: Option<&mut dyn SerialDevice>,
+        scheduler: &mut GameBoyScheduler,
+        now: u64,
+    ) {
+        if let Some(device) = device {
+            device.tick(now);
+        }
+        scheduler.schedule(
+            now + DRIVE_DEVICE_CYCLES,
+            GameBoyEmulatorEvent::DriveSerialDevice,
+        );
+    }
+
+    pub(super) fn complete_transfer(
+        &mut self,
+        device: Option<&mut dyn SerialDevice>,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        now: u64,
+    ) {
+        let outgoing = self.data.read_value();
+        let incoming = device.map_or(0xFF, |d| d.transfer_as_master(now, outgoing));
+        self.finish_transfer(incoming, interrupt_flag, scheduler, now);
+    }
+
+    fn waiting_for_external_clock(&self) -> bool {
+        self.control.read_flag(SerialControlFlag::TransferStart)
+            && !self.control.read_flag(SerialControlFlag::InternalClock)
+    }
+
+    pub(super) fn poll_device(
+        &mut self,
+        device: Option<&mut dyn SerialDevice>,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        now: u64,
+    ) {
+        if !self.waiting_for_external_clock() {
+            if let Some(device) = device {
+                device.stop_waiting(now);
+            }
+            return;
+        }
+
+        let outgoing = self.data.read_value();
+        match device.and_then(|d| d.poll_as_slave(now, outgoing)) {
+            Some(incoming) => self.finish_transfer(incoming, interrupt_flag, scheduler, now),
+            None => scheduler.schedule(
+                now + SLAVE_POLL_CYCLES,
+                GameBoyEmulatorEvent::PollSerialDevice,
+            ),
+        }
+    }
+
+    fn finish_transfer(
+        &mut self,
+        incoming: u8,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        now: u64,
+    ) {
+        self.data.set_value(incoming);
+        self.control
+            .set_flag(SerialControlFlag::TransferStart, false);
+
+        interrupt_flag.set_flag(InterruptFlag::Serial, true);
+        scheduler.schedule(now, GameBoyEmulatorEvent::HandleInterrupts);
+    }
+}
+
+/// This implementation is where reads for serial control go
+impl<'a> MemoryMappedHardware for (&'a SerialPort, &'a GameBoyScheduler) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        assert_eq!(address, 0);
+        let (serial, _scheduler) = self;
+        serial.control.read_value()
+    }
+
+    fn set_value(&mut self, _address: u16, _value: u8) {
+        unreachable!()
+    }
+}
+
+/// This implementation is where the writes for serial control go
+impl<'a> MemoryMappedHardware for (&'a mut SerialPort, &'a mut GameBoyScheduler) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        assert_eq!(address, 0);
+        let (serial, _scheduler) = self;
+        serial.control.read_value()
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_value(&mut self, address: u16, value: u8) {
+        assert_eq!(address, 0);
+        let (serial, scheduler) = self;
+
+        serial.control.set_value(value);
+
+        scheduler.drop_events(|e| {
+            matches!(
+                e,
+                GameBoyEmulatorEvent::SerialTransferComplete
+                    | GameBoyEmulatorEvent::PollSerialDevice
+            )
+        });
+
+        // The device is told right away whether we are waiting on it or not
+        let now = scheduler.now();
+        scheduler.schedule(now, GameBoyEmulatorEvent::PollSerialDevice);
+
+        if serial.control.read_flag(SerialControlFlag::TransferStart)
+            && serial.control.read_flag(SerialControlFlag::InternalClock)
+        {
+            scheduler.schedule(
+                now + TRANSFER_CYCLES,
+                GameBoyEmulatorEvent::SerialTransferComplete,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_boy_emulator::{
+        assemble, GameBoyEmulator, GamePak, MemoryAccessor as _, NullGameBoyOps,
+    };
+
+    fn load_test_rom(ops: &mut NullGameBoyOps, program: &str) {
+        let rom = assemble(program).unwrap();
+        let game_pak = GamePak::new(&rom.bin, &mut ops.storage, None).unwrap();
+        ops.load_game_pak(game_pak);
+    }
+
+    fn run_until_done(e: &mut GameBoyEmulator, ops: &mut NullGameBoyOps) {
+        let start = e.elapsed_cycles();
+        while e.cpu.read_program_counter() != 0x0150 {
+            assert!(
+                e.elapsed_cycles() - start < 1_000_000,
+                "test ROM never finished"
+            );
+            e.tick(ops);
+        }
+    }
+
+    const SEND_BYTES: &str = "
+    SECTION send,ROM0[$0200]
+    .send
+        ldh  [$FF01],a
+        ld   a,$81
+        ldh  [$FF02],a
+    .wait
+        ldh  a,[$FF02]
+        and  a,$80
+        jr   nz,.wait
+        ret
+    ";
+
+    #[test]
+    fn serial_output_captured() {
+        let mut ops = NullGameBoyOps::null();
+        let capture = CapturingSerialDevice::new();
+        ops.plug_in_serial_device(capture.clone());
+        load_test_rom(
+            &mut ops,
+            &format!(
+                "
+    SECTION test,ROM0[$0100]
+        ld   a,$4F
+        call $0200
+        ld   a,$4B
+        call $0200
+        ld   a,$0A
+        call $0200
+        jp   $0150
+    SECTION done,ROM0[$0150]
+    .done
+        jr   .done
+    {SEND_BYTES}"
+            ),
+        );
+        let mut e = GameBoyEmulator::new();
+        run_until_done(&mut e, &mut ops);
+
+        assert_eq!(capture.captured(), b"OK\n");
+        let memory_map = ops.memory_map(&e.bridge);
+        assert_eq!(memory_map.read_memory(0xFF01), 0xFF);
+        assert_eq!(memory_map.read_memory(0xFF02), 0x7F);
+        assert_ne!(
+            memory_map.read_memory(0xFF0F) & InterruptFlag::Serial as u8,
+            0
+        );
+    }
+
+    #[test]
+    fn internal_clock_transfer_timing() {
+        let mut ops = NullGameBoyOps::null();
+        load_test_rom(
+            &mut ops,
+            &format!(
+                "
+    SECTION test,ROM0[$0100]
+        ld   a,$12
+        call $0200
+        jp   $0150
+    SECTION done,ROM0[$0150]
+    .done
+        jr   .done
+    {SEND_BYTES}"
+            ),
+        );
+        let mut e = GameBoyEmulator::new();
+        let start = e.elapsed_cycles();
+        run_until_done(&mut e, &mut ops);
+
+        let elapsed = e.elapsed_cycles() - start;
+        assert!(
+            (TRANSFER_CYCLES..TRANSFER_CYCLES + 200).contains(&elapsed),
+            "{elapsed}"
+        );
+    }
+
+    /// Drives the clock itself on the third time it is polled.
+    #[derive(Clone, Default)]
+    struct TestMaster {
+        polls: Rc<RefCell<Vec<u8>>>,
+    }
+
+    impl SerialDevice for TestMaster {
+        fn transfer_as_master(&mut self, _now: u64, _value: u8) -> u8 {
+            panic!("unexpected internal clock transfer");
+        }
+
+        fn poll_as_slave(&mut self, _now: u64, value: u8) -> Option<u8> {
+            let mut polls = self.polls.borrow_mut();
+            polls.push(value);
+            (polls.len() == 3).then_some(0x99)
+        }
+    }
+
+    #[test]
+    fn external_clock_transfer() {
+        let mut ops = NullGameBoyOps::null();
+        let master = TestMaster::default();
+        ops.plug_in_serial_device(master.clone());
+        load_test_rom(
+            &mut ops,
+            "
+    SECTION test,ROM0[$0100]
+        ld   a,$42
+        ldh  [$FF01],a
+        ld   a,$80
+        ldh  [$FF02],a
+    .wait
+        ldh  a,[$FF02]
+        and  a,$80
+        jr   nz,.wait
+        jp   $0150
+    SECTION done,ROM0[$0150]
+    .done
+        jr   .done
+    ",
+        );
+        let mut e = GameBoyEmulator::new();
+        run_until_done(&mut e, &mut ops);
+
+        assert_eq!(*master.polls.borrow(), [0x42, 0x42, 0x42]);
+        let memory_map = ops.memory_map(&e.bridge);
+        assert_eq!(memory_map.read_memory(0xFF01), 0x99);
+        assert_eq!(memory_map.read_memory(0xFF02), 0x7E);
+    }
+
+    #[test]
+    fn external_clock_without_device_waits() {
+        let mut ops = NullGameBoyOps::null();
+        load_test_rom(
+            &mut ops,
+            "
+    SECTION test,ROM0[$0100]
+        ld   a,$80
+        ldh  [$FF02],a
+    .wait
+        jr   .wait
+    ",
+        );
+        let mut e = GameBoyEmulator::new();
+        let start = e.elapsed_cycles();
+        while e.elapsed_cycles() - start < TRANSFER_CYCLES * 4 {
+            e.tick(&mut ops);
+        }
+        assert_eq!(ops.memory_map(&e.bridge).read_memory(0xFF02), 0xFE);
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright 2026 Remi Bernotavicius
+
+//! The timer is driven by a 16-bit system counter which counts up every cycle. DIV is the top
+//! byte of it. TIMA counts falling edges of one of the bits of the counter (picked by TAC, and
+//! ANDed with the enable bit of TAC). That means anything which makes that signal go from high to
+//! low also counts, like writing to DIV (which resets the counter) or changing TAC.
+//!
+//! Rather than ticking the counter every cycle, it is worked out from the time it was last reset,
+//! and TIMA is only touched at the falling edges, which are scheduled ahead of time.
+//!
+//! When TIMA overflows it reads 0 for one M-cycle before it is reloaded from TMA and the interrupt
+//! is requested. Writing TIMA during that M-cycle stops the reload from happening. Writing TIMA
+//! in the M-cycle it is reloaded is ignored, and writing TMA then also goes into TIMA.
+
+use super::memory_controller::{FlagMask, GameBoyFlags, GameBoyRegister, MemoryMappedHardware};
+use super::{GameBoyEmulatorEvent, GameBoyScheduler, InterruptFlag};
+use core::fmt;
+use enum_iterator::IntoEnumIterator;
+use num_enum::IntoPrimitive;
+use serde_derive::{Deserialize, Serialize};
+
+/// How long after overflowing TIMA gets reloaded from TMA.
+const RELOAD_DELAY: u64 = 4;
+
+/// What the system counter is when the boot ROM hands over to the cartridge.
+const POST_BOOT_SYSTEM_COUNTER: u16 = 0xABCC;
+
+#[derive(Debug, Clone, Copy, IntoPrimitive, IntoEnumIterator)]
+#[repr(u8)]
+enum TimerFlags {
+    Enabled = 0b00000100,
+    Speed = 0b00000011,
+}
+
+impl FlagMask for TimerFlags {
+    fn read_mask() -> u8 {
+        TimerFlags::Enabled as u8 | TimerFlags::Speed as u8
+    }
+
+    fn write_mask() -> u8 {
+        TimerFlags::Enabled as u8 | TimerFlags::Speed as u8
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub(super) struct GameBoyTimer {
+    /// When the system counter was last 0.
+    counter_start: u64,
+    counter: GameBoyRegister,
+    modulo: GameBoyRegister,
+    control: GameBoyFlags<TimerFlags>,
+    /// When TIMA is going to be reloaded after overflowing.
+    reload: Option<u64>,
+    /// When TIMA was last reloaded after overflowing.
+    reloaded: Option<u64>,
+    /// The system counter is held at 0 while in STOP mode.
+    stopped: bool,
+}
+
+impl fmt::Debug for GameBoyTimer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameBoyTimer")
+            .field("counter_start", &self.counter_start)
+            .field("counter", &self.counter)
+            .field("modulo", &self.modulo)
+            .field("control", &self.control)
+            .finish()
+    }
+}
+
+impl GameBoyTimer {
+    fn enabled(&self) -> bool {
+        self.control.read_flag(TimerFlags::Enabled)
+    }
+
+    /// Which bit of the system counter TIMA counts the falling edges of.
+    fn input_bit(&self) -> u32 {
+        match self.control.read_flag_value(TimerFlags::Speed) {
+            0b00 => 9,
+            0b01 => 3,
+            0b10 => 5,
+            0b11 => 7,
+            _ => panic!(),
+        }
+    }
+
+    /// How many cycles there are between increments of TIMA.
+    fn timer_speed(&self) -> u64 {
+        2 << self.input_bit()
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn system_counter(&self, now: u64) -> u16 {
+        if self.stopped {
+            return 0;
+        }
+        now.wrapping_sub(self.counter_start) as u16
+    }
+
+    /// The signal TIMA counts the falling edges of.
+    fn input(&self, now: u64) -> bool {
+        self.enabled() && (self.system_counter(now) >> self.input_bit()) & 0x1 != 0
+    }
+
+    pub(super) fn set_state_post_bios(&mut self, now: u64) {
+        self.counter_start = now.wrapping_sub(POST_BOOT_SYSTEM_COUNTER as u64);
+        self.counter.set_value(0x0);
+        self.modulo.set_value(0x0);
+        self.control.set_value(0xf8);
+    }
+
+    pub(super) fn schedule_initial_events(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        self.schedule_tick(scheduler, now);
+    }
+
+    /// Schedules the next falling edge of the input to TIMA.
+    fn schedule_tick(&self, scheduler: &mut GameBoyScheduler, now: u64) {
+        if self.enabled() {
+            let speed = self.timer_speed();
+            let elapsed = self.system_counter(now) as u64 % speed;
+            scheduler.schedule(now + speed - elapsed, GameBoyEmulatorEvent::TimerTick);
+        }
+    }
+
+    /// The counter or TAC changed, so the next falling edge moved.
+    fn reschedule_tick(&self, scheduler: &mut GameBoyScheduler, now: u64) {
+        scheduler.drop_events(|e| matches!(e, GameBoyEmulatorEvent::TimerTick));
+        self.schedule_tick(scheduler, now);
+    }
+
+    fn increment(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        let counter = self.counter.read_value().wrapping_add(1);
+        self.counter.set_value(counter);
+        if counter == 0 {
+            self.reload = Some(now + RELOAD_DELAY);
+            scheduler.schedule(now + RELOAD_DELAY, GameBoyEmulatorEvent::TimerReload);
+        }
+    }
+
+    pub(super) fn fire(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        self.increment(scheduler, now);
+        self.schedule_tick(scheduler, now);
+    }
+
+    pub(super) fn reload(
+        &mut self,
+        interrupt_flag: &mut GameBoyFlags<InterruptFlag>,
+        scheduler: &mut GameBoyScheduler,
+        now: u64,
+    ) {
+        if self.reload != Some(now) {
+            return;
+        }
+        self.reload = None;
+        self.reloaded = Some(now);
+
+        self.counter.set_value(self.modulo.read_value());
+        interrupt_flag.set_flag(InterruptFlag::Timer, true);
+        scheduler.schedule(now, GameBoyEmulatorEvent::HandleInterrupts);
+    }
+
+    /// STOP resets the system counter and holds it there.
+    pub(super) fn stop(&mut self, scheduler: &mut GameBoyScheduler) {
+        let now = scheduler.now();
+        if self.input(now) {
+            self.increment(scheduler, now);
+        }
+        self.stopped = true;
+        scheduler.drop_events(|e| matches!(e, GameBoyEmulatorEvent::TimerTick));
+    }
+
+    pub(super) fn resume(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        self.stopped = false;
+        self.counter_start = now;
+        self.schedule_tick(scheduler, now);
+    }
+
+    fn read_register(&self, now: u64, address: u16) -> u8 {
+        match address {
+            0 => (self.system_counter(now) >> 8) as u8,
+            1 => self.counter.read_value(),
+            2 => self.modulo.read_value(),
+            3 => self.control.read_value(),
+            _ => panic!("address = {address}"),
+        }
+    }
+
+    fn set_divider(&mut self, scheduler: &mut GameBoyScheduler, now: u64) {
+        if self.input(now) {
+            self.increment(scheduler, now);
+        }
+        self.counter_start = now;
+        self.reschedule_tick(scheduler, now);
+    }
+
+    fn set_counter(&mut self, now: u64, value: u8) {
+        if self.reloaded != Some(now) {
+            self.counter.set_value(value);
+            self.reload = None;
+        }
+    }
+
+    fn set_modulo(&mut self, now: u64, value: u8) {
+        self.modulo.set_value(value);
+        if self.reloaded == Some(now) {
+            self.counter.set_value(value);
+        }
+    }
+
+    fn set_control(&mut self, scheduler: &mut GameBoyScheduler, now: u64, value: u8) {
+        let input = self.input(now);
+        self.control.set_value(value);
+        if input && !self.input(now) {
+            self.increment(scheduler, now);
+        }
+        self.reschedule_tick(scheduler, now);
+    }
+}
+
+/// This implementation is where reads for DIV through TAC go
+impl<'a> MemoryMappedHardware for (&'a GameBoyTimer, &'a GameBoyScheduler) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        let (timer, scheduler) = self;
+        timer.read_register(scheduler.now(), address)
+    }
+
+    fn set_value(&mut self, _address: u16, _value: u8) {
+        unreachable!()
+    }
+}
+
+/// This implementation is where the writes for DIV through TAC go
+impl<'a> MemoryMappedHardware for (&'a mut GameBoyTimer, &'a mut GameBoyScheduler) {
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn read_value(&self, address: u16) -> u8 {
+        let (timer, scheduler) = self;
+        timer.read_register(scheduler.now(), address)
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_value(&mut self, address: u16, value: u8) {
+        let (timer, scheduler) = self;
+        let now = scheduler.now();
+        match address {
+            0 => timer.set_divider(scheduler, now),
+            1 => timer.set_counter(now, value),
+            2 => timer.set_modulo(now, value),
+            3 => timer.set_control(scheduler, now, value),
+            _ => panic!("address = {address}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIV: u16 = 0;
+    const TIMA: u16 = 1;
+    const TMA: u16 = 2;
+    const TAC: u16 = 3;
+
+    struct TestTimer {
+        timer: GameBoyTimer,
+        scheduler: GameBoyScheduler,
+        interrupt_flag: GameBoyFlags<InterruptFlag>,
+    }
+
+    impl TestTimer {
+        fn new() -> Self {
+            let mut timer = GameBoyTimer::default();
+            let mut scheduler = GameBoyScheduler::new();
+            timer.schedule_initial_events(&mut scheduler, 0);
+            Self {
+                timer,
+                scheduler,
+                interrupt_flag: GameBoyFlags::new(),
+            }
+        }
+
+        /// Delivers the timer's events up to the given time.
+        fn run_until(&mut self, now: u64) {
+            while let Some((time, event)) = self.scheduler.poll(now) {
+                match event {
+                    GameBoyEmulatorEvent::TimerTick => self.timer.fire(&mut self.scheduler, time),
+                    GameBoyEmulatorEvent::TimerReload => {
+                        self.timer
+                            .reload(&mut self.interrupt_flag, &mut self.scheduler, time)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn read(&mut self, now: u64, address: u16) -> u8 {
+            self.run_until(now);
+            (&self.timer, &self.scheduler).read_value(address)
+        }
+
+        fn write(&mut self, now: u64, address: u16, value: u8) {
+            self.run_until(now);
+            (&mut self.timer, &mut self.scheduler).set_value(address, value);
+        }
+
+        fn interrupted(&self) -> bool {
+            self.interrupt_flag.read_flag(InterruptFlag::Timer)
+        }
+    }
+
+    #[test]
+    fn div_is_top_of_system_counter() {
+        let mut t = TestTimer::new();
+        assert_eq!(t.read(0x12FF, DIV), 0x12);
+        assert_eq!(t.read(0x1300, DIV), 0x13);
+
+        t.write(0x1380, DIV, 0x42);
+        assert_eq!(t.read(0x1380, DIV), 0x00);
+        assert_eq!(t.read(0x147F, DIV), 0x00);
+        assert_eq!(t.read(0x1480, DIV), 0x01);
+    }
+
+    #[test]
+    fn tima_counts_at_each_speed() {
+        for (tac, speed) in [(0x04, 1024), (0x05, 16), (0x06, 64), (0x07, 256)] {
+            let mut t = TestTimer::new();
+            t.write(0, TAC, tac);
+            assert_eq!(t.read(speed * 10 - 1, TIMA), 9);
+            assert_eq!(t.read(speed * 10, TIMA), 10);
+        }
+    }
+
+    #[test]
+    fn tima_does_not_count_when_disabled() {
+        let mut t = TestTimer::new();
+        t.write(0, TAC, 0x01);
+        assert_eq!(t.read(10_000, TIMA), 0);
+    }
+
+    /// Resetting the counter while the selected bit is high is a falling edge.
+    #[test]
+    fn div_write_increments_tima() {
+        let mut t = TestTimer::new();
+        t.write(0, TAC, 0x05);
+        t.write(8, DIV, 0);
+        assert_eq!(t.read(8, TIMA), 1);
+
+        // While the selected bit is low it doesn't, and the next increment is a full period after
+        // the write.
+        t.write(12, DIV, 0);
+        assert_eq!(t.read(27, TIMA), 1);
+        assert_eq!(t.read(28, TIMA), 2);
+    }
+
+    /// Changing TAC such that the signal goes from high to low is a falling edge.
+    #[test]
+    fn tac_write_increments_tima() {
+        let mut t = TestTimer::new();
+        t.write(0, TAC, 0x05);
+        t.write(8, TAC, 0x01);
+        assert_eq!(t.read(8, TIMA), 1);
+
+        t.write(24, TAC, 0x05);
+        t.write(24, TAC, 0x04);
+        assert_eq!(t.read(24, TIMA), 2);
+    }
+
+    #[test]
+    fn tima_is_reloaded_one_m_cycle_after_overflow() {
+        let mut t = TestTimer::new();
+        t.write(0, TIMA, 0xFF);
+        t.write(0, TMA, 0x42);
+        t.write(0, TAC, 0x05);
+
+        assert_eq!(t.read(16, TIMA), 0x00);
+        assert!(!t.interrupted());
+        assert_eq!(t.read(20, TIMA), 0x42);
+        assert!(t.interrupted());
+    }
+
+    #[test]
+    fn tima_write_before_reload_cancels_it() {
+        let mut t = TestTimer::new();
+        t.write(0, TIMA, 0xFF);
+        t.write(0, TMA, 0x42);
+        t.write(0, TAC, 0x05);
+
+        t.write(16, TIMA, 0x10);
+        assert_eq!(t.read(20, TIMA), 0x10);
+        assert!(!t.interrupted());
+    }
+
+    #[test]
+    fn tima_write_during_reload_is_ignored() {
+        let mut t = TestTimer::new();
+        t.write(0, TIMA, 0xFF);
+        t.write(0, TMA, 0x42);
+        t.write(0, TAC, 0x05);
+
+        t.write(20, TIMA, 0x10);
+        assert_eq!(t.read(20, TIMA), 0x42);
+        assert!(t.interrupted());
+    }
+
+    #[test]
+    fn tma_write_during_reload_goes_to_tima() {
+        let mut t = TestTimer::new();
+        t.write(0, TIMA, 0xFF);
+        t.write(0, TMA, 0x42);
+        t.write(0, TAC, 0x05);
+
+        t.write(20, TMA, 0x10);
+        assert_eq!(t.read(20, TIMA), 0x10);
+    }
+}
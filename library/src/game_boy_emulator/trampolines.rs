@@ -4,21 +4,26 @@ use super::coverage::{self, CoverageData};
 pub use super::debugger::run_debugger;
 use super::joypad::{PlaybackJoyPad, RecordingJoyPad};
 use super::{
-    game_pak::GamePak, joypad, tandem, ControllerJoyPad, GameBoyEmulator, GameBoyOps,
-    ModuloCounter, NullPerfObserver, PerfObserver, Result, SLEEP_INPUT_TICKS,
+    connect_link_cable, game_pak::GamePak, joypad, run_linked, tandem, Boot, ControllerJoyPad,
+    CpuMode, GameBoyEmulator, GameBoyOps, ModuloCounter, NullPerfObserver, PerfObserver, PpuMode,
+    Result, SerialDevice, SLEEP_INPUT_TICKS,
 };
 use crate::io;
 use crate::rendering::Renderer;
 use crate::sound::{NullSoundStream, SoundStream};
 use crate::storage::{OpenMode, PersistentStorage};
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 #[allow(clippy::too_many_arguments)]
 pub fn run_emulator<Storage: PersistentStorage>(
     renderer: impl Renderer,
     sound_stream: impl SoundStream,
     storage: Storage,
-    game_pak: GamePak<Storage>,
+    mut game_pak: GamePak<Storage>,
+    boot: Boot,
+    serial_device: Option<Box<dyn SerialDevice>>,
+    ppu_mode: PpuMode,
+    cpu_mode: CpuMode,
     save_state: Option<Vec<u8>>,
     unlock_cpu: bool,
     observer: &mut impl PerfObserver,
@@ -26,17 +31,26 @@ pub fn run_emulator<Storage: PersistentStorage>(
     run_until: Option<u64>,
 ) -> Result<()> {
     let mut ops = GameBoyOps::new(renderer, sound_stream, storage);
-    ops.load_game_pak(game_pak);
 
     if !disable_joypad {
-        ops.plug_in_joy_pad(ControllerJoyPad::new());
+        let mut joypad = ControllerJoyPad::new();
+        game_pak.set_rumble(joypad.rumble());
+        #[cfg(feature = "std")]
+        game_pak.set_tilt_sensor(joypad.tilt());
+        ops.plug_in_joy_pad(joypad);
     }
 
+    ops.serial_device = serial_device;
+    ops.ppu_mode = ppu_mode;
+    ops.cpu_mode = cpu_mode;
+
+    ops.load_game_pak(game_pak);
+
     if unlock_cpu {
         ops.clock_speed_hz = u32::MAX;
     }
 
-    let mut e = GameBoyEmulator::new();
+    let mut e = GameBoyEmulator::new_with_boot(boot);
 
     if let Some(save_state) = save_state {
         e.load_state(ops.game_pak.as_mut(), &save_state[..])?;
@@ -123,6 +137,58 @@ pub fn run_until_and_take_screenshot<Storage: PersistentStorage + 'static>(
     Ok(())
 }
 
+/// One of the two Game Boys given to `run_linked_until_and_take_screenshots`
+pub struct LinkedGameBoy<'a, Renderer, Storage: PersistentStorage> {
+    pub renderer: Renderer,
+    pub storage: Storage,
+    pub game_pak: GamePak<Storage>,
+    pub replay_key: Option<&'a str>,
+    pub output_key: &'a str,
+}
+
+impl<Renderer, Storage: PersistentStorage + 'static> LinkedGameBoy<'_, Renderer, Storage> {
+    fn into_ops(
+        self,
+    ) -> Result<(
+        GameBoyOps<Renderer, NullSoundStream, Storage>,
+        Storage::File,
+    )> {
+        let mut storage = self.storage;
+        let output_file = storage.open(OpenMode::Write, self.output_key)?;
+
+        let mut ops = GameBoyOps::new(self.renderer, NullSoundStream, storage);
+        if let Some(replay_key) = self.replay_key {
+            let joy_pad = PlaybackJoyPad::new(&mut ops.storage, self.game_pak.hash(), replay_key)?;
+            ops.plug_in_joy_pad(joy_pad);
+        }
+        ops.load_game_pak(self.game_pak);
+        Ok((ops, output_file))
+    }
+}
+
+pub fn run_linked_until_and_take_screenshots<Storage: PersistentStorage + 'static>(
+    a: LinkedGameBoy<'_, impl Renderer, Storage>,
+    b: LinkedGameBoy<'_, impl Renderer, Storage>,
+    ticks: u64,
+) -> Result<()> {
+    let (mut ops_a, output_a) = a.into_ops()?;
+    let (mut ops_b, output_b) = b.into_ops()?;
+    connect_link_cable(&mut ops_a, &mut ops_b);
+
+    let mut e_a = GameBoyEmulator::new();
+    let mut e_b = GameBoyEmulator::new();
+    run_linked(&mut e_a, &mut ops_a, &mut e_b, &mut ops_b, ticks);
+    log::info!(
+        "Ran linked Game Boy emulators until CPU clocks were {} and {}",
+        e_a.cpu.elapsed_cycles,
+        e_b.cpu.elapsed_cycles
+    );
+
+    ops_a.renderer.save_buffer(output_a)?;
+    ops_b.renderer.save_buffer(output_b)?;
+    Ok(())
+}
+
 pub fn run_and_record_replay<Storage: PersistentStorage + 'static>(
     mut storage: Storage,
     renderer: impl Renderer,
@@ -2,7 +2,7 @@
 
 use crate::emulator_common::debugger::{Debugger, DebuggerOps, SimulatedInstruction};
 use crate::emulator_common::Intel8080Register;
-use crate::emulator_common::{MemoryAccessor, SimpleMemoryAccessor};
+use crate::emulator_common::{BusAccess, MemoryAccessor, SimpleMemoryAccessor};
 use crate::io::{self, Result};
 use crate::lr35902_emulator::opcodes::LR35902Instruction;
 use crate::lr35902_emulator::{LR35902Emulator, LR35902Flag, LR35902InstructionSetOps};
@@ -82,7 +82,13 @@ impl<'a, M: MemoryAccessor> LR35902InstructionSetOps for SimulatedInstructionLR3
 
     fn pop_frame(&mut self) {}
 
+    fn increment_decrement(&mut self, _address: u16, _access: BusAccess) {}
+
+    fn set_enabling_interrupts(&mut self, _value: bool) {}
+
     fn wait_until_interrupt(&mut self) {}
+
+    fn wait_until_button_press(&mut self) {}
 }
 
 struct Hex<T>(T);
@@ -1,14 +1,16 @@
 // Copyright 2017 Remi Bernotavicius
 
+use crate::emulator_common::MemoryDescription;
 use crate::intel_8080_emulator::{
     Intel8080Flag, Intel8080InstructionSet, Intel8080InstructionSetOps,
 };
 use crate::util::TwosComplement;
 use alloc::{format, string::String, vec::Vec};
+use core::mem;
 use serde_derive::{Deserialize, Serialize};
 
 pub use crate::emulator_common::Intel8080Register;
-pub use crate::emulator_common::{MemoryAccessor, SimpleMemoryAccessor};
+pub use crate::emulator_common::{BusAccess, MemoryAccessor, SimpleMemoryAccessor};
 pub use crate::lr35902_emulator::debugger::run_debugger;
 pub use crate::lr35902_emulator::opcodes::{
     disassemble_lr35902_rom, IllegalInstructionError, LR35902Instruction, LR35902InstructionSet,
@@ -46,6 +48,9 @@ pub struct LR35902Emulator {
     pub crash_message: Option<String>,
     pub call_stack: Vec<u16>,
     halted: bool,
+    stopped: bool,
+    halt_bug: bool,
+    enabling_interrupts: bool,
 }
 
 impl Default for LR35902Emulator {
@@ -63,6 +68,9 @@ impl LR35902Emulator {
             crash_message: None,
             call_stack: Vec::new(),
             halted: false,
+            stopped: false,
+            halt_bug: false,
+            enabling_interrupts: false,
         };
 
         e.set_register_pair(Intel8080Register::SP, 0xFFFE);
@@ -172,6 +180,37 @@ impl LR35902Emulator {
     pub fn is_halted(&self) -> bool {
         self.halted
     }
+
+    /// HALT ran with IME clear while an interrupt was already pending. The CPU doesn't halt, but
+    /// it fails to increment PC after reading the next opcode, so that byte gets read twice.
+    #[cold]
+    pub fn trigger_halt_bug(&mut self) {
+        self.halted = false;
+        self.halt_bug = true;
+    }
+
+    /// The address an interrupt dispatched now returns to. If the HALT bug hasn't yet played out,
+    /// PC is still pointing at the HALT instruction once the interrupt's PC adjustment is made,
+    /// so the HALT runs again after the interrupt.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    pub fn interrupt_return_address(&mut self) -> u16 {
+        if mem::take(&mut self.halt_bug) {
+            self.program_counter.wrapping_sub(1)
+        } else {
+            self.program_counter
+        }
+    }
+
+    /// Wakes the CPU from the low-power mode entered by STOP.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    pub fn wake(&mut self) {
+        self.stopped = false;
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
 }
 
 /*   ___
@@ -199,6 +238,10 @@ pub trait LR35902InstructionSetOps {
     fn add_cycles(&mut self, cycles: u8);
     fn push_frame(&mut self, address: u16);
     fn pop_frame(&mut self);
+    fn increment_decrement(&mut self, address: u16, access: BusAccess);
+
+    /// Sets whether IME gets set once the instruction after the current one is done.
+    fn set_enabling_interrupts(&mut self, value: bool);
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn get_relative_address(&self, n: u8) -> u16 {
@@ -295,6 +338,7 @@ pub trait LR35902InstructionSetOps {
     }
 
     fn wait_until_interrupt(&mut self);
+    fn wait_until_button_press(&mut self);
 }
 
 struct InstructionDispatchOps<'a, M: MemoryAccessor> {
@@ -356,6 +400,40 @@ impl<'a, M: MemoryAccessor> InstructionDispatchOps<'a, M> {
     fn set_interrupts_enabled(&mut self, value: bool) {
         self.memory_accessor.set_interrupts_enabled(value);
     }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn increment_decrement(&mut self, address: u16, access: BusAccess) {
+        self.memory_accessor.increment_decrement(address, access);
+    }
+}
+
+/// What the CPU sees when it reads the instruction after the HALT bug. PC isn't incremented after
+/// the opcode is read, so every byte after it comes one address early.
+struct HaltBugMemoryAccessor<'a, M: MemoryAccessor> {
+    memory_accessor: &'a M,
+    opcode_address: u16,
+}
+
+impl<'a, M: MemoryAccessor> MemoryAccessor for HaltBugMemoryAccessor<'a, M> {
+    fn read_memory(&self, address: u16) -> u8 {
+        if address > self.opcode_address {
+            self.memory_accessor.read_memory(address - 1)
+        } else {
+            self.memory_accessor.read_memory(address)
+        }
+    }
+
+    fn set_memory(&mut self, _address: u16, _value: u8) {
+        unreachable!()
+    }
+
+    fn set_interrupts_enabled(&mut self, _enabled: bool) {
+        unreachable!()
+    }
+
+    fn describe_address(&self, address: u16) -> MemoryDescription {
+        self.memory_accessor.describe_address(address)
+    }
 }
 
 impl<'a, M: MemoryAccessor> LR35902InstructionSetOps for InstructionDispatchOps<'a, M> {
@@ -439,10 +517,25 @@ impl<'a, M: MemoryAccessor> LR35902InstructionSetOps for InstructionDispatchOps<
         self.emulator.call_stack.pop();
     }
 
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn increment_decrement(&mut self, address: u16, access: BusAccess) {
+        self.increment_decrement(address, access);
+    }
+
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn wait_until_interrupt(&mut self) {
         self.emulator.halted = true;
     }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn set_enabling_interrupts(&mut self, value: bool) {
+        self.emulator.enabling_interrupts = value;
+    }
+
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn wait_until_button_press(&mut self) {
+        self.emulator.stopped = true;
+    }
 }
 
 /*   ___   ___   ___   ___    _          _     ____  _________  ___   ___ ____
@@ -582,6 +675,16 @@ impl<I: LR35902InstructionSetOps> Intel8080InstructionSetOps for I {
     fn wait_until_interrupt(&mut self) {
         self.wait_until_interrupt();
     }
+
+    /// The LR35902 writes the high byte first, one M-cycle before the low byte.
+    #[cfg_attr(feature = "aggressive-inline", inline(always))]
+    fn push_u16_onto_stack(&mut self, data: u16) {
+        let sp = self.read_register_pair(Intel8080Register::SP);
+        let [high, low] = data.to_be_bytes();
+        self.set_memory(sp.wrapping_sub(1), high);
+        self.set_memory(sp.wrapping_sub(2), low);
+        self.set_register_pair(Intel8080Register::SP, sp.wrapping_sub(2));
+    }
 }
 
 /*   ___              _____         _
@@ -758,6 +861,16 @@ fn perform_subtraction_sets_half_carry_flag() {
  *
  */
 
+/// `LD [HL+]` and friends read or write through HL at the same time as it is incremented or
+/// decremented.
+fn hl_access(src_register: Intel8080Register) -> BusAccess {
+    if src_register == Intel8080Register::M {
+        BusAccess::Read
+    } else {
+        BusAccess::Write
+    }
+}
+
 impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn move_and_increment_hl(
@@ -765,8 +878,9 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
         dest_register: Intel8080Register,
         src_register: Intel8080Register,
     ) {
-        LR35902InstructionSet::move_data(self, dest_register, src_register);
         let old_value = self.read_register_pair(Intel8080Register::H);
+        self.increment_decrement(old_value, hl_access(src_register));
+        LR35902InstructionSet::move_data(self, dest_register, src_register);
         self.set_register_pair(Intel8080Register::H, old_value.wrapping_add(1));
     }
 
@@ -776,8 +890,9 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
         dest_register: Intel8080Register,
         src_register: Intel8080Register,
     ) {
-        LR35902InstructionSet::move_data(self, dest_register, src_register);
         let old_value = self.read_register_pair(Intel8080Register::H);
+        self.increment_decrement(old_value, hl_access(src_register));
+        LR35902InstructionSet::move_data(self, dest_register, src_register);
         self.set_register_pair(Intel8080Register::H, old_value.wrapping_sub(1));
     }
 
@@ -843,12 +958,13 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn return_and_enable_interrupts(&mut self) {
         LR35902InstructionSet::return_unconditionally(self);
-        LR35902InstructionSet::enable_interrupts(self);
+        // Unlike EI, RETI sets IME right away.
+        self.set_interrupts_enabled(true);
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn halt_until_button_press(&mut self) {
-        unimplemented!();
+        self.wait_until_button_press();
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1094,7 +1210,8 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn enable_interrupts(&mut self) {
-        Intel8080InstructionSet::enable_interrupts(self)
+        // EI only takes effect after the instruction following it.
+        self.set_enabling_interrupts(true);
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1134,6 +1251,8 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn decrement_register_pair(&mut self, register: Intel8080Register) {
+        let address = self.read_register_pair(register);
+        self.increment_decrement(address, BusAccess::Idle);
         Intel8080InstructionSet::decrement_register_pair(self, register)
     }
 
@@ -1225,6 +1344,8 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn increment_register_pair(&mut self, register: Intel8080Register) {
+        let address = self.read_register_pair(register);
+        self.increment_decrement(address, BusAccess::Idle);
         Intel8080InstructionSet::increment_register_pair(self, register)
     }
 
@@ -1245,7 +1366,15 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn push_data_onto_stack(&mut self, register_pair: Intel8080Register) {
-        Intel8080InstructionSet::push_data_onto_stack(self, register_pair)
+        // SP is put on the bus once before the writes, and then again along with each of them.
+        let sp = self.read_register_pair(Intel8080Register::SP);
+        self.increment_decrement(sp, BusAccess::Idle);
+        let [high, low] = self.read_register_pair(register_pair).to_be_bytes();
+        self.increment_decrement(sp.wrapping_sub(1), BusAccess::Write);
+        self.set_memory(sp.wrapping_sub(1), high);
+        self.increment_decrement(sp.wrapping_sub(2), BusAccess::Write);
+        self.set_memory(sp.wrapping_sub(2), low);
+        self.set_register_pair(Intel8080Register::SP, sp.wrapping_sub(2));
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1260,7 +1389,13 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn pop_data_off_stack(&mut self, register_pair: Intel8080Register) {
-        Intel8080InstructionSet::pop_data_off_stack(self, register_pair)
+        let sp = self.read_register_pair(Intel8080Register::SP);
+        self.increment_decrement(sp, BusAccess::Read);
+        let low = self.read_memory(sp);
+        self.increment_decrement(sp.wrapping_add(1), BusAccess::Read);
+        let high = self.read_memory(sp.wrapping_add(1));
+        self.set_register_pair(Intel8080Register::SP, sp.wrapping_add(2));
+        self.set_register_pair(register_pair, u16::from_le_bytes([low, high]));
     }
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
@@ -1280,6 +1415,8 @@ impl<I: LR35902InstructionSetOps> LR35902InstructionSet for I {
 
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     fn disable_interrupts(&mut self) {
+        // DI also cancels an EI which hasn't taken effect yet.
+        self.set_enabling_interrupts(false);
         Intel8080InstructionSet::disable_interrupts(self)
     }
 
@@ -1596,6 +1733,7 @@ fn return_and_enable_interrupts() {
         e.return_and_enable_interrupts();
         assert_eq!(e.read_program_counter(), 0x0000);
         assert_eq!(e.read_register_pair(Intel8080Register::SP), 0x0402);
+        assert!(e.memory_accessor.interrupts_enabled);
     });
 }
 
@@ -2486,11 +2624,20 @@ impl LR35902Emulator {
         instruction: LR35902Instruction,
         memory_accessor: &mut M,
     ) {
-        let instruction_size = instruction.size();
+        let fetch_duration = Self::fetch_duration(&instruction);
         let total_duration = instruction.duration();
         let mut ops = InstructionDispatchOps::new(self, memory_accessor);
         instruction.dispatch(&mut ops);
-        self.add_cycles(total_duration - (instruction_size * 4));
+        self.add_cycles(total_duration - fetch_duration);
+    }
+
+    /// How long loading the instruction takes, one M-cycle per byte. STOP is two bytes long but
+    /// only takes the one M-cycle.
+    fn fetch_duration(instruction: &LR35902Instruction) -> u8 {
+        match instruction {
+            LR35902Instruction::HaltUntilButtonPress => 4,
+            _ => instruction.size() * 4,
+        }
     }
 
     #[cold]
@@ -2505,13 +2652,41 @@ impl LR35902Emulator {
         memory_accessor: &M,
     ) -> Option<LR35902Instruction> {
         debug_assert!(!self.halted);
+        debug_assert!(!self.stopped);
+
+        if self.halt_bug {
+            return self.load_instruction_after_halt_bug(memory_accessor);
+        }
 
         let pc = self.read_program_counter();
         let instr = LR35902Instruction::from_memory(memory_accessor, pc);
 
         if let Some(instr) = &instr {
             self.set_program_counter(pc + instr.size() as u16);
-            self.add_cycles(instr.size() * 4);
+            self.add_cycles(Self::fetch_duration(instr));
+        }
+        instr
+    }
+
+    /// Loads the instruction after the HALT bug. The opcode byte is also read as the byte after
+    /// it, and PC ends up one byte short.
+    #[cold]
+    fn load_instruction_after_halt_bug<M: MemoryAccessor>(
+        &mut self,
+        memory_accessor: &M,
+    ) -> Option<LR35902Instruction> {
+        self.halt_bug = false;
+
+        let pc = self.read_program_counter();
+        let memory_accessor = HaltBugMemoryAccessor {
+            memory_accessor,
+            opcode_address: pc,
+        };
+        let instr = LR35902Instruction::from_memory(&memory_accessor, pc);
+
+        if let Some(instr) = &instr {
+            self.set_program_counter(pc + instr.size() as u16 - 1);
+            self.add_cycles(Self::fetch_duration(instr));
         }
         instr
     }
@@ -2523,7 +2698,16 @@ impl LR35902Emulator {
         instr: LR35902Instruction,
     ) {
         debug_assert!(!self.halted);
+        debug_assert!(!self.stopped);
+
+        let enabling_interrupts = self.enabling_interrupts;
         self.run_lr35902_instruction(instr, memory_accessor);
+
+        // An EI before this instruction takes effect now, unless this instruction was a DI.
+        if enabling_interrupts && self.enabling_interrupts {
+            self.enabling_interrupts = false;
+            memory_accessor.set_interrupts_enabled(true);
+        }
     }
 
     pub fn run_one_instruction<M: MemoryAccessor>(&mut self, memory_accessor: &mut M) {
@@ -2557,5 +2741,146 @@ fn emulator_crashes_on_unkown_opcode() {
     assert_eq!(e.crash_message.unwrap(), "Unknown opcode at address 0");
 }
 
+#[test]
+fn enable_interrupts_takes_effect_after_next_instruction() {
+    let mut e = LR35902Emulator::new();
+    let mut memory_accessor = SimpleMemoryAccessor::new();
+    // EI, NOP
+    memory_accessor.memory[0..2].clone_from_slice(&[0xfb, 0x00]);
+    e.set_program_counter(0);
+
+    e.run_one_instruction(&mut memory_accessor);
+    assert!(!memory_accessor.interrupts_enabled);
+
+    e.run_one_instruction(&mut memory_accessor);
+    assert!(memory_accessor.interrupts_enabled);
+}
+
+#[test]
+fn disable_interrupts_cancels_pending_enable() {
+    let mut e = LR35902Emulator::new();
+    let mut memory_accessor = SimpleMemoryAccessor::new();
+    // EI, DI, NOP
+    memory_accessor.memory[0..3].clone_from_slice(&[0xfb, 0xf3, 0x00]);
+    e.set_program_counter(0);
+
+    for _ in 0..3 {
+        e.run_one_instruction(&mut memory_accessor);
+        assert!(!memory_accessor.interrupts_enabled);
+    }
+}
+
+#[test]
+fn halt_bug_reads_opcode_twice() {
+    let mut e = LR35902Emulator::new();
+    let mut memory_accessor = SimpleMemoryAccessor::new();
+    // LD A,$14
+    memory_accessor.memory[0..2].clone_from_slice(&[0x3e, 0x14]);
+    e.set_program_counter(0);
+    e.trigger_halt_bug();
+
+    e.run_one_instruction(&mut memory_accessor);
+    assert_eq!(e.read_register(Intel8080Register::A), 0x3e);
+    assert_eq!(e.read_program_counter(), 0x0001);
+
+    // The bug only affects the one instruction.
+    e.run_one_instruction(&mut memory_accessor);
+    assert_eq!(e.read_register(Intel8080Register::D), 0x01);
+    assert_eq!(e.read_program_counter(), 0x0002);
+}
+
+#[test]
+fn halt_bug_interrupt_returns_to_halt() {
+    let mut e = LR35902Emulator::new();
+    e.set_program_counter(0x0201);
+    e.trigger_halt_bug();
+    assert_eq!(e.interrupt_return_address(), 0x0200);
+    assert_eq!(e.interrupt_return_address(), 0x0201);
+}
+
+#[test]
+fn stop_takes_one_m_cycle() {
+    let mut e = LR35902Emulator::new();
+    let mut memory_accessor = SimpleMemoryAccessor::new();
+    // STOP
+    memory_accessor.memory[0..2].clone_from_slice(&[0x10, 0x00]);
+    e.set_program_counter(0);
+    let start = e.elapsed_cycles;
+
+    e.run_one_instruction(&mut memory_accessor);
+    assert!(e.is_stopped());
+    assert_eq!(e.read_program_counter(), 0x0002);
+    assert_eq!(e.elapsed_cycles - start, 4);
+}
+
+/// Counts the memory accesses an instruction makes, other than fetching it.
+#[cfg(test)]
+struct AccessCountingMemoryAccessor {
+    memory: SimpleMemoryAccessor,
+    accesses: core::cell::Cell<usize>,
+}
+
+#[cfg(test)]
+impl MemoryAccessor for AccessCountingMemoryAccessor {
+    fn read_memory(&self, address: u16) -> u8 {
+        self.accesses.set(self.accesses.get() + 1);
+        self.memory.read_memory(address)
+    }
+
+    fn set_memory(&mut self, address: u16, value: u8) {
+        self.accesses.set(self.accesses.get() + 1);
+        self.memory.set_memory(address, value);
+    }
+
+    fn set_interrupts_enabled(&mut self, enabled: bool) {
+        self.memory.set_interrupts_enabled(enabled);
+    }
+
+    fn describe_address(&self, address: u16) -> MemoryDescription {
+        self.memory.describe_address(address)
+    }
+}
+
+#[test]
+fn access_cycles_match_memory_accesses() {
+    let opcodes = (0x00..=0xFF).map(|op| vec![op, 0x00]);
+    let cb_opcodes = (0x00..=0xFF).map(|op| vec![0xCB, op]);
+    for opcode in opcodes.chain(cb_opcodes) {
+        let mut memory = SimpleMemoryAccessor::new();
+        memory.memory[0x100..0x102].clone_from_slice(&opcode);
+        let Some(instr) = LR35902Instruction::from_memory(&memory, 0x100) else {
+            continue;
+        };
+
+        // Conditional instructions only access memory when the condition holds, so try with the
+        // flags all set and all clear.
+        let mut most_accesses = 0;
+        for flags in [0x00, 0xF0] {
+            let mut e = LR35902Emulator::new();
+            e.set_register_pair(Intel8080Register::A, flags);
+            e.set_register_pair(Intel8080Register::H, 0xC000);
+            e.set_register_pair(Intel8080Register::SP, 0xD000);
+            let mut memory_accessor = AccessCountingMemoryAccessor {
+                memory: SimpleMemoryAccessor::new(),
+                accesses: Default::default(),
+            };
+            e.execute_instruction(&mut memory_accessor, instr.clone());
+            most_accesses = most_accesses.max(memory_accessor.accesses.get());
+        }
+
+        // The accesses come after the instruction is fetched, one per M-cycle.
+        let access_cycles = instr.access_cycles();
+        assert_eq!(most_accesses, access_cycles.len(), "{opcode:02x?}");
+        assert!(
+            access_cycles.windows(2).all(|w| w[0] < w[1]),
+            "{opcode:02x?}"
+        );
+        assert!(
+            access_cycles.iter().all(|&c| c >= instr.size()),
+            "{opcode:02x?}"
+        );
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests;
@@ -4361,6 +4361,2091 @@ impl LR35902Instruction {
         }
     }
 }
+impl LR35902Instruction {
+    #[doc = r" The M-cycles (counting from the opcode fetch as zero) in which the instruction"]
+    #[doc = r" reads or writes memory, in the order it does so."]
+    pub fn access_cycles(&self) -> &'static [u8] {
+        match self {
+            Self::NoOperation { .. } => &[],
+            Self::LoadRegisterPairImmediate {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::StoreAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[1u8],
+            Self::IncrementRegisterPair {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::RotateAccumulatorLeft { .. } => &[],
+            Self::StoreSpDirect { .. } => &[3u8, 4u8],
+            Self::DoubleAdd {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::LoadAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[1u8],
+            Self::DecrementRegisterPair {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::RotateAccumulatorRight { .. } => &[],
+            Self::HaltUntilButtonPress { .. } => &[],
+            Self::LoadRegisterPairImmediate {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::StoreAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[1u8],
+            Self::IncrementRegisterPair {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::RotateAccumulatorLeftThroughCarry { .. } => &[],
+            Self::JumpRelative { .. } => &[],
+            Self::DoubleAdd {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::LoadAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[1u8],
+            Self::DecrementRegisterPair {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::RotateAccumulatorRightThroughCarry { .. } => &[],
+            Self::JumpRelativeIfNotZero { .. } => &[],
+            Self::LoadRegisterPairImmediate {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveAndIncrementHl {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::A,
+                ..
+            } => &[1u8],
+            Self::IncrementRegisterPair {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::DecimalAdjustAccumulator { .. } => &[],
+            Self::JumpRelativeIfZero { .. } => &[],
+            Self::DoubleAdd {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveAndIncrementHl {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::DecrementRegisterPair {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ComplementAccumulator { .. } => &[],
+            Self::JumpRelativeIfNoCarry { .. } => &[],
+            Self::LoadRegisterPairImmediate {
+                register1: Intel8080Register::SP,
+                ..
+            } => &[],
+            Self::MoveAndDecrementHl {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::A,
+                ..
+            } => &[1u8],
+            Self::IncrementRegisterPair {
+                register1: Intel8080Register::SP,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8, 2u8],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8, 2u8],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::SetCarry { .. } => &[],
+            Self::JumpRelativeIfCarry { .. } => &[],
+            Self::DoubleAdd {
+                register1: Intel8080Register::SP,
+                ..
+            } => &[],
+            Self::MoveAndDecrementHl {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::DecrementRegisterPair {
+                register1: Intel8080Register::SP,
+                ..
+            } => &[],
+            Self::IncrementRegisterOrMemory {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::DecrementRegisterOrMemory {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveImmediateData {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ComplementCarry { .. } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::B,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::C,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::D,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::E,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::H,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::L,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::B,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::C,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::D,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::E,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::H,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::L,
+                ..
+            } => &[1u8],
+            Self::Halt { .. } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::M,
+                register2: Intel8080Register::A,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::MoveData {
+                register1: Intel8080Register::A,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::AddToAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::AddToAccumulatorWithCarry {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::SubtractFromAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::SubtractFromAccumulatorWithBorrow {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::LogicalAndWithAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::LogicalExclusiveOrWithAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::LogicalOrWithAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::M,
+                ..
+            } => &[1u8],
+            Self::CompareWithAccumulator {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ReturnIfNotZero { .. } => &[2u8, 3u8],
+            Self::PopDataOffStack {
+                register1: Intel8080Register::B,
+                ..
+            } => &[1u8, 2u8],
+            Self::JumpIfNotZero { .. } => &[],
+            Self::Jump { .. } => &[],
+            Self::CallIfNotZero { .. } => &[4u8, 5u8],
+            Self::PushDataOntoStack {
+                register1: Intel8080Register::B,
+                ..
+            } => &[2u8, 3u8],
+            Self::AddImmediateToAccumulator { .. } => &[],
+            Self::Restart { data1: 0u8, .. } => &[2u8, 3u8],
+            Self::ReturnIfZero { .. } => &[2u8, 3u8],
+            Self::ReturnUnconditionally { .. } => &[1u8, 2u8],
+            Self::JumpIfZero { .. } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::RotateRegisterLeft {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::RotateRegisterRight {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::RotateRegisterLeftThroughCarry {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::RotateRegisterRightThroughCarry {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ShiftRegisterLeft {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ShiftRegisterRightSigned {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SwapRegister {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SwapRegister {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ShiftRegisterRight {
+                register1: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 0u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 1u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 2u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 3u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 4u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 5u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 6u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8],
+            Self::TestBit {
+                data1: 7u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 0u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 1u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 2u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 3u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 4u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 5u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 6u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::ResetBit {
+                data1: 7u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 0u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 1u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 2u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 3u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 4u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 5u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 6u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::B,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::C,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::D,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::E,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::H,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::L,
+                ..
+            } => &[],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::M,
+                ..
+            } => &[2u8, 3u8],
+            Self::SetBit {
+                data1: 7u8,
+                register2: Intel8080Register::A,
+                ..
+            } => &[],
+            Self::CallIfZero { .. } => &[4u8, 5u8],
+            Self::Call { .. } => &[4u8, 5u8],
+            Self::AddImmediateToAccumulatorWithCarry { .. } => &[],
+            Self::Restart { data1: 1u8, .. } => &[2u8, 3u8],
+            Self::ReturnIfNoCarry { .. } => &[2u8, 3u8],
+            Self::PopDataOffStack {
+                register1: Intel8080Register::D,
+                ..
+            } => &[1u8, 2u8],
+            Self::JumpIfNoCarry { .. } => &[],
+            Self::CallIfNoCarry { .. } => &[4u8, 5u8],
+            Self::PushDataOntoStack {
+                register1: Intel8080Register::D,
+                ..
+            } => &[2u8, 3u8],
+            Self::SubtractImmediateFromAccumulator { .. } => &[],
+            Self::Restart { data1: 2u8, .. } => &[2u8, 3u8],
+            Self::ReturnIfCarry { .. } => &[2u8, 3u8],
+            Self::ReturnAndEnableInterrupts { .. } => &[1u8, 2u8],
+            Self::JumpIfCarry { .. } => &[],
+            Self::CallIfCarry { .. } => &[4u8, 5u8],
+            Self::SubtractImmediateFromAccumulatorWithBorrow { .. } => &[],
+            Self::Restart { data1: 3u8, .. } => &[2u8, 3u8],
+            Self::StoreAccumulatorDirectOneByte { .. } => &[2u8],
+            Self::PopDataOffStack {
+                register1: Intel8080Register::H,
+                ..
+            } => &[1u8, 2u8],
+            Self::StoreAccumulatorOneByte { .. } => &[1u8],
+            Self::PushDataOntoStack {
+                register1: Intel8080Register::H,
+                ..
+            } => &[2u8, 3u8],
+            Self::AndImmediateWithAccumulator { .. } => &[],
+            Self::Restart { data1: 4u8, .. } => &[2u8, 3u8],
+            Self::AddImmediateToSp { .. } => &[],
+            Self::LoadProgramCounter { .. } => &[],
+            Self::StoreAccumulatorDirect { .. } => &[3u8],
+            Self::ExclusiveOrImmediateWithAccumulator { .. } => &[],
+            Self::Restart { data1: 5u8, .. } => &[2u8, 3u8],
+            Self::LoadAccumulatorDirectOneByte { .. } => &[2u8],
+            Self::PopDataOffStack {
+                register1: Intel8080Register::PSW,
+                ..
+            } => &[1u8, 2u8],
+            Self::LoadAccumulatorOneByte { .. } => &[1u8],
+            Self::DisableInterrupts { .. } => &[],
+            Self::PushDataOntoStack {
+                register1: Intel8080Register::PSW,
+                ..
+            } => &[2u8, 3u8],
+            Self::OrImmediateWithAccumulator { .. } => &[],
+            Self::Restart { data1: 6u8, .. } => &[2u8, 3u8],
+            Self::StoreSpPlusImmediate { .. } => &[],
+            Self::LoadSpFromHAndL { .. } => &[],
+            Self::LoadAccumulatorDirect { .. } => &[3u8],
+            Self::EnableInterrupts { .. } => &[],
+            Self::CompareImmediateWithAccumulator { .. } => &[],
+            Self::Restart { data1: 7u8, .. } => &[2u8, 3u8],
+            instr => panic!("invalid instruction {:?}", instr),
+        }
+    }
+}
 pub trait LR35902InstructionSet {
     fn add_immediate_to_accumulator(&mut self, data1: u8);
     fn add_immediate_to_accumulator_with_carry(&mut self, data1: u8);
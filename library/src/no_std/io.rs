@@ -15,6 +15,14 @@ pub enum Error {
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::UnexpectedEof(m) | Self::Other(m) => write!(f, "{m}"),
+        }
+    }
+}
+
 pub trait Read {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
 
@@ -36,6 +36,10 @@ fn try_translate_keycode(code: Keycode) -> Result<egui::Key, &'static str> {
         Keycode::F2 => Ok(egui::Key::F2),
         Keycode::F3 => Ok(egui::Key::F3),
         Keycode::F4 => Ok(egui::Key::F4),
+        Keycode::I => Ok(egui::Key::I),
+        Keycode::J => Ok(egui::Key::J),
+        Keycode::K => Ok(egui::Key::K),
+        Keycode::L => Ok(egui::Key::L),
         Keycode::Left => Ok(egui::Key::ArrowLeft),
         Keycode::Return => Ok(egui::Key::Enter),
         Keycode::Right => Ok(egui::Key::ArrowRight),
@@ -26,6 +26,10 @@ pub enum Keycode {
     F2,
     F3,
     F4,
+    I,
+    J,
+    K,
+    L,
     Left,
     Return,
     Right,
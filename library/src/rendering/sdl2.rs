@@ -19,6 +19,10 @@ impl From<sdl2::keyboard::Keycode> for Keycode {
             Keycode::F2 => Self::F2,
             Keycode::F3 => Self::F3,
             Keycode::F4 => Self::F4,
+            Keycode::I => Self::I,
+            Keycode::J => Self::J,
+            Keycode::K => Self::K,
+            Keycode::L => Self::L,
             _ => Self::Unknown,
         }
     }
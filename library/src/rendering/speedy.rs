@@ -67,6 +67,10 @@ fn keycode_translate(keycode: VirtualKeyCode) -> Keycode {
         VirtualKeyCode::F2 => Keycode::F2,
         VirtualKeyCode::F3 => Keycode::F3,
         VirtualKeyCode::F4 => Keycode::F4,
+        VirtualKeyCode::I => Keycode::I,
+        VirtualKeyCode::J => Keycode::J,
+        VirtualKeyCode::K => Keycode::K,
+        VirtualKeyCode::L => Keycode::L,
         _ => Keycode::Unknown,
     }
 }
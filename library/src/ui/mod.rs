@@ -11,6 +11,7 @@ const GITHUB_URL: &str = "https://github.com/bobbobbio/come_boy";
 pub trait EmulatorUiHandler {
     fn load_rom_from_dialog(&mut self);
     fn loaded_rom(&mut self) -> Option<&str>;
+    fn load_error(&mut self) -> Option<&str>;
     fn palette_mut(&mut self) -> &mut Palette;
     fn meta(&mut self, name: &str) -> String;
 }
@@ -54,6 +55,9 @@ pub fn render_main_gui(ui: &mut egui::Ui, emulator: &mut impl EmulatorUiHandler)
             if let Some(loaded_rom) = emulator.loaded_rom() {
                 ui.label(format!("playing: {loaded_rom}"));
             }
+            if let Some(load_error) = emulator.load_error() {
+                ui.colored_label(egui::Color32::RED, load_error);
+            }
         });
         ui.collapsing("pallete", |ui| {
             let palette = emulator.palette_mut();
@@ -212,6 +212,13 @@ impl<T> Scheduler<T> {
         None
     }
 
+    /// Moves the current time forward without delivering any events.
+    pub fn advance(&mut self, now: u64) {
+        assert!(now >= self.now);
+        assert!(self.peek_time().is_none_or(|time| time >= now));
+        self.now = now;
+    }
+
     #[cfg_attr(feature = "aggressive-inline", inline(always))]
     pub fn peek_time(&self) -> Option<u64> {
         self.timeline.front().map(|e| e.time)
@@ -46,6 +46,10 @@ impl JoyPad for PicoJoyPad {
         }
         self.inner.respond_to_events(events);
     }
+
+    fn take_interrupt(&mut self) -> bool {
+        self.inner.take_interrupt()
+    }
 }
 
 #[derive(IntoEnumIterator, Copy, Clone)]
@@ -2,7 +2,7 @@ use super::storage::WebStorage;
 use super::window;
 use come_boy::game_boy_emulator::{
     rom_hash, ControllerJoyPad, GameBoyEmulator, GameBoyOps, GamePak, Palette, UserControl,
-    SLEEP_INPUT_TICKS,
+    WallClock, SLEEP_INPUT_TICKS,
 };
 use come_boy::rendering::egui::EguiBackRenderer;
 use come_boy::sound::cpal::CpalSoundStream;
@@ -24,6 +24,15 @@ fn f64_saturating_sub(a: f64, b: f64) -> f64 {
     }
 }
 
+/// `SystemTime` isn't available in the browser, so the cartridge clock uses `Date` instead.
+struct DateWallClock;
+
+impl WallClock for DateWallClock {
+    fn now(&self) -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+}
+
 struct Underclocker {
     start_cycles: u64,
     start_time: f64,
@@ -58,6 +67,7 @@ pub struct Emulator {
     ops: GameBoyOps<EguiBackRenderer, CpalSoundStream, WebStorage>,
     underclocker: Underclocker,
     running: bool,
+    load_error: Option<String>,
 }
 
 impl Emulator {
@@ -74,15 +84,28 @@ impl Emulator {
             ops,
             underclocker,
             running: false,
+            load_error: None,
         }
     }
 
     pub fn load_rom(&mut self, rom: &[u8]) {
-        self.emulator = GameBoyEmulator::new();
         let sram_key = format!("{:x}", rom_hash(rom));
-        let game_pak = GamePak::new(rom, &mut self.ops.storage, Some(&sram_key)).unwrap();
+        let mut game_pak = match GamePak::new(rom, &mut self.ops.storage, Some(&sram_key)) {
+            Ok(game_pak) => game_pak,
+            Err(error) => {
+                // Keep running whatever was loaded before
+                self.load_error = Some(format!("failed to load ROM: {error}"));
+                return;
+            }
+        };
+        self.load_error = None;
+        self.emulator = GameBoyEmulator::new();
+        game_pak.set_wall_clock(DateWallClock);
+        let mut joypad = ControllerJoyPad::new();
+        game_pak.set_rumble(joypad.rumble());
+        game_pak.set_tilt_sensor(joypad.tilt());
         self.ops.load_game_pak(game_pak);
-        self.ops.plug_in_joy_pad(ControllerJoyPad::new());
+        self.ops.plug_in_joy_pad(joypad);
         self.underclocker =
             Underclocker::new(self.emulator.elapsed_cycles(), self.ops.clock_speed_hz);
         self.running = true;
@@ -92,6 +115,10 @@ impl Emulator {
         self.ops.loaded_game_pak().map(|gp| gp.title())
     }
 
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
     pub fn palette_mut(&mut self) -> &mut Palette {
         self.emulator.palette_mut()
     }
@@ -163,6 +163,10 @@ impl come_boy::ui::EmulatorUiHandler for BorrowedEmulator<'_> {
         self.ref_mut.loaded_rom()
     }
 
+    fn load_error(&mut self) -> Option<&str> {
+        self.ref_mut.load_error()
+    }
+
     fn palette_mut(&mut self) -> &mut Palette {
         self.ref_mut.palette_mut()
     }